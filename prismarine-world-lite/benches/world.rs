@@ -0,0 +1,41 @@
+// benches/world.rs
+// Criterion suite for the native hot paths. Runs on generated terrain, plus any captured `map_chunk` packet data
+// dropped into `benches/fixtures/` as `<chunkX>_<chunkZ>.bin`.
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use prismarine_world_lite::bench::{Fixture, FIXTURE_VERSION};
+use std::path::Path;
+
+fn fixture() -> Fixture {
+    let mut fixture = Fixture::new(FIXTURE_VERSION).expect("Failed to create the fixture world");
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures");
+    if dir.is_dir() {
+        fixture.add_packet_dir(&dir).expect("Failed to read packet fixtures");
+    }
+    fixture.load_columns().expect("Failed to load fixture columns");
+    fixture
+}
+
+fn world_benches(c: &mut Criterion) {
+    let fixture = fixture();
+    let mut group = c.benchmark_group("world");
+
+    group.throughput(Throughput::Elements(fixture.column_count() as u64));
+    group.bench_function("chunk_parse", |b| b.iter(|| fixture.load_columns().unwrap()));
+
+    group.throughput(Throughput::Elements(fixture.ray_count() as u64));
+    group.bench_function("raycast_terrain", |b| b.iter(|| fixture.raycast_terrain()));
+
+    group.throughput(Throughput::Elements(fixture.find_blocks_volume() as u64));
+    group.bench_function("find_blocks", |b| b.iter(|| fixture.find_blocks()));
+
+    let mut layer = 0;
+    group.throughput(Throughput::Elements(32 * 16 * 32));
+    group.bench_function("bulk_set", |b| b.iter(|| {
+        layer += 1;
+        fixture.bulk_set(layer).unwrap()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, world_benches);
+criterion_main!(benches);
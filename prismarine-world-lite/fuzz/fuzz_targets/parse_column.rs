@@ -0,0 +1,19 @@
+// fuzz/fuzz_targets/parse_column.rs
+// Feeds arbitrary bytes to the chunk parser as if a server had sent them. Parsing may fail, but must not panic,
+// and every block of a parsed column must be readable.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use prismarine_world_lite::fuzz::parse_column_bytes;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(column) = parse_column_bytes(data) {
+        for (_, section) in column.sections() {
+            let mut states = [0u32; 4096];
+            section.block_states().unpack_into(&mut states);
+            for index in 0..64 {
+                section.biomes().get(index);
+            }
+        }
+    }
+});
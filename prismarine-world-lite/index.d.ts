@@ -3,6 +3,13 @@
 
 /* auto-generated by NAPI-RS */
 
+export class ExternalObject<T> {
+  readonly '': {
+    readonly '': unique symbol
+    [K: symbol]: T
+  }
+}
+
 export interface WorldCoords {
   x: number
   y: number
@@ -14,6 +21,25 @@ export interface BlockInfo {
   skyLight: number
   biomeId: number
 }
+export interface BlockNeighbors {
+  block: BlockInfo
+  bottom?: BlockInfo
+  top?: BlockInfo
+  north?: BlockInfo
+  south?: BlockInfo
+  west?: BlockInfo
+  east?: BlockInfo
+}
+export interface FlatLayer {
+  stateId: number
+  height: number
+}
+export interface BiomeInfo {
+  id: number
+  name: string
+  temperature: number
+  rainfall: number
+}
 export const enum BlockFace {
   Bottom = 0,
   Top = 1,
@@ -26,42 +52,1334 @@ export interface RaycastResult {
   position: WorldCoords
   face: number
   intersectPoint: Vec3Arg
+  exitedLoadedTerrain: boolean
+  distance: number
+  enteredInside: boolean
+  shape?: HitShape
+}
+export interface HitShape {
+  index: number
+  min: Vec3Arg
+  max: Vec3Arg
+}
+export interface TraceShape {
+  min: Vec3Arg
+  max: Vec3Arg
+  hitT?: number
+}
+export interface TraceVoxel {
+  position: WorldCoords
+  t: number
+  stateId: number
+  loaded: boolean
+  shapes: Array<TraceShape>
+}
+export interface RaycastTrace {
+  hit?: RaycastResult
+  exitedLoadedTerrain: boolean
+  voxels: Array<TraceVoxel>
+}
+/** A block raycast's result whether or not it hit anything */
+export interface RaycastOutcome {
+  hit?: RaycastResult
+  exitedLoadedTerrain: boolean
 }
 export interface Vec3Arg {
   x: number
   y: number
   z: number
 }
+export interface AabbArg {
+  min: Vec3Arg
+  max: Vec3Arg
+}
+export interface BiomeSearchResult {
+  position: WorldCoords
+  biomeId: number
+  distance: number
+}
+export interface FluidLevel {
+  blockY: number
+  surfaceY: number
+  fluid: string
+}
+export interface PathOptions {
+  maxNodes?: number
+  allowDiagonal?: boolean
+  maxJumpHeight?: number
+  maxFallHeight?: number
+}
+export interface PathResult {
+  status: string
+  path: Array<WorldCoords>
+  cost: number
+  nodesExplored: number
+}
+export interface AgentSize {
+  width?: number
+  height?: number
+}
+export interface WalkOptions {
+  width?: number
+  height?: number
+  stepHeight?: number
+}
+export interface StandPosition {
+  position: WorldCoords
+  feetY: number
+}
+export interface LandingPosition {
+  position: Vec3Arg
+  block: WorldCoords
+  fallDistance: number
+}
+export interface NavCell {
+  position: WorldCoords
+  feetY: number
+}
+export interface NavLink {
+  from: number
+  to: WorldCoords
+  cost: number
+}
+export interface NavmeshData {
+  cells: Array<NavCell>
+  links: Array<NavLink>
+}
+export interface EnchantmentArg {
+  name: string
+  level: number
+}
+export interface DigOptions {
+  toolName?: string
+  enchantments?: Array<EnchantmentArg>
+  efficiencyLevel?: number
+  hasteLevel?: number
+  miningFatigueLevel?: number
+  inWater?: boolean
+  onGround?: boolean
+}
+export interface SectionPosition {
+  chunkX: number
+  sectionY: number
+  chunkZ: number
+}
+export interface BlockUpdate {
+  x: number
+  y: number
+  z: number
+  stateId: number
+}
+/** An inclusive range of chunk coordinates. */
+export interface ChunkRange {
+  minX: number
+  minZ: number
+  maxX: number
+  maxZ: number
+}
+/** Identifies native world state shared with `share`. Plain data, so it can be posted to worker threads. */
+export interface ExternalHandle {
+  id: number
+}
+export interface ChunkBatchResult {
+  chunkCount: number
+  parseMs: number
+  chunksPerTick: number
+}
+export interface SectionStats {
+  sectionY: number
+  paletteType: string
+  paletteSize: number
+  bitsPerValue: number
+  nonAirCount: number
+  solidBlockCount: number
+  biomePaletteType: string
+  biomePaletteSize: number
+}
+export interface ColumnStats {
+  sectionCount: number
+  sections: Array<SectionStats>
+}
+export interface PaletteDump {
+  paletteType: string
+  bitsPerValue: number
+  palette: Array<number>
+  data: Array<string>
+}
+export interface SectionPalette {
+  paletteType: string
+  palette: Array<number>
+  bitsPerValue: number
+}
+export interface SectionDump {
+  solidBlockCount: number
+  blockStates: PaletteDump
+  biomes: PaletteDump
+}
+export interface ValidationIssue {
+  chunkX: number
+  sectionY: number
+  chunkZ: number
+  kind: string
+  message: string
+}
+export interface ValidationReport {
+  valid: boolean
+  columnsChecked: number
+  sectionsChecked: number
+  issues: Array<ValidationIssue>
+}
+export interface SurfaceBlock {
+  x: number
+  y: number
+  z: number
+  stateId: number
+}
+export interface LightSource {
+  x: number
+  y: number
+  z: number
+  stateId: number
+  level: number
+}
+export interface ChunkPosition {
+  chunkX: number
+  chunkZ: number
+}
+/**
+ * An entity to test rays against. Its hitbox is `width` wide on X and Z, centered on `position`, and `height` tall
+ * from there up.
+ */
+export interface EntityHitbox {
+  id: number
+  position: Vec3Arg
+  name?: string
+  width?: number
+  height?: number
+  yaw?: number
+}
+export interface EntityRaycastResult {
+  id: number
+  intersectPoint: Vec3Arg
+  distance: number
+  face: number
+  enteredInside: boolean
+}
+export interface PlacementOptions {
+  requireSupport?: boolean
+  entities?: Array<AabbArg>
+}
+export interface PlacementCheck {
+  canPlace: boolean
+  reason?: string
+  against?: WorldCoords
+  face?: number
+}
+export interface ScaffoldStep {
+  position: WorldCoords
+  against: WorldCoords
+  face: number
+}
+export interface SchematicDiffEntry {
+  position: WorldCoords
+  expected: number
+  actual: number
+}
+export interface SchematicDiff {
+  missing: Array<SchematicDiffEntry>
+  wrong: Array<SchematicDiffEntry>
+  extra: Array<SchematicDiffEntry>
+}
+/** An entity stored in a structure template. Entities aren't spawned by pasting; they're listed for the caller. */
+export interface SchematicEntity {
+  name: string
+  position: Vec3Arg
+}
+/** How to transform a schematic when placing or comparing it: mirrored first, then rotated clockwise around Y. */
+export interface TransformOptions {
+  rotation?: number
+  mirror?: string
+}
+/** The blocks making up a tree found by `detect_tree` */
+export interface TreeBlocks {
+  logs: Array<WorldCoords>
+  leaves: Array<WorldCoords>
+}
+export interface HarvestableCrop {
+  position: WorldCoords
+  crop: string
+  stateId: number
+}
+export interface SignText {
+  front: Array<string>
+  back: Array<string>
+  waxed: boolean
+}
+export interface ContainerItem {
+  slot: number
+  name: string
+  count: number
+}
+/** What happened to a block write. */
+export interface BlockWriteResult {
+  status: string
+  previousStateId?: number
+  createdColumn: boolean
+}
+/** Options for `raycast_ex`. Everything but `max_distance` is optional. */
+export interface RaycastOptions {
+  maxDistance: number
+  matcher?: Array<number | string>
+  fluidMode?: 'none' | 'source' | 'any'
+  shapeSource?: 'collision' | 'outline' | 'full'
+  includeEntities?: Array<EntityHitbox>
+  unloadedChunks?: 'air' | 'solid' | 'stop'
+  returnAll?: boolean
+  minY?: number
+  maxY?: number
+}
+/** A block or entity hit by `raycast_ex`. */
+export interface RaycastExHit {
+  position?: WorldCoords
+  stateId?: number
+  entityId?: number
+  face: number
+  intersectPoint: Vec3Arg
+  distance: number
+  enteredInside: boolean
+  exitedLoadedTerrain: boolean
+  shape?: HitShape
+}
+/** Construction-time settings of a world, for `World.withOptions`. */
+export interface WorldOptions {
+  version: string
+  minY?: number
+  dimensionHeight?: number
+  strictParsing?: boolean
+  maxLoadedColumns?: number
+  emitEvents?: boolean
+}
+/** Counts and timings of a world's native work since it was created or `reset_perf_counters` was called. */
+export interface PerfReport {
+  chunkParses: number
+  chunkParseMs: number
+  raycasts: number
+  raycastMs: number
+  blockGets: number
+  blockSets: number
+  lockContentions: number
+  columnCacheHits: number
+  columnCacheMisses: number
+  columnCacheHitRate: number
+}
+/** Timing of one self-benchmark workload. */
+export interface BenchmarkResult {
+  name: string
+  iterations: number
+  operations: number
+  totalMs: number
+  meanMs: number
+  nsPerOp: number
+}
+export interface ColdColumnStats {
+  coldColumns: number
+  compressedBytes: number
+  compressions: number
+  thaws: number
+}
+export interface BlockBox {
+  min: WorldCoords
+  max: WorldCoords
+}
+/** Reads to service under the locks of `withColumns`. */
+export interface ColumnReads {
+  /** Block positions to read, as x, y, z triples. */
+  positions?: Int32Array
+  /** Inclusive block boxes to read whole, e.g. the blocks around an entity's bounding box. */
+  boxes?: Array<BlockBox>
+}
+export interface ColumnReadResult {
+  /** Whether each requested column was loaded, in request order. */
+  loaded: Array<boolean>
+  /** State IDs at `positions`, in order. */
+  states: Uint32Array
+  /** State IDs of each box, indexed `((y - min.y) * sizeZ + (z - min.z)) * sizeX + (x - min.x)`. */
+  boxes: Array<Uint32Array>
+}
+export interface SurfaceHit {
+  x: number
+  y: number
+  z: number
+  stateId: number
+  surfaceY: number
+}
+export interface VisibilityResult {
+  visibleFraction: number
+  visibleSamples: number
+  samples: number
+  firstVisiblePoint?: Vec3Arg
+}
+export interface ConeRay {
+  direction: Vec3Arg
+  hit?: RaycastResult
+}
+/** Unit offset to the neighbor on the given side of a block, e.g. `{ x: 0, y: 1, z: 0 }` for `Top`. */
+export declare function faceToOffset(face: BlockFace): WorldCoords
+/**
+ * The position `distance` blocks (1 by default) out through the given face of `pos`, e.g. the block a
+ * placement against a raycast hit's face lands in.
+ */
+export declare function offsetPosition(pos: { x: number, y: number, z: number }, face: BlockFace, distance?: number | null): WorldCoords
+/** The face on the other side of a block: `Top` for `Bottom`, `West` for `East` and so on. */
+export declare function oppositeFace(face: BlockFace): BlockFace
+/**
+ * The face whose normal points closest to the given vector, e.g. the side of a block facing a viewer when
+ * passed the direction to them. Ties go to the X faces, then the Y faces.
+ */
+export declare function faceFromVector(dx: number, dy: number, dz: number): BlockFace
+export interface LookAngles {
+  yaw: number
+  pitch: number
+}
 export type NapiWorld = World
+/**
+ * Unit view direction for a Minecraft yaw and pitch in degrees (yaw 0 faces +Z and 90 faces -X, positive pitch
+ * looks down), e.g. to raycast along an entity's line of sight.
+ */
+export declare function directionFromLook(yaw: number, pitch: number): Vec3Arg
+/**
+ * Yaw and pitch in Minecraft's conventions (see `directionFromLook`) to look from one point at another, e.g.
+ * the eye position and a block face center to aim at. Looking straight up or down gives a yaw of -90, like
+ * the game.
+ */
+export declare function lookAt(from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }): LookAngles
+/**
+ * Packs chunk coordinates into a signed 64-bit key, x in the high 32 bits and z in the low 32 bits, i.e.
+ * `(BigInt(x) << 32n) | (BigInt(z) & 0xffffffffn)`. Keys can key `Map`s and `Set`s of columns and be passed to
+ * the column methods in place of `chunkX, chunkZ`.
+ */
+export declare function chunkKey(chunkX: number, chunkZ: number): bigint
+/** Unpacks a key made by `chunkKey` into its chunk coordinates. */
+export declare function parseChunkKey(key: bigint): ChunkPosition
 export declare class World {
   static withVersion(versionString: string): NapiWorld
+  /**
+   * Creates a world that reads Bedrock Edition sub-chunks (see `load_sub_chunk`), so Bedrock bots can use the same
+   * queries and raycaster. Blocks are stored as state IDs of the Java `version_string`, which provides the block data;
+   * `runtime_ids[runtimeId]` gives the state ID for each Bedrock runtime ID. Runtime IDs outside the map become air.
+   */
+  static withBedrockRuntimeIds(versionString: string, runtimeIds: Uint32Array): NapiWorld
+  /**
+   * Creates a world for the version operating on the column storage of another world (see `columns_handle`).
+   * Both worlds see each other's loaded (and compressed) columns and block writes; events, watchers and caches
+   * stay separate.
+   */
+  static fromColumns(versionString: string, columns: ExternalObject<unknown>): NapiWorld
+  /** Gets a handle to this world's column storage, for `World.fromColumns`. */
+  columnsHandle(): ExternalObject<unknown>
+  /** Opens a world shared with `share`, from any thread of the process. */
+  static fromHandle(handle: ExternalHandle): NapiWorld
+  /**
+   * Forgets a shared handle so it can't be opened anymore. Worlds already opened from it keep working.
+   * Returns false if the handle was unknown.
+   */
+  static releaseHandle(handle: ExternalHandle): boolean
   /** Loads chunk column data from a network buffer (like `map_chunk` packet data). */
   loadColumn(chunkX: number, chunkZ: number, dataBuffer: Buffer): void
-  /** Unloads a chunk column. */
-  unloadColumn(chunkX: number, chunkZ: number): void
+  /**
+   * Replaces one section of a loaded column with a lone section payload (block count, block states, biomes),
+   * as sent by protocols that resend single sections. Blocks that differ are reported like any other block change.
+   */
+  loadSection(chunkX: number, sectionY: number, chunkZ: number, dataBuffer: Buffer): void
+  /** Unloads a chunk column, given by coordinates or a `chunkKey`. */
+  unloadColumn(chunkX: number | bigint, chunkZ?: number | undefined | null): void
+  /**
+   * Ensures a column is loaded at the coordinates (or `chunkKey`), inserting an empty (all air) one if missing.
+   * Returns true if a column was created. Useful when serving chunks that were never received.
+   */
+  getOrCreateColumn(chunkX: number | bigint, chunkZ?: number | undefined | null): boolean
+  /**
+   * Loads a column made of horizontal layers stacked from the bottom of the world (first layer lowest),
+   * replacing any column already loaded there. Everything above the layers is air.
+   */
+  createFlatColumn(chunkX: number, chunkZ: number, layers: Array<FlatLayer>): void
   /** Gets the state ID of the block at the given world coordinates. */
   getBlockStateId(x: number, y: number, z: number): number
-  /** Sets the state ID of the block at the given world coordinates. */
-  setBlockStateId(x: number, y: number, z: number, stateId: number): void
+  /**
+   * Sets the state ID of the block at the given world coordinates, returning whether the block changed.
+   * While a transaction is open the write is staged until `commit`. Fails if the position is outside the world
+   * or its chunk isn't loaded (see `try_set_block_state_id` and `set_unloaded_write_mode`).
+   */
+  setBlockStateId(x: number, y: number, z: number, stateId: number): boolean
   /** Gets a simplified block object (stateId, light, skyLight, biomeId). */
   getBlock(x: number, y: number, z: number): BlockInfo | null
+  /**
+   * Gets the block at the given world coordinates (like `get_block`) together with its six neighbors in one
+   * call, reading across chunk borders with one lock per chunk, waiting out concurrent writes to them. Neighbors in
+   * unloaded chunks are `null`, and so is the result if the block's own chunk isn't loaded.
+   */
+  getBlockWithNeighbors(x: number, y: number, z: number): BlockNeighbors | null
   /** Gets the block light level at the given world coordinates. */
   getBlockLight(x: number, y: number, z: number): number
   /** Gets the sky light level at the given world coordinates. */
   getSkyLight(x: number, y: number, z: number): number
   /** Gets the biome ID at the given world coordinates. */
   getBiomeId(x: number, y: number, z: number): number
+  /**
+   * Gets the biome at the given world coordinates resolved through minecraft-data (id, name, temperature, rainfall).
+   * Returns `null` if the chunk isn't loaded or the biome ID is unknown for this version.
+   */
+  getBiome(x: number, y: number, z: number): BiomeInfo | null
   /**
    * Performs a raycast from the origin in the given direction.
    *
    * Args:
    * - `origin`: `{ x: number, y: number, z: number }` - The starting point of the ray.
    * - `direction`: `{ x: number, y: number, z: number }` - The direction vector of the ray (should be normalized).
-   * - `max_distance`: `number` - The maximum distance the ray should travel.
-   * - `intersect_non_solid_block_names`: `string[] | null` - Optional array of block names (e.g., "water", "grass") to intersect even if they are not solid.
+   * - `max_distance`: `number` - The maximum Euclidean distance from the origin to the hit point; anything further is ignored.
+   * - `intersect_non_solid_block_names`: `string[] | null` - Optional array of block names (e.g., "water", "grass") or block tags (e.g., "#minecraft:flowers") to intersect even if they are not solid.
+   * - `unloaded_chunks`: `'air' | 'solid' | 'stop' | null` - How blocks in unloaded chunks are treated (defaults to `'air'`).
+   *   With `'solid'` they are hit like full cubes, with `'stop'` the ray ends at the first unloaded block and reports it.
+   * - `min_y` / `max_y`: `number | null` - Optional inclusive block Y bounds within the dimension (its range by default); the ray stops once it leaves them for good.
    *
    * Returns:
-   * - `object | null`: An object containing `position` (block coords), `face` (number), and `intersect_point` (exact coords), or `null` if no intersection is found within the distance.
+   * - `object | null`: An object containing `position` (block coords), `face` (number), `intersect_point` (exact coords),
+   *   `exited_loaded_terrain` (whether the ray crossed an unloaded chunk), `distance` (from the origin to `intersect_point`),
+   *   `entered_inside` (whether the origin is inside the hit box, e.g. standing in a snow layer; the hit is then at the origin),
+   *   and `shape` (`index`, `min` and `max` of the collision box that was hit, relative to the block; `null` for blocks hit as
+   *   full cubes),
+   *   or `null` if no intersection is found within the distance. Use `raycastOutcome` to learn whether a ray that
+   *   missed crossed unloaded chunks.
+   */
+  raycast(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): RaycastResult | null
+  /**
+   * Performs the same raycast as `raycast` (with the same arguments), returning `{ hit, exitedLoadedTerrain }`
+   * so that a miss still tells whether the ray crossed unloaded chunks. `hit` is `null` on a miss.
+   */
+  raycastOutcome(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): RaycastOutcome
+  /**
+   * Performs the same raycast as `raycast` (with the same arguments), but also returns every voxel the ray
+   * visited, in order, with the distance at which it entered the voxel and each box tested against the ray.
+   * Meant for visualizing why a ray hit or missed something.
+   */
+  raycastDebug(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): RaycastTrace
+  /**
+   * Finds a point on the target block that is visible from `eye_pos`, for aiming at partially occluded blocks.
+   *
+   * Face centers are tried first, then points near the corners of each face turned towards the eye.
+   * Returns `null` if every sampled point is blocked or further away than `max_distance` (defaults to unlimited).
+   */
+  getVisiblePoint(eye_pos: { x: number, y: number, z: number }, target_block: { x: number, y: number, z: number }, max_distance?: number | null): Vec3Arg | null
+  /**
+   * Calculates how exposed a bounding box is to an explosion, matching vanilla's `Explosion.getSeenPercent`.
+   *
+   * Rays are cast from a grid of points across the box towards the explosion; the result is the
+   * fraction (0 to 1) of rays that reach it without hitting a block. Damage scales linearly with this value.
+   */
+  getExplosionExposure(explosion_pos: { x: number, y: number, z: number }, target_aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }): number
+  /**
+   * Checks whether a hostile mob could naturally spawn with its feet at the given position.
+   *
+   * The block below must be a solid, non-transparent block and the feet and head blocks must be free of collision boxes and fluids.
+   */
+  isSpawnable(x: number, y: number, z: number): boolean
+  /** Finds every spawnable position inside the region (inclusive corners), see `is_spawnable`. */
+  findSpawnablePositions(min: WorldCoords, max: WorldCoords): Array<WorldCoords>
+  /**
+   * Sets the biome of every 4x4x4 biome cell touched by the region (inclusive corners).
+   * Cells in unloaded chunks are skipped. Returns the number of cells written.
+   */
+  setBiomesInRegion(min: WorldCoords, max: WorldCoords, biomeId: number): number
+  /**
+   * Exports the biome IDs of every 4x4x4 biome cell touched by the region (inclusive corners).
+   *
+   * Values are ordered Y, then Z, then X (X varies fastest), with the dimensions being the
+   * number of cells along each axis (`(max >> 2) - (min >> 2) + 1`). Unloaded cells are 0.
+   */
+  exportBiomes(min: WorldCoords, max: WorldCoords): Uint32Array
+  /**
+   * Finds the nearest loaded 4x4x4 biome cell whose biome is one of `biomes` (IDs or names, e.g. `"desert"`).
+   *
+   * Sections whose biome palette can't contain a match are skipped without unpacking them.
+   * Returns the center block of the closest cell within `max_distance`, or `null` if there is none.
+   */
+  findNearestBiome(point: { x: number, y: number, z: number }, biomes: Array<number | string>, max_distance: number): BiomeSearchResult | null
+  /**
+   * Finds the surface of the highest water or lava column at the given X/Z position.
+   * Returns `null` if the chunk isn't loaded or there's no fluid in the column.
+   */
+  getFluidLevel(x: number, z: number): FluidLevel | null
+  /** Checks whether any part of the bounding box is inside a fluid, optionally only `'water'` or `'lava'`. */
+  isSubmerged(aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }, fluid?: 'water' | 'lava' | null): boolean
+  /**
+   * Lists every block the straight line between two points passes through, in order from `from` to `to`.
+   *
+   * This is a full voxel traversal (no block is skipped, even when the line only clips a corner), and
+   * doesn't look at the world. Pass block centers (`x + 0.5`) to walk between two block positions.
+   */
+  blocksOnLine(from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }): Array<WorldCoords>
+  /**
+   * Lists the blocks whose centers lie within `radius` of the center block's center.
+   *
+   * Returns a flat `Int32Array` of `[x, y, z]` triples, or `[x, y, z, stateId]` quads when `include_states` is set.
+   */
+  blocksInSphere(center: WorldCoords, radius: number, includeStates?: boolean | undefined | null): Int32Array
+  /**
+   * Lists the blocks of a vertical cylinder whose base is centered on `center`, extending `height` blocks upwards.
+   * A block is included when its center column lies within `radius` of the center column.
+   *
+   * Returns the same packed layout as `blocks_in_sphere`.
+   */
+  blocksInCylinder(center: WorldCoords, radius: number, height: number, includeStates?: boolean | undefined | null): Int32Array
+  /**
+   * Lists the blocks on the outer surface of the cuboid between two corners (inclusive).
+   *
+   * Returns the same packed layout as `blocks_in_sphere`.
+   */
+  cuboidShell(min: WorldCoords, max: WorldCoords, includeStates?: boolean | undefined | null): Int32Array
+  /**
+   * Sets every loaded block in the region to `state_id`. Returns the number of blocks that changed; positions
+   * outside the world aren't written.
+   */
+  fillRegion(region: Region, stateId: number): number
+  /**
+   * Replaces every loaded block in the region whose state is one of `from_state_ids` with `to_state_id`.
+   * Returns the number of blocks replaced; positions outside the world aren't written.
+   */
+  replaceInRegion(region: Region, fromStateIds: Array<number>, toStateId: number): number
+  /** Counts the loaded blocks in the region whose state is one of `state_ids`. */
+  countInRegion(region: Region, stateIds: Array<number>): number
+  /**
+   * Finds a walking path between two feet positions with A* over the loaded blocks.
+   *
+   * The agent is 1 block wide and 2 tall, can step or jump up `maxJumpHeight` blocks, drop `maxFallHeight`
+   * blocks and, unless disabled, move diagonally when both adjacent columns are clear.
+   * If the node budget runs out, the path to the node closest to the goal is returned with status `"partial"`.
+   */
+  findPath(start: WorldCoords, goal: WorldCoords, options?: PathOptions | undefined | null): PathResult
+  /**
+   * Checks whether an agent can walk in a straight line between two feet positions without jumping or falling.
+   *
+   * The agent's bounding box is swept along the ground in small steps: at every step there has to be ground under it
+   * that is at most `stepHeight` above or below the previous step, and nothing may intersect its body.
+   * Used for smoothing paths (string pulling) after `find_path`.
+   */
+  canWalkLine(from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }, options?: WalkOptions | null): boolean
+  /**
+   * Checks whether an agent (default 0.6 wide, 1.8 tall) centered in the block can stand with its feet in it:
+   * a collision surface inside the feet block to stand on, and no collision boxes intersecting its body.
+   */
+  canStandAt(x: number, y: number, z: number, size?: AgentSize | undefined | null): boolean
+  /**
+   * Finds where a box (default 0.6 wide, like a player) falling straight down from the bottom center position
+   * comes to rest: the highest collision surface under its footprint, including partial blocks like slabs,
+   * carpets and fences. Liquids and blocks without collision are fallen through. Returns `null` if it would fall
+   * out of the world or into an unloaded column, or if it starts below the world. Positions above the world
+   * fall from its top. Only `width` of the size is used.
+   */
+  getLandingPosition(x: number, y: number, z: number, size?: AgentSize | undefined | null): LandingPosition | null
+  /** Finds the standable block closest to `point` within `radius` blocks (at most 64; see `can_stand_at`). */
+  findNearestStandable(point: WorldCoords, radius: number, size?: AgentSize | undefined | null): StandPosition | null
+  /**
+   * Lists every feet position a walking agent can reach from `start`, using the same movement rules as `find_path`
+   * (`maxNodes` in the rules is ignored in favour of the explicit limit).
+   *
+   * Positions are returned in breadth-first order, so when `max_nodes` cuts the search short the closest ones are kept.
+   */
+  floodFillReachable(start: WorldCoords, maxNodes: number, movementRules?: PathOptions | undefined | null): Array<WorldCoords>
+  /**
+   * Builds (or returns the cached) navigation mesh of a chunk: every cell a player-sized agent can stand in,
+   * plus the walk/jump/fall links between cells using `find_path`'s default movement rules.
+   *
+   * Cached meshes are updated column by column as blocks change, and dropped when the chunk or a neighbor is (un)loaded.
+   */
+  buildNavmesh(chunkX: number, chunkZ: number): NavmeshData
+  /**
+   * Calculates how long breaking a block takes in milliseconds, using vanilla's formula with the block's hardness,
+   * the held tool (tier speed, efficiency, whether it can harvest the block), haste/mining fatigue and the
+   * underwater/airborne penalties. Instantly broken blocks take 0 ms; unbreakable ones return `Infinity`.
+   */
+  getDigTime(stateId: number, options?: DigOptions | undefined | null): number
+  /** Gets a block state's hardness. Unbreakable blocks report -1; unknown states return null. */
+  getBlockHardness(stateId: number): number | null
+  /** Gets a block state's material, e.g. "mineable/pickaxe" (null if unknown or not set). */
+  getBlockMaterial(stateId: number): string | null
+  /** Checks whether mining a block state with the given tool (none for an empty hand) drops its item. */
+  canHarvest(stateId: number, toolName?: string | undefined | null): boolean
+  /** Checks whether a block state is opaque, i.e. not transparent and fully blocking light. */
+  isOpaque(stateId: number): boolean
+  /** Checks whether a block state is transparent (can be seen through). Unknown states count as transparent. */
+  isTransparent(stateId: number): boolean
+  /** Gets how many light levels a block state removes from light passing through it (0-15). */
+  getLightFilter(stateId: number): number
+  /**
+   * Checks whether a block state's collision shape fills the entire block space.
+   * Full cubes hide the adjacent faces of their neighbors, so renderers can cull those faces.
+   */
+  isFullCube(stateId: number): boolean
+  /**
+   * Returns every section changed since the last call (block, biome or light writes, or loading its column)
+   * and clears the list. Renderers can call this once per frame to know exactly which meshes to rebuild.
+   */
+  takeDirtySections(): Array<SectionPosition>
+  /**
+   * Gets a content hash of a section's block states and biomes as a 16-digit hex string, or null if its column
+   * isn't loaded or the section is outside the world. Equal sections hash equally in every process, so this is
+   * a cheap change/divergence check.
+   */
+  getSectionHash(chunkX: number, sectionY: number, chunkZ: number): string | null
+  /** Calls `callback` with the new block whenever the block at the position changes. Returns a watcher ID for `unwatch`. */
+  watchBlock(x: number, y: number, z: number, callback: (updates: BlockUpdate[]) => void): number
+  /**
+   * Calls `callback` with the changed blocks whenever blocks inside the region (inclusive corners) change.
+   * Changes are coalesced and delivered at most once per macrotask. Returns a watcher ID for `unwatch`.
+   */
+  watchRegion(min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number }, callback: (updates: BlockUpdate[]) => void): number
+  /** Removes a watcher. Returns false if no watcher had this ID. */
+  unwatch(watcherId: number): boolean
+  /**
+   * Subscribes to block updates, optionally only those inside the given chunk ranges.
+   * Updates outside the event center (see `set_event_center`) are never sent. Returns a subscription ID.
+   */
+  onBlockUpdate(callback: (updates: BlockUpdate[]) => void, chunkRanges?: ChunkRange[] | null): number
+  /** Removes a block update subscription. Returns false if no subscription had this ID. */
+  offBlockUpdate(subscriptionId: number): boolean
+  /**
+   * Only sends block update events within `radius` blocks (horizontally) of `center`, for every subscriber.
+   * Call again as the center moves, or with no arguments to send updates from everywhere again.
+   */
+  setEventCenter(center?: { x: number, y: number, z: number } | null, radius?: number | null): void
+  /** Pauses block update delivery to watchers and subscribers; updates queue up until `resume_events` or `flush_events`. */
+  pauseEvents(): void
+  /** Undoes one `pause_events`, delivering everything queued once no pause is left. */
+  resumeEvents(): void
+  /**
+   * Delivers all queued block updates now, even while paused, instead of at the next macrotask.
+   * Each watcher and subscriber receives its updates as a single array.
+   */
+  flushEvents(): void
+  /**
+   * Starts staging `set_block_state_id` writes instead of applying them. Reads keep returning the
+   * committed blocks until `commit`, which applies every staged write at once; `rollback` discards them.
+   */
+  beginTransaction(): void
+  /**
+   * Applies the open transaction's writes atomically: every affected column is locked before any write happens,
+   * and watchers/subscribers receive all changes as one batch. If a column was unloaded or can't be locked,
+   * nothing is written and the transaction stays open. Returns the number of blocks that changed.
+   */
+  commit(): number
+  /** Discards the open transaction's staged writes. Returns false if no transaction was open. */
+  rollback(): boolean
+  /**
+   * Serializes a loaded column (by coordinates or `chunkKey`) into a compact buffer for `deserialize_column`,
+   * e.g. to hand it to a worker thread. The format is internal to this library and may change between versions.
+   */
+  serializeColumn(chunkX: number | bigint, chunkZ?: number | undefined | null): Buffer
+  /** Loads a column produced by `serialize_column`, replacing any column already loaded at these coordinates. */
+  deserializeColumn(chunkX: number, chunkZ: number, data: Buffer): void
+  /**
+   * Shares this world's native state (columns, block/biome data, options such as the dimension's Y range, the cold
+   * column policy, navmesh and block caches) with other worker threads. Post the returned handle to a worker and
+   * open it there with `World.fromHandle`: both sides then operate on the same loaded chunks without copying them.
+   * Watchers, subscriptions and transactions remain per world.
+   */
+  share(): ExternalHandle
+  /**
+   * Starts a chunk batch (the chunk_batch_start packet). Until `end_chunk_batch`, column load timings are
+   * collected and block update events are held back.
+   */
+  beginChunkBatch(): void
+  /**
+   * Finishes the chunk batch (the chunk_batch_finished packet), delivering held events. Returns the batch's parse
+   * timing and the chunks-per-tick rate to answer with, averaged over recent batches the way the vanilla client does.
+   */
+  endChunkBatch(): ChunkBatchResult
+  /**
+   * Generates and loads a superflat column from a layer string such as
+   * `"minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block"` (bottom layer first, starting at the bottom
+   * of the world). The biome defaults to plains.
+   */
+  generateFlat(chunkX: number, chunkZ: number, layerSpec: string, biome?: number | string | null): void
+  /** Generates and loads an empty column. The biome defaults to the void. */
+  generateVoid(chunkX: number, chunkZ: number, biome?: number | string | null): void
+  /**
+   * Gets a digest of every loaded column (coordinates plus the hash of each section) as a 16-digit hex string.
+   * Two worlds with the same columns loaded and the same content produce the same digest, whatever order the
+   * columns were loaded in. Hashes every section of every column, so avoid calling it every tick.
+   */
+  getWorldHash(): string
+  /**
+   * Describes how each present section of a loaded column is stored: palette type and size, bits per value,
+   * and counted vs. stored non-air blocks. Takes coordinates or a `chunkKey`; returns null if the column isn't loaded.
+   */
+  getColumnStats(chunkX: number | bigint, chunkZ?: number | undefined | null): ColumnStats | null
+  /**
+   * Dumps a section's raw storage (palettes and data longs) for debugging. Returns null if the column
+   * isn't loaded or has no such section.
+   */
+  dumpSection(chunkX: number, sectionY: number, chunkZ: number): SectionDump | null
+  /**
+   * Gets the state IDs of all 4096 blocks of a section at once, indexed by `(y * 16 + z) * 16 + x`
+   * (section-relative). Missing sections read as air. Returns null if the column isn't loaded or the section is
+   * outside the world.
+   */
+  getSectionBlocks(chunkX: number, sectionY: number, chunkZ: number): Uint32Array | null
+  /**
+   * Gets how a section's blocks are stored: container type and palette entries (state IDs). Palettes can hold
+   * states no block uses anymore, but a state missing from them is certainly absent from the section. Missing
+   * sections read as a single air entry. Returns null if the column isn't loaded or the section is outside the
+   * world.
+   */
+  getSectionPalette(chunkX: number, sectionY: number, chunkZ: number): SectionPalette | null
+  /**
+   * Checks every loaded section for storage corruption: palette indices out of bounds, invalid bits per value
+   * or data lengths, unknown block states and block counts that don't match the data. Lookups silently treat
+   * corrupt data as air, so run this when a bot sees blocks that shouldn't be there.
+   */
+  validate(): ValidationReport
+  /**
+   * Gets the collision boxes of a block state, relative to the block origin (0-1 on each axis).
+   * Solid blocks without collision data get a full cube; non-solid blocks without collision data get none.
+   * Shapes depend only on the state, so neighboring blocks (e.g. fence connections) must already be in its properties.
+   */
+  getCollisionShapes(stateId: number): Array<AabbArg>
+  /**
+   * Gets the collision boxes of the block at a position, in world coordinates.
+   * Air and blocks in unloaded chunks have none.
+   */
+  getShapeAt(x: number, y: number, z: number): Array<AabbArg>
+  /**
+   * Casts many rays at once on a thread pool, without blocking the event loop.
+   *
+   * `rays` holds 6 numbers per ray: origin x, y, z and direction x, y, z. The other arguments are the same as
+   * for `raycast` and apply to every ray. Resolves to 8 numbers per ray, in the same order: block x, y, z, face,
+   * intersection point x, y, z and distance. Rays that hit nothing have face -1 and NaN for everything else.
+   * Blocks written while the rays are being cast may or may not be seen by them.
+   */
+  raycastBatch(rays: Float64Array, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): Promise<Float64Array>
+  /**
+   * Lists the loaded sections that contain blocks and are at least partly inside a camera frustum,
+   * sorted front-to-back (by distance from `camera_pos` to the section center).
+   *
+   * `frustum` is either a 16-number column-major view-projection matrix (OpenGL clip space, as in three.js or
+   * gl-matrix) or 24 numbers giving six planes `a, b, c, d`, with the inside where `a*x + b*y + c*z + d >= 0`.
+   */
+  getSectionsInFrustum(camera_pos: { x: number, y: number, z: number }, frustum: number[]): Array<SectionPosition>
+  /**
+   * Finds the topmost block at (x, z) whose state is one of `state_ids`, or the topmost non-air block if none
+   * are given. Returns `null` if the chunk isn't loaded or nothing matches.
+   */
+  getHighestBlock(x: number, z: number, stateIds?: Array<number> | undefined | null): SurfaceBlock | null
+  /**
+   * Finds the topmost solid block of every (x, z) column in the region, looking only within the region's Y range.
+   * Columns without a solid block there, and unloaded chunks, are left out.
+   */
+  getSurfaceBlocks(region: Region): Array<SurfaceBlock>
+  /**
+   * Gets how open to the sky each block column of a chunk is: the sky light level right above its topmost
+   * opaque block, after passing through the translucent blocks above it (leaves, water, glass...).
+   * 15 means nothing filters the sky there. Only direct light is considered, not light spreading sideways.
+   * Returns 256 values indexed by `z * 16 + x` (chunk-relative).
+   */
+  getSkyExposure(chunkX: number, chunkZ: number): Uint8Array
+  /**
+   * Finds every light-emitting block (torches, glowstone, lit furnaces...) between two corners (inclusive),
+   * with the light level it emits. Levels come from the block data; blocks with a `lit` property only count
+   * while lit. Sections without any emitting block state are skipped. Unloaded chunks are left out.
+   */
+  findLightSources(min: WorldCoords, max: WorldCoords): Array<LightSource>
+  /**
+   * Loads one Bedrock sub-chunk (versions 1, 8 and 9, with runtime ID palettes), creating an empty column if none
+   * is loaded. Version 9 sub-chunks carry their own Y; for older ones `section_y` must be given.
+   * Only the block layer is kept: waterlogging layers and biomes aren't stored.
+   */
+  loadSubChunk(chunkX: number, chunkZ: number, dataBuffer: Buffer, sectionY?: number | undefined | null): void
+  /**
+   * Loads a column from the sub-chunks at the start of a Bedrock `level_chunk` payload, replacing any column already
+   * loaded there. `sub_chunk_count` comes from the packet; whatever follows the sub-chunks (biomes, block entities)
+   * is ignored. Sub-chunks without a stored Y are placed upwards from the bottom of the world.
+   */
+  loadBedrockColumn(chunkX: number, chunkZ: number, subChunkCount: number, dataBuffer: Buffer): void
+  /**
+   * Guesses the layout of a chunk packet's section data from its structure: `'1.18'` (1.18 and later),
+   * `'1.16'` (1.16-1.17), `'1.14'` (1.14-1.15) or `'1.13'`, or `null` if it matches none of them.
+   */
+  detectFormat(dataBuffer: Buffer): '1.18' | '1.16' | '1.14' | '1.13' | null
+  /**
+   * Loads a column like `load_column`, first detecting which version's layout the data uses (see `detect_format`).
+   * Before 1.18 only some sections are sent: bit `i` of `section_mask` (from the packet) marks section Y `i`,
+   * and without it the sections are placed upwards from Y 0. Biomes sent along before 1.15 and 1.13's in-band
+   * light are skipped. Returns the detected format.
+   */
+  loadColumnAuto(chunkX: number, chunkZ: number, dataBuffer: Buffer, sectionMask?: number | undefined | null): '1.18' | '1.16' | '1.14' | '1.13'
+  /**
+   * Attaches a directory of `.mca` region files (e.g. `<world>/region`) as a lazy column source. Region files
+   * are memory-mapped when first needed, and a column is parsed the first time a block, biome or raycast query
+   * touches it; whole-world queries only see columns loaded so far. Only 1.18+ chunks are read.
+   * The files must not be modified by other programs while attached.
+   */
+  attachRegionDir(path: string): void
+  /** Detaches the region directory. Columns already read from it stay loaded. */
+  detachRegionDir(): void
+  /**
+   * Writes all loaded columns to `.mca` region files in `path` (created if needed), in the 1.18+ disk format the
+   * game and external tools read. Columns already in existing region files that aren't loaded are kept; loaded
+   * ones replace them. `dataVersion` defaults to the world's version and must be given for versions not known
+   * here. Light and heightmaps aren't written; the game recomputes them. Returns the number of columns written.
+   */
+  saveToRegionDir(path: string, dataVersion?: number | undefined | null): number
+  /**
+   * Serializes a loaded column to the chunk NBT stored in region files (the same structure
+   * `saveToRegionDir` writes), uncompressed unless `compression` is `'gzip'` or `'zlib'`.
+   * `dataVersion` defaults to the world's version.
+   */
+  columnToNbt(chunkX: number, chunkZ: number, compression?: 'gzip' | 'zlib' | 'none' | null, dataVersion?: number | null): Buffer
+  /**
+   * Loads the column at the given chunk position from disk-format chunk NBT (1.18+, as stored in region files or
+   * returned by `columnToNbt`), replacing any loaded column there. Gzip or zlib compressed NBT is detected and
+   * decompressed.
+   */
+  loadColumnFromNbt(chunkX: number, chunkZ: number, nbtBuffer: Buffer): void
+  /**
+   * Loads a column from the attached region directory now, unless it's already loaded.
+   * Returns whether the column is loaded afterwards.
+   */
+  loadRegionColumn(chunkX: number, chunkZ: number): boolean
+  /**
+   * Keeps the columns within `radius` chunks (at most 64) of a block position loaded from the attached region
+   * directory, like a view distance. Columns are read nearest-first on a background thread; columns it loaded earlier
+   * that are now out of range are unloaded again (columns loaded any other way are left alone). Call it again as the
+   * position moves; outdated work is dropped. The thread runs until `stop_prefetch` or until the world is
+   * garbage-collected.
+   */
+  setPrefetchCenter(x: number, z: number, radius: number): void
+  /** Calls `callback` with the positions of columns as the prefetcher loads them. Replaces any earlier callback. */
+  onPrefetchLoad(callback: (columns: ChunkPosition[]) => void): void
+  /** Stops the prefetch thread. Columns it loaded stay loaded. */
+  stopPrefetch(): void
+  /**
+   * Casts a ray against entity hitboxes and returns the nearest entity hit within `max_distance`, or `null`.
+   * Hitbox sizes come from `width`/`height`, else from minecraft-data by `name`; entities with `yaw` set are
+   * tested as boxes rotated to it. Unless `ignore_blocks` is set, entities behind the first block the ray hits
+   * don't count, like on the server.
+   */
+  raycastEntities(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, entities: EntityHitbox[], ignore_blocks?: boolean | null): EntityRaycastResult | null
+  /**
+   * Checks whether a block state could be placed at `target` the way the server validates it: the position is
+   * loaded and inside the world, the block there is replaceable (air, liquids, grass...), a neighboring block
+   * exists to click on (unless `requireSupport` is false), and the new block's collision boxes don't intersect
+   * any of the given entity boxes. On success, `against`/`face` tell which neighbor face to click.
+   */
+  canPlaceBlock(target: WorldCoords, stateId: number, options?: PlacementOptions | undefined | null): PlacementCheck
+  /**
+   * Checks whether the block at the position has what it needs to stay in place, by its attachment rules:
+   * torches, ladders, buttons and signs need the block they hang on, crops need farmland, plants need soil,
+   * rails and redstone need a solid block below, upper halves of doors and tall plants need their lower half.
+   * A full collision cube counts as solid. Blocks without such rules are always supported.
+   */
+  isBlockSupported(x: number, y: number, z: number): boolean
+  /**
+   * Plans the block placements needed to bridge from one standing position to another: the blocks under the feet
+   * along the line between them (face-connected, so each placed block can hold the next) that aren't there yet,
+   * in placement order, each with the block and face to click, which may be a block placed earlier in the plan.
+   * Returns an empty list if the floor is already complete, or `null` if a block can't be placed (unloaded,
+   * outside the world, or nothing to place it against).
+   */
+  planScaffold(from: WorldCoords, to: WorldCoords): Array<ScaffoldStep> | null
+  /** Copies the blocks of a region into a schematic. Blocks in unloaded columns become void. */
+  copySchematic(region: Region): Schematic
+  /**
+   * Compares the world to a schematic placed with its minimum corner at `origin`, listing the blocks that are
+   * missing (air where the schematic has a block), wrong (another block) or extra (a block where the schematic
+   * has air). Void positions and unloaded columns are skipped. Lists are in Y, Z, X order per column.
+   * With `transform`, the schematic is compared as mirrored and rotated, its minimum corner still at `origin`.
+   */
+  diffSchematic(schematic: Schematic, origin: WorldCoords, transform?: TransformOptions | undefined | null): SchematicDiff
+  /**
+   * Reads a vanilla structure template (`.nbt` as saved by structure blocks, gzip compressed or not) into a
+   * schematic. Positions the template doesn't list (structure voids) are void. With several palettes, the
+   * first is used. Unknown blocks become air.
+   */
+  loadStructure(buffer: Buffer): Schematic
+  /**
+   * Writes a schematic into the world with its minimum corner at `origin`. Void positions and unloaded columns
+   * are left alone. Returns the number of blocks that changed. With `transform`, the schematic is mirrored and
+   * rotated (stairs, logs, rails... turned to match), its minimum corner still at `origin`.
+   */
+  pasteSchematic(schematic: Schematic, origin: WorldCoords, transform?: TransformOptions | undefined | null): number
+  /**
+   * Rotates a block state clockwise (seen from above) by 0, 90, 180 or 270 degrees: stairs, logs, rails, signs,
+   * fences and other directional blocks are turned to match. Blocks without directions are returned as is.
+   */
+  rotateState(stateId: number, rotation: number): number
+  /**
+   * Mirrors a block state across an axis: `'x'` flips east and west, `'z'` flips north and south. Left and
+   * right variants (stair corners, door hinges, double chests) swap too.
+   */
+  mirrorState(stateId: number, axis: 'x' | 'z'): number
+  /**
+   * Collects the connected region of blocks matching `blocks` (state IDs, block names or `#` block tags) that
+   * contains `start`, e.g. a lava lake or a vein of ore. Blocks connect through faces, or also through edges and corners with
+   * `diagonal`. At most `max_blocks` are returned, nearest (in steps) first. Returns an empty list if the start
+   * block doesn't match or isn't loaded.
+   */
+  floodFillBlocks(start: WorldCoords, blocks: Array<number | string>, max_blocks: number, diagonal?: boolean | null): Array<WorldCoords>
+  /**
+   * Finds the tree a log belongs to: its logs (connected through faces, edges and corners, so branches count)
+   * and the naturally grown leaves held by them. Leaves are followed outwards only while their `distance`
+   * grows by one per block, like the game's leaf decay, so leaves of neighboring trees and player-placed leaves
+   * are left out. At most `max_blocks` (default 4096) blocks are collected. Returns `null` if there's no log
+   * at the position.
+   */
+  detectTree(logPos: WorldCoords, maxBlocks?: number | undefined | null): TreeBlocks | null
+  /**
+   * Lists the block tags (e.g. `logs`, `mineable/pickaxe`) of a block state's block. minecraft-data has no tag
+   * data, so these are approximations, also used wherever blocks can be given as `#` tags: `mineable/*` tags
+   * come from block materials, while `rails`, `sand`, `dirt`, `ice`, `climbable`, `crops`, `fire`, `campfires`,
+   * `beehives`, `anvil`, `small_flowers`, `tall_flowers`, `flowers`, `leaves`, `planks`, `wool`, `wool_carpets`,
+   * `stairs`, `slabs`, `walls`, `fences`, `fence_gates`, `doors`, `trapdoors`, `buttons`, `pressure_plates`,
+   * `beds`, `banners`, `signs`, `all_signs`, `logs`, `saplings`, `candles`, `shulker_boxes`, `cauldrons`,
+   * `terracotta` and `<ore>_ores` are guessed from block names and may differ from the game's.
+   */
+  getApproximateBlockTags(stateId: number): Array<string>
+  /**
+   * Checks whether a block state's block is in an approximated tag (see `getApproximateBlockTags`), given as
+   * `#minecraft:logs`, `#logs` or `logs`.
+   */
+  blockHasApproximateTag(stateId: number, tag: string): boolean
+  /**
+   * Lists the names of the blocks in an approximated tag (see `getApproximateBlockTags`), e.g. for building a
+   * raycast filter. Unknown tags are empty.
+   */
+  getBlocksInApproximateTag(tag: string): Array<string>
+  /**
+   * Finds the fully grown crops in the region (wheat, carrots, potatoes, beetroots, nether wart, cocoa, sweet
+   * berries, pitcher and torchflower crops): those whose `age` property is at its maximum. Sections without any
+   * grown crop are skipped without reading their blocks. Unloaded chunks are left out.
+   */
+  findHarvestableCrops(region: Region): Array<HarvestableCrop>
+  /**
+   * Stores the block entity data (sign text, container items...) of the block at the given position, as an
+   * uncompressed NBT compound with a named root (e.g. from prismarine-nbt's `writeUncompressed`). Block entities
+   * in chunks loaded from region files or chunk NBT are stored automatically.
+   */
+  setBlockEntity(x: number, y: number, z: number, nbtBuffer: Buffer): void
+  /** Removes the block entity data at the given position. Returns false if there was none. */
+  removeBlockEntity(x: number, y: number, z: number): boolean
+  /** Gets the stored block entity data at the given position as uncompressed NBT, or `null` if there is none. */
+  getBlockEntity(x: number, y: number, z: number): Buffer | null
+  /**
+   * Gets the text of the sign at the given position as plain text, one entry per line, with formatting dropped.
+   * Returns `null` if the block isn't a sign or its block entity data isn't known.
+   */
+  getSignText(x: number, y: number, z: number): SignText | null
+  /**
+   * Lists the items of the container (chest, barrel, furnace, hopper, shulker box...) at the given position, by
+   * slot. Returns `null` if there is no block entity data with items there.
+   */
+  getContainerItems(x: number, y: number, z: number): Array<ContainerItem> | null
+  /**
+   * Checks whether the chest (or trapped chest) at the given position is half of a double chest. Read from the
+   * block state, so it works without block entity data. False for single chests and anything that isn't a chest.
+   */
+  isChestDouble(x: number, y: number, z: number): boolean
+  /**
+   * Sets a block like `set_block_state_id`, but reports what happened instead of failing: whether the block
+   * changed, was staged by an open transaction, or couldn't be written because it's outside the world or its
+   * chunk isn't loaded.
+   */
+  trySetBlockStateId(x: number, y: number, z: number, stateId: number): BlockWriteResult
+  /**
+   * Sets what block writes to chunks that aren't loaded do: `'reject'` (the default) fails them, `'create'`
+   * loads an empty (all air) column there first, for worlds built from block writes alone, and `'defer'` keeps
+   * them until the chunk is loaded and then applies them on top of its data, for block updates that arrive
+   * before their chunk.
+   */
+  setUnloadedWriteMode(mode: 'reject' | 'create' | 'defer'): void
+  /**
+   * Turns write deduplication on or off (off by default), for servers applying updates that echo their own
+   * writes back. Writes of the state a block already has never count as changes; with deduplication on, blocks
+   * that a transaction commit or a chunk's deferred writes set back to the state they started from aren't
+   * reported either, and a block updated several times before its watchers and subscribers get the updates is
+   * delivered once, with its latest state.
+   */
+  setWriteDedup(enabled: boolean): void
+  /** Counts the deferred writes still waiting for their chunk to load. */
+  getDeferredEditCount(): number
+  /**
+   * Drops the deferred writes waiting for one chunk, or for every chunk if none is given. Returns how many
+   * writes were dropped.
+   */
+  clearDeferredEdits(chunkX?: number | undefined | null, chunkZ?: number | undefined | null): number
+  /**
+   * Gets a read-only view of a loaded column that shares its storage instead of copying it, for readers such
+   * as renderer workers. Takes coordinates or a `chunkKey`; returns `null` if the column isn't loaded.
+   */
+  getColumnView(chunkX: number | bigint, chunkZ?: number | undefined | null): ColumnView | null
+  /**
+   * Casts a ray configured by an options object: which blocks to hit (`matcher`), whether fluids are hit
+   * (`fluid_mode`), which boxes hit blocks have (`shape_source`), entities to test as well, how unloaded chunks
+   * are treated, and whether to return every hit instead of the first. Returns the hits nearest first: at most
+   * one unless `return_all` is set, with blocks and entities hidden behind the first hit left out.
+   */
+  raycastEx(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, options: RaycastOptions): Array<RaycastExHit>
+  /**
+   * Creates a world from an options object collecting the construction-time settings: the version, the
+   * dimension's Y range (within the -64..320 range columns store), strict chunk parsing, a cap on loaded
+   * columns and whether block changes are reported to watchers and subscribers. `loadColumn` and `loadSection`
+   * read chunk packets as carrying the dimension's sections only, and writes outside it are rejected.
+   */
+  static withOptions(options: WorldOptions): NapiWorld
+  /** Version string the world was created for. */
+  get version(): string
+  /** Lowest block Y of the dimension. */
+  get minY(): number
+  /** Height of the dimension in blocks. */
+  get dimensionHeight(): number
+  get strictParsing(): boolean
+  get maxLoadedColumns(): number | null
+  get emitEvents(): boolean
+  /**
+   * Gets counts and timings of the world's native work: chunk parses, raycasts, block reads and writes, lock
+   * contention and how often columns were found loaded. Counting is always on and costs an atomic add per event.
+   */
+  getPerfCounters(): PerfReport
+  /** Sets every performance counter back to zero. */
+  resetPerfCounters(): void
+  /**
+   * Times the native hot paths (chunk parsing, raycasts through terrain, block searches and bulk writes) on
+   * generated fixture terrain, in a scratch world of this world's version; this world is left untouched.
+   * `iterations` defaults to 10. Compare the results across builds to spot performance regressions.
+   */
+  runSelfBenchmark(iterations?: number | undefined | null): Array<BenchmarkResult>
+  /**
+   * Compresses columns not accessed for `idleSeconds` into compact blobs, decompressing them transparently on
+   * their next access. Sweeps run as columns get loaded; `null` turns the policy off (cold columns stay
+   * compressed until accessed). Whole-world scans like `saveToRegionDir` skip cold columns.
+   */
+  setColdColumnPolicy(idleSeconds?: number | undefined | null): void
+  /**
+   * Compresses idle columns right away instead of waiting for the next sweep. Returns how many were compressed.
+   * Does nothing while no policy is set.
+   */
+  compressColdColumns(): number
+  /**
+   * Gets the number of compressed columns and their total size, and how often columns were compressed and
+   * decompressed.
+   */
+  getColdColumnStats(): ColdColumnStats
+  /**
+   * Finds every block in the loaded columns matching `blocks` (state IDs, block names or `#` tags), on a
+   * thread pool without blocking the event loop. Columns are scanned in parallel and sections that can't hold a
+   * match are skipped from their palette alone. Resolves to positions ordered by column (x, then z), then by y,
+   * z and x, cut to `maxResults` if given. Compressed cold columns are left out.
+   */
+  scanWorld(blocks: Array<number | string>, maxResults?: number | undefined | null): Promise<Array<WorldCoords>>
+  /** Counts the blocks in the loaded columns matching `blocks`, like `scanWorld` without collecting positions. */
+  countInWorld(blocks: Array<number | string>): Promise<number>
+  /**
+   * Casts a ray like `raycast` but writes the result into `out` instead of allocating an object, for callers
+   * casting many rays per tick. The 8 numbers of the result (same layout as `raycastBatch`) go at index
+   * `offset * 8` (`offset` defaults to 0), so one array can pool the results of several rays. Returns whether
+   * the ray hit a block.
+   */
+  raycastInto(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, out: Float64Array, offset?: number | null, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): boolean
+  /**
+   * Synchronous counterpart of `raycastBatch` writing into a caller-provided array: `out` must hold 8 numbers
+   * per ray of `rays`, and is filled in the layout `raycastBatch` resolves to. Returns the number of hits.
+   */
+  raycastBatchInto(rays: Float64Array, out: Float64Array, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null): number
+  /**
+   * Reads blocks from a group of columns (e.g. the 3x3 neighborhood of an entity) under one set of read locks
+   * taken up front, so every read sees the columns in the same state and locks are taken once per column
+   * instead of once per read. Positions outside the requested columns, or in columns that aren't loaded,
+   * read as air; check `loaded` to tell the two apart.
+   */
+  withColumns(chunks: Array<ChunkPosition>, reads: ColumnReads): ColumnReadResult
+  /**
+   * Finds the first solid block straight below (or, with `up`, above) a point of the block column (x, z),
+   * starting with the block containing `fromY` (default: the top of the world when looking down). Looking down
+   * from above the terrain is answered from a cached heightmap, kept up to date as blocks change, instead of
+   * stepping through the air in between. Returns `null` if the chunk isn't loaded or no block is found.
+   */
+  surfaceRaycast(x: number, z: number, fromY?: number | undefined | null, up?: boolean | undefined | null): SurfaceHit | null
+  /**
+   * Turns empty-space skipping for raycasts on or off (off by default). When on, the world keeps a coarse
+   * map of which 4x4x4 cells of each section hold any block, built as rays reach them, and rays step through
+   * empty cells and sections without reading their blocks, which speeds up long rays through open air (sky to
+   * ground, long horizontal rays). Results are the same either way; `raycastDebug` never skips. The setting and the
+   * map are shared by worlds operating on the same columns (`fromColumns`, `fromHandle`).
+   */
+  setRaycastAcceleration(enabled: boolean): void
+  /**
+   * Casts rays from an eye position to `samples` points spread through a target box (its center first) and
+   * reports which fraction of them no block hides, with the first visible point in sample order, e.g. to pick
+   * a target and the point to aim at. Points farther than `maxDistance` (if given) count as hidden.
+   */
+  sampleVisibility(eye: { x: number, y: number, z: number }, target_aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }, samples: number, max_distance?: number | null): VisibilityResult
+  /**
+   * Casts `rays` rays spread evenly within a cone around `direction`, whose half-angle is `angle` degrees,
+   * e.g. for field-of-view checks, explosion shielding or rough area scans. The first ray goes straight along
+   * `direction`, the last along the edge of the cone. Returns each ray's direction with its closest hit, if any,
+   * in the order they were cast.
+   */
+  coneCast(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, angle: number, rays: number, max_distance: number, intersect_non_solid_block_names?: string[] | null): Array<ConeRay>
+  /**
+   * Gets the outline (selection) boxes of the block at a position, in world coordinates: the boxes the game
+   * draws its black wireframe around, and the ones `raycastEx` tests with `shapeSource: 'outline'`. Derived from
+   * the collision boxes, with vanilla's outlines for common blocks that differ (plants, torches, rails, fences...).
+   * Air, fluids and blocks in unloaded chunks have none.
+   */
+  getOutlineShape(x: number, y: number, z: number): Array<AabbArg>
+  /**
+   * Gets the state IDs of a section's blocks plus a one-block border from the sections and columns around
+   * it, 18x18x18 values indexed by `((y + 1) * 18 + z + 1) * 18 + x + 1` for section-relative x, y, z from -1
+   * to 16: what a mesher needs to cull faces on the section's edges. Blocks in unloaded columns, missing
+   * sections and outside the world read as air. Returns null if the section's own column isn't loaded or the
+   * section is outside the world.
+   */
+  getSectionWithBorder(chunkX: number, sectionY: number, chunkZ: number): Uint32Array | null
+  /**
+   * Gets the light of a section's blocks plus a one-block border around it, laid out like
+   * `getSectionWithBorder`, one byte per block holding sky light in the high four bits and block light in the
+   * low four (`sky = v >> 4`, `block = v & 15`): enough for smooth lighting and ambient occlusion in one pass.
+   * Levels are the ones `getBlockLight`/`getSkyLight` report; unloaded columns read as full sky light without
+   * block light. Returns null if the section's own column isn't loaded or the section is outside the world.
+   */
+  getLightWithBorder(chunkX: number, sectionY: number, chunkZ: number): Uint8Array | null
+  /**
+   * Gets how many milliseconds ago a loaded column (by coordinates or `chunkKey`) was last read, written or
+   * loaded, for custom unloading policies. Access times are kept from the first call to this or
+   * `unloadOlderThan` on; columns loaded before count as accessed then. Returns null if the column isn't
+   * loaded.
+   */
+  getColumnAge(chunkX: number | bigint, chunkZ?: number | undefined | null): number | null
+  /**
+   * Unloads every column (compressed ones included, see `setColdColumnPolicy`) not accessed for `ms`
+   * milliseconds, e.g. to prune the cache of a bot that stays in one place, where distance-based unloading
+   * never kicks in. Ages are tracked as described for `getColumnAge`, so a first call that starts the clock
+   * unloads nothing, even with `ms` 0. Returns the number of columns unloaded.
+   */
+  unloadOlderThan(ms: number): number
+  /**
+   * Copies a loaded column (by coordinates or `chunkKey`) into another world of the same version, replacing
+   * any column loaded there, without going through JS. The copy is independent of the original. Returns false
+   * if the column isn't loaded in this world.
+   */
+  copyColumnTo(other: World, chunkX: number | bigint, chunkZ?: number | undefined | null): boolean
+  /**
+   * Overlays another world of the same version onto this one, e.g. a backup onto the live cache. Without
+   * `region`, every column loaded in `other` is copied over, replacing the column loaded here. With `region`,
+   * only the blocks and block entities inside it are copied, into columns loaded in both worlds, and blocks
+   * that change are reported like any other block change. Returns the number of columns merged.
+   */
+  mergeFrom(other: World, region?: Region | null): number
+  /**
+   * Creates an independent copy of this world: same version and options (unloaded write mode, write
+   * deduplication, cold column policy, raycast acceleration), with deep copies of every loaded (and compressed)
+   * column, so destructive analyses or simulations can't touch the original. Watchers, subscriptions,
+   * transactions, deferred writes and attached region directories aren't copied. `fork` is cheaper when only a
+   * few columns will be written.
+   */
+  clone(): World
+  /**
+   * Creates a copy-on-write fork of this world: like `clone`, but columns aren't copied up front. Both worlds
+   * keep reading the same columns, and the first write to one of them, from either world, gives the writer its
+   * own copy of that column, so neither sees the other's writes. Suits read-mostly uses like trying out a few
+   * placements. Columns shared with a fork aren't compressed by the cold column policy until one side writes
+   * them.
+   */
+  fork(): World
+}
+/** A cuboid block selection defined by two inclusive corners. */
+export declare class Region {
+  /** Creates a region spanning both corners, in any order. */
+  constructor(a: WorldCoords, b: WorldCoords)
+  get min(): WorldCoords
+  get max(): WorldCoords
+  /** Number of blocks in the region. */
+  get volume(): number
+  contains(pos: WorldCoords): boolean
+  /**
+   * Returns a copy grown by the given amount on every side (per axis when `dy`/`dz` are given).
+   * Negative amounts shrink the region, down to a single block.
+   */
+  expand(dx: number, dy?: number | undefined | null, dz?: number | undefined | null): Region
+  /** Returns the overlap of both regions, or `null` if they don't overlap. */
+  intersect(other: Region): Region | null
+  /** Lists every position in the region as a flat `Int32Array` of `[x, y, z]` triples (Y outermost, X fastest). */
+  iterBlocks(): Int32Array
+}
+/**
+ * A set of named worlds (one per dimension) for one Minecraft version, sharing their block and biome data.
+ * Dimension names may be given with or without the `minecraft:` prefix.
+ */
+export declare class Universe {
+  /** Creates an empty universe for the version. Worlds are created as they're first asked for. */
+  constructor(versionString: string)
+  /**
+   * Gets the world of a dimension (e.g. `overworld`, `the_nether`, `the_end`), creating it if needed.
+   * Every call returns a handle to the same world. A new world spans the dimension's Y range: `minY` and
+   * `height` if given (from the dimension type the server sent), else the vanilla range for the Nether and the
+   * End (0..256) and -64..320 for anything else. They're ignored for a world that already exists.
+   */
+  world(name: string, minY?: number | undefined | null, height?: number | undefined | null): NapiWorld
+  hasWorld(name: string): boolean
+  /** Names of the dimensions that have a world, sorted. */
+  dimensions(): Array<string>
+  /**
+   * Makes a dimension the current one (e.g. on a respawn packet after going through a portal) and returns its
+   * world, creating it if needed (`minY` and `height` as for `world`). The previous dimension's world keeps its
+   * columns; unload them or call `removeWorld` if they won't be needed again.
+   */
+  switchDimension(name: string, minY?: number | undefined | null, height?: number | undefined | null): NapiWorld
+  /** Name of the current dimension, if `switchDimension` was called. */
+  get currentDimension(): string | null
+  /** The current dimension's world, if any. */
+  currentWorld(): NapiWorld | null
+  /**
+   * Drops a dimension's world from the universe, stopping its prefetcher. Handles to it obtained earlier keep
+   * working on their own. Returns false if there was no such world.
+   */
+  removeWorld(name: string): boolean
+}
+/**
+ * A block template: a cuboid of state IDs stored Y outermost, then Z, with X fastest (like chunk sections).
+ * Positions holding `0xFFFFFFFF` are left alone when pasting and ignored when diffing.
+ */
+export declare class Schematic {
+  /** Creates a schematic of the given size (`x` wide, `y` tall, `z` long) from its state IDs. */
+  constructor(size: WorldCoords, states: Uint32Array)
+  get size(): WorldCoords
+  /** State ID at a position relative to the schematic's minimum corner, or `null` if it's outside or void. */
+  getStateId(x: number, y: number, z: number): number | null
+  /** All state IDs, in storage order. */
+  states(): Uint32Array
+  /** Entities stored with the schematic (only structure templates have them). */
+  entities(): Array<SchematicEntity>
+}
+/**
+ * Read-only access to one loaded column, sharing the world's storage instead of copying it. A view keeps
+ * reading the column it was made for, even after the world unloads or replaces it (see `is_current`).
+ */
+export declare class ColumnView {
+  get chunkX(): number
+  get chunkZ(): number
+  /** Checks whether the column is still the one the world has loaded at its position. */
+  isCurrent(): boolean
+  /** Gets the state ID of the block at the given world coordinates. Positions outside this column read as air. */
+  getBlockStateId(x: number, y: number, z: number): number
+  /** Gets the biome ID at the given world coordinates. Positions outside this column read as biome 0. */
+  getBiomeId(x: number, y: number, z: number): number
+  /**
+   * Gets the state IDs of all 4096 blocks of a section, indexed by `(y * 16 + z) * 16 + x` (section-relative).
+   * Missing sections read as air.
+   */
+  getSectionBlocks(sectionY: number): Uint32Array
+  /**
+   * Exports the state IDs of every block of the column, section after section from the bottom of the world,
+   * each indexed like `get_section_blocks`.
    */
-  raycast(origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null): RaycastResult | null
+  getAllBlocks(): Uint32Array
 }
@@ -0,0 +1,73 @@
+// src/aging.rs
+use crate::chunk_keys::column_coords;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+impl NapiWorld {
+    // Access times are only kept once asked for, so block reads don't pay for them otherwise
+    fn track_column_ages(&self) -> bool {
+        self.cold.track_ages(self.columns.iter().map(|entry| *entry.key()))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Gets how many milliseconds ago a loaded column (by coordinates or `chunkKey`) was last read, written or
+    /// loaded, for custom unloading policies. Access times are kept from the first call to this or
+    /// `unloadOlderThan` on; columns loaded before count as accessed then. Returns null if the column isn't
+    /// loaded.
+    #[napi]
+    pub fn get_column_age(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<Option<f64>> {
+        let coords = column_coords(chunk_x, chunk_z)?;
+        self.track_column_ages();
+        Ok(self.cold.age_ms(coords).map(|age| age as f64))
+    }
+
+    /// Unloads every column (compressed ones included, see `setColdColumnPolicy`) not accessed for `ms`
+    /// milliseconds, e.g. to prune the cache of a bot that stays in one place, where distance-based unloading
+    /// never kicks in. Ages are tracked as described for `getColumnAge`, so a first call that starts the clock
+    /// unloads nothing, even with `ms` 0. Returns the number of columns unloaded.
+    #[napi]
+    pub fn unload_older_than(&self, ms: f64) -> Result<u32> {
+        if ms.is_nan() || ms < 0.0 {
+            return Err(napi::Error::new(napi::Status::InvalidArg, format!("ms must not be negative, got {}", ms)));
+        }
+        if self.track_column_ages() {
+            return Ok(0);
+        }
+        let mut candidates: Vec<_> = self.columns.iter().map(|entry| *entry.key()).collect();
+        candidates.extend(self.cold.cold_coords());
+
+        let mut unloaded = 0;
+        for coords in candidates {
+            if self.cold.age_ms(coords).is_some_and(|age| age as f64 >= ms) {
+                self.remove_column(coords);
+                unloaded += 1;
+            }
+        }
+        Ok(unloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ChunkColumn;
+    use crate::coords::ChunkCoords;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::{ColumnStore, NapiWorld};
+    use std::sync::Arc;
+
+    #[test]
+    fn the_first_unload_only_starts_the_clock() {
+        let world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(test_registry()), Arc::new(test_biomes()));
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, ChunkColumn::new());
+        world.insert_column(ChunkCoords { x: 1, z: 0 }, ChunkColumn::new());
+
+        assert_eq!(world.unload_older_than(0.0).unwrap(), 0);
+        assert_eq!(world.columns.len(), 2);
+        assert_eq!(world.unload_older_than(60_000.0).unwrap(), 0);
+        assert_eq!(world.unload_older_than(0.0).unwrap(), 2);
+        assert!(world.columns.is_empty());
+    }
+}
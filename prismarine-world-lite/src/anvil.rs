@@ -0,0 +1,869 @@
+// src/anvil.rs
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::coords::{ChunkCoords, WorldCoords, BIOME_SECTION_VOLUME, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_VOLUME};
+use crate::nbt::{read_nbt, write_nbt, Tag};
+use crate::palette::needed_bits;
+use crate::registry::{BiomeRegistry, BlockRegistry};
+use crate::world::NapiWorld;
+use dashmap::{DashMap, DashSet};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use memmap2::Mmap;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::fs::File;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Region files (r.<x>.<z>.mca) hold 32x32 columns. They start with 1024 big endian u32 locations (sector offset << 8 |
+// sector count, in 4 KiB sectors) and 1024 timestamps. Each column is stored as u32 length, u8 compression, then
+// the compressed NBT; compression types with the high bit set live in a separate c.<x>.<z>.mcc file instead.
+pub const SECTOR_SIZE: usize = 4096;
+pub const HEADER_SIZE: usize = 2 * SECTOR_SIZE;
+
+pub const COMPRESSION_GZIP: u8 = 1;
+pub const COMPRESSION_ZLIB: u8 = 2;
+pub const COMPRESSION_NONE: u8 = 3;
+const COMPRESSION_EXTERNAL: u8 = 128;
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+// Index of a column in its region file's header
+pub fn region_slot(coords: ChunkCoords) -> usize {
+    (coords.x & 31) as usize + (coords.z & 31) as usize * 32
+}
+
+pub fn region_file_name(region_x: i32, region_z: i32) -> String {
+    format!("r.{}.{}.mca", region_x, region_z)
+}
+
+// Unpacks values stored the 1.16+ way: each long holds `64 / bits` values from its low bits, the rest is padding
+pub fn unpack_padded(longs: &[i64], bits: usize, count: usize) -> Vec<u32> {
+    let values_per_long = 64 / bits;
+    let mask = (1u64 << bits) - 1;
+    (0..count)
+        .map(|index| {
+            let long = longs.get(index / values_per_long).copied().unwrap_or(0) as u64;
+            ((long >> ((index % values_per_long) * bits)) & mask) as u32
+        })
+        .collect()
+}
+
+// Packs values the 1.16+ way, the inverse of `unpack_padded`
+pub fn pack_padded(values: &[u32], bits: usize) -> Vec<i64> {
+    let values_per_long = 64 / bits;
+    values
+        .chunks(values_per_long)
+        .map(|chunk| chunk.iter().enumerate().fold(0u64, |long, (i, &v)| long | (v as u64) << (i * bits)) as i64)
+        .collect()
+}
+
+// DataVersion written into saved chunks, so the game knows which format (and upgrades) they need.
+// Only versions using the 1.18+ chunk format are listed.
+pub fn data_version_for(minecraft_version: &str) -> Option<i32> {
+    Some(match minecraft_version {
+        "1.18" => 2860,
+        "1.18.1" => 2865,
+        "1.18.2" => 2975,
+        "1.19" => 3105,
+        "1.19.1" => 3117,
+        "1.19.2" => 3120,
+        "1.19.3" => 3218,
+        "1.19.4" => 3337,
+        "1.20" => 3463,
+        "1.20.1" => 3465,
+        "1.20.2" => 3578,
+        "1.20.3" => 3698,
+        "1.20.4" => 3700,
+        "1.20.5" => 3837,
+        "1.20.6" => 3839,
+        "1.21" => 3953,
+        "1.21.1" => 3955,
+        "1.21.2" => 4080,
+        "1.21.3" => 4082,
+        "1.21.4" => 4189,
+        _ => return None,
+    })
+}
+
+// Reads a paletted section container from disk NBT (`palette` plus packed `data`), mapping each palette entry
+fn read_disk_container(container: &Tag, count: usize, min_bits: usize, resolve: impl Fn(&Tag) -> u32) -> Vec<u32> {
+    let palette: Vec<u32> = container.get("palette").and_then(Tag::as_list).unwrap_or(&[]).iter().map(resolve).collect();
+    match (palette.len(), container.get("data").and_then(Tag::as_long_array)) {
+        (0, _) => vec![0; count],
+        (1, _) | (_, None) => vec![palette[0]; count],
+        (len, Some(data)) => {
+            let bits = needed_bits(len - 1).max(min_bits);
+            unpack_padded(data, bits, count)
+                .into_iter()
+                .map(|index| palette.get(index as usize).copied().unwrap_or(0))
+                .collect()
+        }
+    }
+}
+
+// Resolves a block state palette entry (`Name` plus optional `Properties`), as used by chunks and structures
+pub fn state_from_nbt(entry: &Tag, registry: &BlockRegistry) -> Option<u32> {
+    let name = entry.get("Name").and_then(Tag::as_str)?;
+    let properties: Vec<(String, String)> = entry
+        .get("Properties")
+        .and_then(Tag::as_compound)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+        .collect();
+    registry.state_id_from_properties(name, &properties)
+}
+
+// Builds a column from the NBT of a 1.18+ disk chunk. Unknown blocks become air and unknown biomes biome 0.
+pub fn column_from_nbt(root: &Tag, registry: &BlockRegistry, biomes: &BiomeRegistry) -> std::io::Result<ChunkColumn> {
+    let Some(sections) = root.get("sections").and_then(Tag::as_list) else {
+        return Err(invalid(if root.get("Level").is_some() { "Chunks from before 1.18 aren't supported" } else { "Chunk has no sections" }));
+    };
+
+    let mut column = ChunkColumn::new();
+    for section_tag in sections {
+        let Some(section_y) = section_tag.get("Y").and_then(Tag::as_i64) else { continue; };
+        if !(MIN_SECTION_Y as i64..=MAX_SECTION_Y as i64).contains(&section_y) { continue; }
+        // Sections only holding light data have no blocks
+        let Some(block_states) = section_tag.get("block_states") else { continue; };
+
+        let states = read_disk_container(block_states, SECTION_VOLUME, 4, |entry| {
+            state_from_nbt(entry, registry).unwrap_or(0)
+        });
+        let mut section = ChunkSection::from_block_states(&states, registry);
+
+        if let Some(biome_container) = section_tag.get("biomes") {
+            let biome_ids = read_disk_container(biome_container, BIOME_SECTION_VOLUME, 1, |entry| {
+                entry.as_str().and_then(|name| biomes.biome_id_by_name(name)).unwrap_or(0)
+            });
+            section.set_biomes(&biome_ids);
+        }
+        column.insert_section(section_y as i32, section);
+    }
+
+    // Block entities carry their absolute position
+    for entity in root.get("block_entities").and_then(Tag::as_list).unwrap_or_default() {
+        let coord = |name| entity.get(name).and_then(Tag::as_i64).map(|v| v as i32);
+        let (Some(x), Some(y), Some(z)) = (coord("x"), coord("y"), coord("z")) else { continue; };
+        column.set_block_entity(WorldCoords { x, y, z }, entity.clone());
+    }
+    Ok(column)
+}
+
+// Writes a paletted section container in the disk layout: a local palette, plus packed `data` unless the palette
+// has a single entry
+fn write_disk_container(values: &[u32], min_bits: usize, entry: impl Fn(u32) -> Tag) -> Tag {
+    let mut palette: Vec<u32> = Vec::new();
+    let mut palette_index: HashMap<u32, u32> = HashMap::new();
+    let indices: Vec<u32> = values
+        .iter()
+        .map(|&value| {
+            *palette_index.entry(value).or_insert_with(|| {
+                palette.push(value);
+                palette.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    let mut entries = vec![("palette".to_string(), Tag::List(palette.iter().map(|&value| entry(value)).collect()))];
+    if palette.len() > 1 {
+        let bits = needed_bits(palette.len() - 1).max(min_bits);
+        entries.push(("data".to_string(), Tag::LongArray(pack_padded(&indices, bits))));
+    }
+    Tag::Compound(entries)
+}
+
+// Builds the NBT of a 1.18+ disk chunk from a column, the inverse of `column_from_nbt`. Light and heightmaps
+// are left out; the game recomputes them when the chunk is loaded.
+pub fn column_to_nbt(
+    column: &ChunkColumn,
+    coords: ChunkCoords,
+    data_version: i32,
+    registry: &BlockRegistry,
+    biomes: &BiomeRegistry,
+) -> Tag {
+    let sections = column
+        .sections()
+        .map(|(section_y, section)| {
+            let states: Vec<u32> = (0..SECTION_VOLUME).map(|i| section.block_states().get(i)).collect();
+            let block_states = write_disk_container(&states, 4, |state_id| {
+                let name = registry.block(state_id).map_or("air", |b| b.name.as_str());
+                let mut entries = vec![("Name".to_string(), Tag::String(format!("minecraft:{}", name)))];
+                let properties = registry.state_properties(state_id);
+                if !properties.is_empty() {
+                    let properties = properties.into_iter().map(|(key, value)| (key, Tag::String(value))).collect();
+                    entries.push(("Properties".to_string(), Tag::Compound(properties)));
+                }
+                Tag::Compound(entries)
+            });
+
+            let biome_ids: Vec<u32> = (0..BIOME_SECTION_VOLUME).map(|i| section.biomes().get(i)).collect();
+            let biome_container = write_disk_container(&biome_ids, 1, |biome_id| {
+                let name = biomes.biome(biome_id).map_or("plains", |b| b.name.as_str());
+                Tag::String(format!("minecraft:{}", name))
+            });
+
+            Tag::Compound(vec![
+                ("Y".to_string(), Tag::Byte(section_y as i8)),
+                ("block_states".to_string(), block_states),
+                ("biomes".to_string(), biome_container),
+            ])
+        })
+        .collect();
+
+    Tag::Compound(vec![
+        ("DataVersion".to_string(), Tag::Int(data_version)),
+        ("xPos".to_string(), Tag::Int(coords.x)),
+        ("zPos".to_string(), Tag::Int(coords.z)),
+        ("yPos".to_string(), Tag::Int(MIN_SECTION_Y)),
+        ("Status".to_string(), Tag::String("minecraft:full".to_string())),
+        ("sections".to_string(), Tag::List(sections)),
+        ("block_entities".to_string(), Tag::List(column.block_entities().map(|(_, entity)| entity.clone()).collect())),
+    ])
+}
+
+pub fn compress(compression: u8, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    match compression {
+        COMPRESSION_GZIP => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        COMPRESSION_ZLIB => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        COMPRESSION_NONE => Ok(data.to_vec()),
+        _ => Err(invalid(&format!("Unsupported chunk compression type: {}", compression))),
+    }
+}
+
+// A column as stored in a region file: its slot, its timestamp, then the compression byte followed by the compressed
+// NBT (just the compression byte for externally stored columns)
+type StoredColumn = (usize, u32, Vec<u8>);
+
+// A column to write: its coordinates, then the compression byte followed by the compressed NBT
+type CompressedColumn = (ChunkCoords, Vec<u8>);
+
+// Column data longer than this doesn't fit the header's 8-bit sector count and goes to a c.<x>.<z>.mcc file
+const MAX_REGION_COLUMN_LEN: usize = 255 * SECTOR_SIZE - 4;
+
+fn external_file_name(coords: ChunkCoords) -> String {
+    format!("c.{}.{}.mcc", coords.x, coords.z)
+}
+
+// Writes a region file from stored columns sorted by slot. The file is written next to the target and renamed
+// over it, so memory maps of the old file stay valid.
+fn write_region_file(path: &Path, columns: &[StoredColumn]) -> std::io::Result<()> {
+    let mut out = vec![0u8; HEADER_SIZE];
+    for (slot, timestamp, stored) in columns {
+        let sector = out.len() / SECTOR_SIZE;
+        let sectors = (stored.len() + 4).div_ceil(SECTOR_SIZE);
+        if sectors > 255 { return Err(invalid("Column too large for a region file")); }
+
+        out[slot * 4..slot * 4 + 4].copy_from_slice(&((sector as u32) << 8 | sectors as u32).to_be_bytes());
+        out[SECTOR_SIZE + slot * 4..SECTOR_SIZE + slot * 4 + 4].copy_from_slice(&timestamp.to_be_bytes());
+        out.extend_from_slice(&(stored.len() as u32).to_be_bytes());
+        out.extend_from_slice(stored);
+        out.resize((sector + sectors) * SECTOR_SIZE, 0);
+    }
+
+    let temp_path = path.with_extension("mca.tmp");
+    std::fs::write(&temp_path, &out)?;
+    std::fs::rename(&temp_path, path)
+}
+
+// The stored columns of an existing region file, by slot. Externally stored entries are kept as they are (their
+// .mcc files stay valid); damaged entries are skipped.
+fn read_stored_columns(path: &Path) -> std::io::Result<HashMap<usize, (u32, Vec<u8>)>> {
+    let file = match std::fs::read(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e),
+    };
+    if file.len() < HEADER_SIZE { return Ok(HashMap::new()); }
+
+    Ok((0..1024)
+        .filter_map(|slot| {
+            let location = u32::from_be_bytes(file[slot * 4..slot * 4 + 4].try_into().unwrap());
+            let offset = (location >> 8) as usize * SECTOR_SIZE;
+            if location == 0 || offset < HEADER_SIZE || offset + 5 > file.len() { return None; }
+            let timestamp = u32::from_be_bytes(file[SECTOR_SIZE + slot * 4..SECTOR_SIZE + slot * 4 + 4].try_into().unwrap());
+            let compression = file[offset + 4];
+            if compression & COMPRESSION_EXTERNAL != 0 { return Some((slot, (timestamp, vec![compression]))); }
+            let length = u32::from_be_bytes(file[offset..offset + 4].try_into().unwrap()) as usize;
+            if length == 0 || offset + 4 + length > file.len() { return None; }
+            Some((slot, (timestamp, file[offset + 4..offset + 4 + length].to_vec())))
+        })
+        .collect())
+}
+
+// Writes compressed columns (compression byte plus data) of one region into its file in `dir`, keeping the file's
+// other columns. Columns too large for the region file go to .mcc files; stale .mcc files of rewritten columns are
+// removed.
+fn merge_region_file(dir: &Path, region_x: i32, region_z: i32, columns: Vec<CompressedColumn>) -> std::io::Result<()> {
+    let file_path = dir.join(region_file_name(region_x, region_z));
+    let mut stored = read_stored_columns(&file_path)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as u32);
+    for (coords, data) in columns {
+        let external_path = dir.join(external_file_name(coords));
+        let data = if data.len() > MAX_REGION_COLUMN_LEN {
+            std::fs::write(&external_path, &data[1..])?;
+            vec![data[0] | COMPRESSION_EXTERNAL]
+        } else {
+            let was_external = stored.get(&region_slot(coords)).is_some_and(|(_, old)| old[0] & COMPRESSION_EXTERNAL != 0);
+            if was_external {
+                match std::fs::remove_file(&external_path) {
+                    Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+            }
+            data
+        };
+        stored.insert(region_slot(coords), (timestamp, data));
+    }
+
+    let mut stored: Vec<StoredColumn> = stored.into_iter().map(|(slot, (timestamp, data))| (slot, timestamp, data)).collect();
+    stored.sort_by_key(|(slot, _, _)| *slot);
+    write_region_file(&file_path, &stored)
+}
+
+pub fn decompress(compression: u8, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    match compression {
+        COMPRESSION_GZIP => { GzDecoder::new(data).read_to_end(&mut out)?; }
+        COMPRESSION_ZLIB => { ZlibDecoder::new(data).read_to_end(&mut out)?; }
+        COMPRESSION_NONE => out.extend_from_slice(data),
+        _ => return Err(invalid(&format!("Unsupported chunk compression type: {}", compression))),
+    }
+    Ok(out)
+}
+
+// Decompresses NBT that may be gzip or zlib compressed, telling them apart by their header
+pub fn decompress_detected(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    // Uncompressed NBT starts with the root compound's type (10)
+    let compression = match data {
+        [0x1f, 0x8b, ..] => COMPRESSION_GZIP,
+        [0x78, ..] => COMPRESSION_ZLIB,
+        _ => COMPRESSION_NONE,
+    };
+    decompress(compression, data)
+}
+
+// A directory of region files, memory-mapped as they're first needed
+pub struct RegionDir {
+    pub path: PathBuf,
+    // (region x, region z) -> mapped file, or None if there's no such file
+    files: DashMap<(i32, i32), Option<Arc<Mmap>>>,
+    // Columns known to be absent from (or unreadable in) the region files
+    missing: DashSet<ChunkCoords>,
+}
+
+impl RegionDir {
+    pub fn new(path: PathBuf) -> Self {
+        RegionDir { path, files: DashMap::new(), missing: DashSet::new() }
+    }
+
+    fn region_file(&self, region_x: i32, region_z: i32) -> Option<Arc<Mmap>> {
+        self.files
+            .entry((region_x, region_z))
+            .or_insert_with(|| {
+                let file = File::open(self.path.join(region_file_name(region_x, region_z))).ok()?;
+                // Safety: the mapping is only read, and the files must not be changed by others while attached
+                // (documented on `attach_region_dir`)
+                unsafe { Mmap::map(&file) }.ok().map(Arc::new)
+            })
+            .clone()
+    }
+
+    // Drops the cached file mappings and missing-column records, after the files changed
+    fn forget_files(&self) {
+        self.files.clear();
+        self.missing.clear();
+    }
+
+    // The decompressed NBT of a column, or None if the region files don't have it
+    pub fn read_column_nbt(&self, coords: ChunkCoords) -> std::io::Result<Option<Vec<u8>>> {
+        let Some(file) = self.region_file(coords.x >> 5, coords.z >> 5) else { return Ok(None); };
+        if file.len() < HEADER_SIZE { return Ok(None); }
+
+        let slot = region_slot(coords) * 4;
+        let location = u32::from_be_bytes(file[slot..slot + 4].try_into().unwrap());
+        if location == 0 { return Ok(None); }
+        let offset = (location >> 8) as usize * SECTOR_SIZE;
+        if offset < HEADER_SIZE || offset + 5 > file.len() { return Err(invalid("Column location outside the region file")); }
+
+        let length = u32::from_be_bytes(file[offset..offset + 4].try_into().unwrap()) as usize;
+        let compression = file[offset + 4];
+        if compression & COMPRESSION_EXTERNAL != 0 {
+            let data = std::fs::read(self.path.join(external_file_name(coords)))?;
+            return decompress(compression & !COMPRESSION_EXTERNAL, &data).map(Some);
+        }
+        if length == 0 || offset + 4 + length > file.len() { return Err(invalid("Column data past the end of the region file")); }
+        decompress(compression, &file[offset + 5..offset + 4 + length]).map(Some)
+    }
+
+    // Parses a column from the region files. Absent and unreadable columns are remembered and not retried.
+    pub fn load_column(&self, coords: ChunkCoords, registry: &BlockRegistry, biomes: &BiomeRegistry) -> Option<ChunkColumn> {
+        if self.missing.contains(&coords) { return None; }
+        let result = self.read_column_nbt(coords).and_then(|nbt| match nbt {
+            Some(nbt) => column_from_nbt(&read_nbt(&nbt)?, registry, biomes).map(Some),
+            None => Ok(None),
+        });
+        match result {
+            Ok(Some(column)) => Some(column),
+            Ok(None) => {
+                self.missing.insert(coords);
+                None
+            }
+            Err(e) => {
+                eprintln!("Failed to read chunk ({}, {}) from region files: {}", coords.x, coords.z, e);
+                self.missing.insert(coords);
+                None
+            }
+        }
+    }
+}
+
+impl NapiWorld {
+    // Makes sure the column is loaded, reading it from the attached region directory if needed.
+    // Returns whether the column is loaded.
+    pub(crate) fn ensure_column(&self, coords: ChunkCoords) -> bool {
+        let loaded = self.columns.contains_key(&coords);
+        self.perf.column_lookup(loaded);
+        if loaded {
+            self.cold.touch(coords);
+            return true;
+        }
+        if self.thaw_column(coords) { return true; }
+        let Some(regions) = self.region_dir.read().unwrap().clone() else { return false; };
+        match regions.load_column(coords, &self.registry, &self.biomes) {
+            Some(column) => {
+                self.insert_column(coords, column);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // The DataVersion to write into disk chunks: the one given, else the one for the world's version
+    fn resolve_data_version(&self, data_version: Option<i32>) -> Result<i32> {
+        data_version
+            .or_else(|| data_version_for(&self.registry.minecraft_version))
+            .ok_or_else(|| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown data version for {}; pass dataVersion", self.registry.minecraft_version),
+            ))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Attaches a directory of `.mca` region files (e.g. `<world>/region`) as a lazy column source. Region files
+    /// are memory-mapped when first needed, and a column is parsed the first time a block, biome or raycast query
+    /// touches it; whole-world queries only see columns loaded so far. Only 1.18+ chunks are read.
+    /// The files must not be modified by other programs while attached.
+    #[napi]
+    pub fn attach_region_dir(&self, path: String) -> Result<()> {
+        let path = PathBuf::from(path);
+        if !path.is_dir() {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Not a directory: {}", path.display()),
+            ));
+        }
+        *self.region_dir.write().unwrap() = Some(Arc::new(RegionDir::new(path)));
+        Ok(())
+    }
+
+    /// Detaches the region directory. Columns already read from it stay loaded.
+    #[napi]
+    pub fn detach_region_dir(&self) {
+        *self.region_dir.write().unwrap() = None;
+    }
+
+    /// Writes all loaded columns to `.mca` region files in `path` (created if needed), in the 1.18+ disk format the
+    /// game and external tools read. Columns already in existing region files that aren't loaded are kept; loaded
+    /// ones replace them; columns too large for a region file go to `.mcc` files, as the game writes them.
+    /// `dataVersion` defaults to the world's version and must be given for versions not known here. Light and
+    /// heightmaps aren't written; the game recomputes them. Returns the number of columns written.
+    #[napi]
+    pub fn save_to_region_dir(&self, path: String, data_version: Option<i32>) -> Result<u32> {
+        let data_version = self.resolve_data_version(data_version)?;
+        let path = PathBuf::from(path);
+        let io_error = |e: Error| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to write region files to {}: {}", path.display(), e),
+        );
+        std::fs::create_dir_all(&path).map_err(io_error)?;
+
+        // Compress every column first, grouped by region
+        let mut regions: HashMap<(i32, i32), Vec<CompressedColumn>> = HashMap::new();
+        for (coords, column) in self.all_columns() {
+            let column = column.read().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire read lock for chunk".to_string(),
+            ))?;
+            let nbt = write_nbt(&column_to_nbt(&column, coords, data_version, &self.registry, &self.biomes));
+            let mut stored = vec![COMPRESSION_ZLIB];
+            stored.extend(compress(COMPRESSION_ZLIB, &nbt).map_err(io_error)?);
+            regions.entry((coords.x >> 5, coords.z >> 5)).or_default().push((coords, stored));
+        }
+
+        let mut written = 0;
+        for ((region_x, region_z), columns) in regions {
+            written += columns.len() as u32;
+            merge_region_file(&path, region_x, region_z, columns).map_err(io_error)?;
+        }
+
+        // An attached directory has to re-map the rewritten files
+        if let Some(regions) = self.region_dir.read().unwrap().as_ref() {
+            if regions.path == path { regions.forget_files(); }
+        }
+        Ok(written)
+    }
+
+    /// Serializes a loaded column to the chunk NBT stored in region files (the same structure
+    /// `saveToRegionDir` writes), uncompressed unless `compression` is `'gzip'` or `'zlib'`.
+    /// `dataVersion` defaults to the world's version.
+    #[napi(ts_args_type = "chunkX: number, chunkZ: number, compression?: 'gzip' | 'zlib' | 'none' | null, dataVersion?: number | null")]
+    pub fn column_to_nbt(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        compression: Option<String>,
+        data_version: Option<i32>,
+    ) -> Result<Buffer> {
+        let compression = match compression.as_deref() {
+            None | Some("none") => COMPRESSION_NONE,
+            Some("gzip") => COMPRESSION_GZIP,
+            Some("zlib") => COMPRESSION_ZLIB,
+            Some(other) => return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown compression: {}", other),
+            )),
+        };
+        let data_version = self.resolve_data_version(data_version)?;
+        let coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(coords);
+        let column_lock = self.columns.get(&coords).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Chunk at {}, {} not loaded", chunk_x, chunk_z),
+        ))?;
+        let column = column_lock.read().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire read lock for chunk".to_string(),
+        ))?;
+
+        let nbt = write_nbt(&column_to_nbt(&column, coords, data_version, &self.registry, &self.biomes));
+        let data = compress(compression, &nbt).map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to compress chunk NBT: {}", e),
+        ))?;
+        Ok(data.into())
+    }
+
+    /// Loads the column at the given chunk position from disk-format chunk NBT (1.18+, as stored in region files or
+    /// returned by `columnToNbt`), replacing any loaded column there. Gzip or zlib compressed NBT is detected and
+    /// decompressed.
+    #[napi]
+    pub fn load_column_from_nbt(&self, chunk_x: i32, chunk_z: i32, nbt_buffer: Buffer) -> Result<()> {
+        let column = decompress_detected(nbt_buffer.as_ref())
+            .and_then(|nbt| read_nbt(&nbt))
+            .and_then(|root| column_from_nbt(&root, &self.registry, &self.biomes))
+            .map_err(|e| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Failed to parse chunk NBT: {}", e),
+            ))?;
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, column);
+        Ok(())
+    }
+
+    /// Loads a column from the attached region directory now, unless it's already loaded.
+    /// Returns whether the column is loaded afterwards.
+    #[napi]
+    pub fn load_region_column(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        self.ensure_column(ChunkCoords { x: chunk_x, z: chunk_z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::{BiomeCoords, SectionRelCoords};
+    use crate::registry::test_support::{test_biomes, test_registry};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pwl-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn zlib_column(data: &[u8]) -> Vec<u8> {
+        let mut stored = vec![COMPRESSION_ZLIB];
+        stored.extend(compress(COMPRESSION_ZLIB, data).unwrap());
+        stored
+    }
+
+    fn read(dir: &Path, x: i32, z: i32) -> Option<Vec<u8>> {
+        RegionDir::new(dir.to_path_buf()).read_column_nbt(ChunkCoords { x, z }).unwrap()
+    }
+
+    fn string(value: &str) -> Tag {
+        Tag::String(value.to_string())
+    }
+
+    fn compound(entries: Vec<(&str, Tag)>) -> Tag {
+        Tag::Compound(entries.into_iter().map(|(name, tag)| (name.to_string(), tag)).collect())
+    }
+
+    #[test]
+    fn columns_round_trip_through_disk_nbt() {
+        let (registry, biomes) = (test_registry(), test_biomes());
+        // Default states of some of the test blocks
+        let defaults = [0, 1, 2, 3, 4, 7, 10, 18, 26, 42];
+        let mut column = ChunkColumn::new();
+        let states: Vec<u32> = (0..SECTION_VOLUME).map(|i| defaults[(i * 7 + i / 13) % defaults.len()]).collect();
+        let mut section = ChunkSection::from_block_states(&states, &registry);
+        section.set_biomes(&(0..BIOME_SECTION_VOLUME as u32).map(|i| i % 3).collect::<Vec<_>>());
+        column.insert_section(-2, section);
+        column.insert_section(5, ChunkSection::from_block_states(&vec![42; SECTION_VOLUME], &registry));
+        let entity_coords = WorldCoords { x: 3, y: 80, z: 9 };
+        let entity = compound(vec![("id", string("minecraft:chest")), ("x", Tag::Int(3)), ("y", Tag::Int(80)), ("z", Tag::Int(9))]);
+        column.set_block_entity(entity_coords, entity.clone());
+
+        let nbt = write_nbt(&column_to_nbt(&column, ChunkCoords { x: 1, z: 2 }, 3953, &registry, &biomes));
+        let loaded = column_from_nbt(&read_nbt(&nbt).unwrap(), &registry, &biomes).unwrap();
+        assert_eq!(loaded.sections().map(|(y, _)| y).collect::<Vec<_>>(), [-2, 5]);
+        for (y, section) in column.sections() {
+            let loaded_section = loaded.get_section(y).unwrap();
+            assert_eq!(section.solid_block_count(), loaded_section.solid_block_count());
+            for index in 0..SECTION_VOLUME {
+                let coords = SectionRelCoords { x: (index & 15) as i32, y: (index >> 8) as i32, z: ((index >> 4) & 15) as i32 };
+                assert_eq!(section.get_block_state_id(coords), loaded_section.get_block_state_id(coords), "section {} block {}", y, index);
+            }
+            for index in 0..BIOME_SECTION_VOLUME as i32 {
+                let coords = BiomeCoords { x: index & 3, y: index >> 4, z: (index >> 2) & 3 };
+                assert_eq!(section.get_biome_id(coords), loaded_section.get_biome_id(coords), "section {} biome {}", y, index);
+            }
+        }
+        assert_eq!(loaded.block_entity(entity_coords), Some(&entity));
+    }
+
+    #[test]
+    fn compressed_nbt_is_detected() {
+        let nbt = write_nbt(&compound(vec![("sections", Tag::List(Vec::new()))]));
+        for compression in [COMPRESSION_GZIP, COMPRESSION_ZLIB, COMPRESSION_NONE] {
+            assert_eq!(decompress_detected(&compress(compression, &nbt).unwrap()).unwrap(), nbt, "compression {}", compression);
+        }
+        assert!(decompress_detected(&[0x1f, 0x8b, 8, 0]).is_err());
+        assert!(decompress_detected(&[0x78, 0x9c, 0xFF]).is_err());
+        assert_eq!(decompress_detected(&[]).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn malformed_disk_nbt_is_rejected_or_read_leniently() {
+        let (registry, biomes) = (test_registry(), test_biomes());
+        let load = |root: Tag| column_from_nbt(&root, &registry, &biomes);
+        assert!(load(compound(vec![])).is_err());
+        assert!(load(compound(vec![("Level", compound(vec![]))])).is_err());
+        assert!(load(compound(vec![("sections", Tag::Int(1))])).is_err());
+
+        let stone = compound(vec![("Name", string("minecraft:stone"))]);
+        let section = |y: Tag, block_states: Tag| compound(vec![("Y", y), ("block_states", block_states)]);
+        let column = load(compound(vec![(
+            "sections",
+            Tag::List(vec![
+                // Skipped: no or non-numeric Y, Y outside the world, no block states, not a compound
+                compound(vec![("block_states", compound(vec![("palette", Tag::List(vec![stone.clone()]))]))]),
+                section(string("1"), compound(vec![("palette", Tag::List(vec![stone.clone()]))])),
+                section(Tag::Byte(100), compound(vec![("palette", Tag::List(vec![stone.clone()]))])),
+                compound(vec![("Y", Tag::Byte(1))]),
+                Tag::Long(7),
+                // Unknown and malformed palette entries become air, indices past the palette and missing longs too
+                section(Tag::Byte(2), compound(vec![
+                    ("palette", Tag::List(vec![stone.clone(), string("minecraft:stone"), compound(vec![("Name", string("minecraft:nope"))])])),
+                    ("data", Tag::LongArray(vec![0xF210])),
+                ])),
+                // A palette too large for the section still unpacks
+                section(Tag::Byte(3), compound(vec![
+                    ("palette", Tag::List(vec![stone.clone(); 100_000])),
+                    ("data", Tag::LongArray(vec![99_999])),
+                ])),
+                // Palette and data of the wrong types
+                section(Tag::Byte(4), compound(vec![("palette", Tag::Int(3)), ("data", Tag::String("x".to_string()))])),
+                section(Tag::Byte(5), compound(vec![("palette", Tag::List(vec![stone.clone(), stone.clone()])), ("data", Tag::IntArray(vec![1]))])),
+            ]),
+        ), (
+            "block_entities",
+            Tag::List(vec![compound(vec![("x", Tag::Int(1))]), Tag::Int(3), compound(vec![("x", Tag::Int(1)), ("y", Tag::Int(2)), ("z", Tag::Int(3))])]),
+        )]))
+        .unwrap();
+
+        assert_eq!(column.sections().map(|(y, _)| y).collect::<Vec<_>>(), [2, 3, 4, 5]);
+        let block = |y: i32, x: i32| column.get_block_state_id(WorldCoords { x, y, z: 0 });
+        assert_eq!((block(32, 0), block(32, 1), block(32, 2), block(32, 3), block(32, 4)), (1, 0, 0, 0, 1));
+        assert_eq!(block(48, 0), 1);
+        assert_eq!(block(64, 0), 0);
+        assert_eq!((block(80, 0), block(80, 1)), (1, 1));
+        assert_eq!(column.block_entities().count(), 1);
+    }
+
+    // A region file whose header only locates column (0, 0), at `location`, followed by `body`
+    fn raw_region(dir: &Path, location: u32, body: &[u8]) {
+        let mut file = vec![0u8; HEADER_SIZE];
+        file[..4].copy_from_slice(&location.to_be_bytes());
+        file.extend_from_slice(body);
+        std::fs::write(dir.join(region_file_name(0, 0)), file).unwrap();
+    }
+
+    // One sector holding a stored column: u32 length (counting the compression byte), compression, data
+    fn sector(length: u32, compression: u8, data: &[u8]) -> Vec<u8> {
+        let mut sector = length.to_be_bytes().to_vec();
+        sector.push(compression);
+        sector.extend_from_slice(data);
+        sector.resize(SECTOR_SIZE, 0);
+        sector
+    }
+
+    #[test]
+    fn malformed_region_files_are_errors() {
+        let dir = temp_dir("malformed");
+        let read_fresh = |location: u32, body: &[u8]| {
+            raw_region(&dir, location, body);
+            RegionDir::new(dir.clone()).read_column_nbt(ChunkCoords { x: 0, z: 0 })
+        };
+        let zlib = compress(COMPRESSION_ZLIB, b"column").unwrap();
+        assert_eq!(read_fresh(2 << 8 | 1, &sector(zlib.len() as u32 + 1, COMPRESSION_ZLIB, &zlib)).unwrap().as_deref(), Some(&b"column"[..]));
+
+        // Absent columns and headerless files are just missing
+        assert_eq!(read_fresh(0, &[]).unwrap(), None);
+        std::fs::write(dir.join(region_file_name(0, 0)), [0xFF; 100]).unwrap();
+        assert_eq!(RegionDir::new(dir.clone()).read_column_nbt(ChunkCoords { x: 0, z: 0 }).unwrap(), None);
+
+        assert!(read_fresh(1 << 8 | 1, &sector(7, COMPRESSION_ZLIB, &zlib)).is_err(), "location inside the header");
+        assert!(read_fresh(3 << 8 | 1, &sector(7, COMPRESSION_ZLIB, &zlib)).is_err(), "location past the end");
+        assert!(read_fresh(0xFFFF_FF01, &[]).is_err(), "largest location");
+        assert!(read_fresh(2 << 8 | 1, &sector(0, COMPRESSION_ZLIB, &zlib)).is_err(), "zero length");
+        assert!(read_fresh(2 << 8 | 1, &sector(SECTOR_SIZE as u32, COMPRESSION_ZLIB, &zlib)).is_err(), "length past the end");
+        assert!(read_fresh(2 << 8 | 1, &sector(u32::MAX, COMPRESSION_ZLIB, &zlib)).is_err(), "largest length");
+        assert!(read_fresh(2 << 8 | 1, &sector(zlib.len() as u32 + 1, 9, &zlib)).is_err(), "unknown compression");
+        assert!(read_fresh(2 << 8 | 1, &sector(100, COMPRESSION_ZLIB, &[0x78, 0x9C, 1, 2, 3])).is_err(), "corrupt zlib");
+        assert!(read_fresh(2 << 8 | 1, &sector(1, COMPRESSION_ZLIB | COMPRESSION_EXTERNAL, &[])).is_err(), "missing .mcc file");
+
+        // Random headers and sectors must never panic
+        let mut seed = 0x853c_49e6_748f_ea9bu64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..50 {
+            let mut file: Vec<u8> = (0..HEADER_SIZE + 2 * SECTOR_SIZE).map(|_| next() as u8).collect();
+            // Point some locations at the sectors after the header, so their contents get parsed too
+            for slot in (0..1024).step_by(7) {
+                file[slot * 4..slot * 4 + 3].copy_from_slice(&[0, 0, 2 + (next() % 3) as u8]);
+            }
+            std::fs::write(dir.join(region_file_name(0, 0)), &file).unwrap();
+            let regions = RegionDir::new(dir.clone());
+            for slot in 0..1024 {
+                let _ = regions.read_column_nbt(ChunkCoords { x: slot as i32 & 31, z: slot as i32 >> 5 });
+            }
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn external_columns_survive_saving_other_columns() {
+        let dir = temp_dir("external");
+        let external = ChunkCoords { x: 0, z: 0 };
+        std::fs::write(dir.join(external_file_name(external)), compress(COMPRESSION_ZLIB, b"external").unwrap()).unwrap();
+        write_region_file(
+            &dir.join(region_file_name(0, 0)),
+            &[(0, 1234, vec![COMPRESSION_ZLIB | COMPRESSION_EXTERNAL]), (1, 1234, zlib_column(b"inline"))],
+        )
+        .unwrap();
+
+        merge_region_file(&dir, 0, 0, vec![(ChunkCoords { x: 2, z: 0 }, zlib_column(b"new"))]).unwrap();
+        assert_eq!(read(&dir, 0, 0).as_deref(), Some(&b"external"[..]));
+        assert_eq!(read(&dir, 1, 0).as_deref(), Some(&b"inline"[..]));
+        assert_eq!(read(&dir, 2, 0).as_deref(), Some(&b"new"[..]));
+        let stored = read_stored_columns(&dir.join(region_file_name(0, 0))).unwrap();
+        assert_eq!(stored[&0], (1234, vec![COMPRESSION_ZLIB | COMPRESSION_EXTERNAL]));
+
+        // Rewriting the column in place makes its .mcc file stale
+        merge_region_file(&dir, 0, 0, vec![(external, zlib_column(b"rewritten"))]).unwrap();
+        assert_eq!(read(&dir, 0, 0).as_deref(), Some(&b"rewritten"[..]));
+        assert!(!dir.join(external_file_name(external)).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_slots_are_dropped_when_merging() {
+        let dir = temp_dir("merge-malformed");
+        let path = dir.join(region_file_name(0, 0));
+        let zlib = zlib_column(b"kept");
+        let mut file = vec![0u8; HEADER_SIZE];
+        // Slot 0 is readable, 1 points into the header, 2 past the end, 3 at a zero length, 4 at a length past the end
+        for (slot, location) in [(0, 2u32 << 8 | 1), (1, 1 << 8 | 1), (2, 9 << 8 | 1), (3, 3 << 8 | 1), (4, 4 << 8 | 1)] {
+            file[slot * 4..slot * 4 + 4].copy_from_slice(&location.to_be_bytes());
+        }
+        file.extend(sector(zlib.len() as u32, zlib[0], &zlib[1..]));
+        file.extend(sector(0, COMPRESSION_ZLIB, &[]));
+        file.extend(sector(u32::MAX, COMPRESSION_ZLIB, &[]));
+        std::fs::write(&path, &file).unwrap();
+
+        let stored = read_stored_columns(&path).unwrap();
+        assert_eq!(stored.keys().collect::<Vec<_>>(), [&0]);
+        merge_region_file(&dir, 0, 0, vec![(ChunkCoords { x: 5, z: 0 }, zlib_column(b"new"))]).unwrap();
+        assert_eq!(read(&dir, 0, 0).as_deref(), Some(&b"kept"[..]));
+        assert_eq!(read(&dir, 5, 0).as_deref(), Some(&b"new"[..]));
+        for x in 1..5 {
+            assert_eq!(read(&dir, x, 0), None);
+        }
+
+        // Truncated and random files must never panic
+        for len in (0..file.len()).step_by(509) {
+            std::fs::write(&path, &file[..len]).unwrap();
+            let _ = read_stored_columns(&path);
+        }
+        let mut seed = 0xda94_2042_e4dd_58b5u64;
+        for _ in 0..20 {
+            let random: Vec<u8> = (0..HEADER_SIZE + SECTOR_SIZE)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    seed as u8
+                })
+                .collect();
+            std::fs::write(&path, random).unwrap();
+            let _ = read_stored_columns(&path);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn oversized_columns_are_stored_externally() {
+        let dir = temp_dir("oversized");
+        let coords = ChunkCoords { x: -1, z: 33 };
+        let data: Vec<u8> = (0..MAX_REGION_COLUMN_LEN as u32 + 10).map(|i| (i.wrapping_mul(7919) >> 3) as u8).collect();
+        let mut stored = vec![COMPRESSION_NONE];
+        stored.extend_from_slice(&data);
+
+        merge_region_file(&dir, coords.x >> 5, coords.z >> 5, vec![(coords, stored)]).unwrap();
+        assert!(dir.join(external_file_name(coords)).exists());
+        assert_eq!(read(&dir, coords.x, coords.z), Some(data));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+// src/api.rs
+//! Rust API over the N-API-free core, for embedding the world in other Rust programs (proxies, headless clients)
+//! without going through Node. Enabled by the `rust-api` feature; build with `default-features = false` to leave
+//! the Node binding out.
+//!
+//! ```ignore
+//! use prismarine_world_lite::api::{ChunkCoords, DVec3, RaycastSettings, World};
+//!
+//! let mut world = World::new("1.21.1")?;
+//! world.load_column(ChunkCoords { x: 0, z: 0 }, &map_chunk_data)?;
+//! let hit = world.raycast(DVec3::new(8.5, 100.0, 8.5), DVec3::NEG_Y, &RaycastSettings::new(64.0));
+//! ```
+use minecraft_data_rs::api::{versions_by_minecraft_version, Api};
+use std::collections::HashMap;
+use std::fmt;
+
+pub use crate::chunk::{ChunkColumn, ChunkSection};
+pub use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+pub use crate::matcher::BlockMatcher;
+pub use crate::parsing::{parse_column, parse_column_bytes};
+pub use crate::raycast::{find_ray_hits, find_ray_outcome, BlockFace, BlockSource, FluidMode, RayOutcome, RaycastHit, RaycastSettings, ShapeSource, UnloadedChunkMode};
+pub use crate::registry::{BiomeRegistry, BlockRegistry, ShapeBox};
+pub use glam::DVec3;
+pub use minecraft_data_rs::models::block::Block;
+
+/// Errors from creating or editing a [`World`].
+#[derive(Debug)]
+pub enum WorldError {
+    /// minecraft-data has no data for the version.
+    UnsupportedVersion(String),
+    /// The version's block or biome data couldn't be loaded.
+    Data(String),
+    /// Column data couldn't be parsed.
+    Parse(std::io::Error),
+    /// The edit targets a column that isn't loaded.
+    ColumnNotLoaded(ChunkCoords),
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldError::UnsupportedVersion(version) => write!(f, "Unsupported Minecraft version: {}", version),
+            WorldError::Data(message) => f.write_str(message),
+            WorldError::Parse(e) => write!(f, "Failed to parse chunk column: {}", e),
+            WorldError::ColumnNotLoaded(coords) => write!(f, "Chunk at {}, {} not loaded", coords.x, coords.z),
+        }
+    }
+}
+
+impl std::error::Error for WorldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WorldError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Loads the block and biome registries of a Minecraft version, e.g. `"1.21.1"`.
+pub fn load_registries(version: &str) -> Result<(BlockRegistry, BiomeRegistry), WorldError> {
+    let versions = versions_by_minecraft_version()
+        .map_err(|e| WorldError::Data(format!("Failed to get Minecraft versions: {}", e)))?;
+    let version_info = versions.get(version).ok_or_else(|| WorldError::UnsupportedVersion(version.to_string()))?;
+    let api = Api::new(version_info.clone());
+    let blocks = BlockRegistry::load(&api)
+        .map_err(|e| WorldError::Data(format!("Failed to load block data for {}: {}", version, e)))?;
+    let biomes = BiomeRegistry::load(&api)
+        .map_err(|e| WorldError::Data(format!("Failed to load biome data for {}: {}", version, e)))?;
+    Ok((blocks, biomes))
+}
+
+/// Loaded chunk columns of one Minecraft version, with the same parsing, storage and raycasting as the Node
+/// binding. Single-threaded: wrap it in a lock to share it.
+pub struct World {
+    registry: BlockRegistry,
+    biomes: BiomeRegistry,
+    columns: HashMap<ChunkCoords, ChunkColumn>,
+}
+
+impl World {
+    /// Creates an empty world for a Minecraft version.
+    pub fn new(version: &str) -> Result<World, WorldError> {
+        let (registry, biomes) = load_registries(version)?;
+        Ok(World { registry, biomes, columns: HashMap::new() })
+    }
+
+    pub fn registry(&self) -> &BlockRegistry {
+        &self.registry
+    }
+
+    pub fn biomes(&self) -> &BiomeRegistry {
+        &self.biomes
+    }
+
+    /// Parses and loads column data from a network buffer (the data of a `map_chunk` packet), replacing any
+    /// column already loaded there. Data that ends early leaves the remaining sections empty.
+    pub fn load_column(&mut self, coords: ChunkCoords, data: &[u8]) -> Result<(), WorldError> {
+        let column = parse_column_bytes(data).map_err(WorldError::Parse)?;
+        self.columns.insert(coords, column);
+        Ok(())
+    }
+
+    /// Loads an already built column, returning the one it replaces.
+    pub fn insert_column(&mut self, coords: ChunkCoords, column: ChunkColumn) -> Option<ChunkColumn> {
+        self.columns.insert(coords, column)
+    }
+
+    pub fn unload_column(&mut self, coords: ChunkCoords) -> Option<ChunkColumn> {
+        self.columns.remove(&coords)
+    }
+
+    pub fn column(&self, coords: ChunkCoords) -> Option<&ChunkColumn> {
+        self.columns.get(&coords)
+    }
+
+    pub fn is_column_loaded(&self, coords: ChunkCoords) -> bool {
+        self.columns.contains_key(&coords)
+    }
+
+    /// Coordinates of every loaded column, in no particular order.
+    pub fn loaded_columns(&self) -> impl Iterator<Item = ChunkCoords> + '_ {
+        self.columns.keys().copied()
+    }
+
+    /// The block state at a position, or `None` if its column isn't loaded.
+    pub fn block_state_id(&self, pos: WorldCoords) -> Option<u32> {
+        self.columns.get(&pos.to_chunk_coords()).map(|column| column.get_block_state_id(pos))
+    }
+
+    /// The block at a position, or `None` if its column isn't loaded or the state is unknown.
+    pub fn block(&self, pos: WorldCoords) -> Option<&Block> {
+        self.block_state_id(pos).and_then(|state_id| self.registry.block(state_id))
+    }
+
+    /// Sets the block state at a position. Positions outside the world's height are ignored.
+    pub fn set_block_state_id(&mut self, pos: WorldCoords, state_id: u32) -> Result<(), WorldError> {
+        let coords = pos.to_chunk_coords();
+        let column = self.columns.get_mut(&coords).ok_or(WorldError::ColumnNotLoaded(coords))?;
+        column.set_block_state_id(pos, state_id);
+        Ok(())
+    }
+
+    /// Casts a block ray; `direction` doesn't need to be normalized. Returns the closest hit, if any.
+    pub fn raycast(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings) -> Option<RaycastHit> {
+        let settings = RaycastSettings { return_all: false, ..settings.clone() };
+        find_ray_hits(self, &self.registry, origin, direction, &settings, None).into_iter().next()
+    }
+
+    /// Casts a block ray like `raycast`, also telling whether the ray crossed unloaded columns when it missed.
+    pub fn raycast_outcome(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings) -> RayOutcome {
+        let settings = RaycastSettings { return_all: false, ..settings.clone() };
+        find_ray_outcome(self, &self.registry, origin, direction, &settings, None)
+    }
+
+    /// Casts a block ray and returns the hit in every block along it, nearest first.
+    pub fn raycast_all(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings) -> Vec<RaycastHit> {
+        let settings = RaycastSettings { return_all: true, ..settings.clone() };
+        find_ray_hits(self, &self.registry, origin, direction, &settings, None)
+    }
+}
+
+impl BlockSource for World {
+    fn block_state_id(&self, pos: WorldCoords) -> Option<u32> {
+        World::block_state_id(self, pos)
+    }
+}
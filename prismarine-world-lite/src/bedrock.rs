@@ -0,0 +1,171 @@
+// src/bedrock.rs
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::coords::{ChunkCoords, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_VOLUME};
+use crate::palette::read_varint;
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use byteorder::{LittleEndian, ReadBytesExt};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::io::{Cursor, Error, ErrorKind};
+use std::sync::Arc;
+
+// Bedrock network sub-chunks:
+//   u8 version: 1 (one storage), 8 (u8 storage count) or 9 (u8 storage count, i8 sub-chunk Y)
+//   per storage: u8 (bits per block << 1 | 1 for runtime ID palettes), then
+//     `ceil(4096 / (32 / bits))` little endian u32 words (none when bits is 0), blocks packed from the low bits
+//     with leftover high bits unused, then a zigzag VarInt palette length (omitted and 1 when bits is 0)
+//     and that many zigzag VarInt runtime IDs
+// Blocks are indexed x << 8 | z << 4 | y. The first storage holds the blocks, the second (if any) waterlogging.
+const VALID_BITS: [u8; 9] = [0, 1, 2, 3, 4, 5, 6, 8, 16];
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn read_zigzag_varint(cursor: &mut Cursor<&[u8]>) -> std::io::Result<i32> {
+    let raw = read_varint(cursor)? as u32;
+    Ok((raw >> 1) as i32 ^ -((raw & 1) as i32))
+}
+
+// Reads one block storage as palette indices plus its palette of runtime IDs
+fn read_storage(cursor: &mut Cursor<&[u8]>) -> std::io::Result<(Vec<u32>, Vec<i32>)> {
+    let header = cursor.read_u8()?;
+    if header & 1 == 0 {
+        return Err(invalid("Sub-chunk uses an NBT palette; only runtime ID palettes are supported".to_string()));
+    }
+    let bits = header >> 1;
+    if !VALID_BITS.contains(&bits) {
+        return Err(invalid(format!("Invalid sub-chunk bits per block: {}", bits)));
+    }
+
+    let mut indices = vec![0u32; SECTION_VOLUME];
+    let mut palette_len = 1;
+    if bits > 0 {
+        let bits = bits as usize;
+        let per_word = 32 / bits;
+        let mask = (1u32 << bits) - 1;
+        for word_index in 0..SECTION_VOLUME.div_ceil(per_word) {
+            let word = cursor.read_u32::<LittleEndian>()?;
+            for slot in 0..per_word {
+                let index = word_index * per_word + slot;
+                if index >= SECTION_VOLUME { break; }
+                indices[index] = (word >> (slot * bits)) & mask;
+            }
+        }
+        palette_len = read_zigzag_varint(cursor)?;
+        // Every entry takes at least one byte, and a storage can't use more entries than it has blocks
+        let remaining = cursor.get_ref().len().saturating_sub(cursor.position() as usize);
+        if palette_len < 1 || palette_len as usize > SECTION_VOLUME || palette_len as usize > remaining {
+            return Err(invalid(format!("Invalid sub-chunk palette length: {}", palette_len)));
+        }
+    }
+
+    let mut palette = Vec::with_capacity(palette_len as usize);
+    for _ in 0..palette_len {
+        palette.push(read_zigzag_varint(cursor)?);
+    }
+    Ok((indices, palette))
+}
+
+// Parses a sub-chunk, converting runtime IDs to state IDs through `runtime_ids` (unknown ones become air).
+// Returns the sub-chunk Y stored in version 9 sub-chunks.
+pub fn parse_sub_chunk(
+    cursor: &mut Cursor<&[u8]>,
+    runtime_ids: &[u32],
+    registry: &BlockRegistry,
+) -> std::io::Result<(Option<i32>, ChunkSection)> {
+    let version = cursor.read_u8()?;
+    let (storage_count, section_y) = match version {
+        1 => (1, None),
+        8 => (cursor.read_u8()?, None),
+        9 => (cursor.read_u8()?, Some(cursor.read_i8()? as i32)),
+        _ => return Err(invalid(format!("Unsupported sub-chunk version: {}", version))),
+    };
+
+    let mut blocks = None;
+    for _ in 0..storage_count {
+        let storage = read_storage(cursor)?;
+        blocks.get_or_insert(storage);
+    }
+
+    let mut states = vec![0u32; SECTION_VOLUME];
+    if let Some((indices, palette)) = blocks {
+        for (bedrock_index, palette_index) in indices.into_iter().enumerate() {
+            let runtime_id = palette.get(palette_index as usize).copied().unwrap_or(-1);
+            let state_id = usize::try_from(runtime_id).ok().and_then(|id| runtime_ids.get(id)).copied().unwrap_or(0);
+            // x << 8 | z << 4 | y  ->  y << 8 | z << 4 | x
+            let (x, z, y) = (bedrock_index >> 8, (bedrock_index >> 4) & 15, bedrock_index & 15);
+            states[y << 8 | z << 4 | x] = state_id;
+        }
+    }
+    Ok((section_y, ChunkSection::from_block_states(&states, registry)))
+}
+
+impl NapiWorld {
+    fn bedrock_runtime_ids(&self) -> Result<Arc<Vec<u32>>> {
+        self.bedrock_runtime_ids.clone().ok_or_else(|| napi::Error::new(
+            napi::Status::GenericFailure,
+            "World was not created with World.withBedrockRuntimeIds".to_string(),
+        ))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Creates a world that reads Bedrock Edition sub-chunks (see `load_sub_chunk`), so Bedrock bots can use the same
+    /// queries and raycaster. Blocks are stored as state IDs of the Java `version_string`, which provides the block data;
+    /// `runtime_ids[runtimeId]` gives the state ID for each Bedrock runtime ID. Runtime IDs outside the map become air.
+    #[napi(factory)]
+    pub fn with_bedrock_runtime_ids(version_string: String, runtime_ids: Uint32Array) -> Result<Self> {
+        let mut world = NapiWorld::with_version(version_string)?;
+        world.bedrock_runtime_ids = Some(Arc::new(runtime_ids.to_vec()));
+        Ok(world)
+    }
+
+    /// Loads one Bedrock sub-chunk (versions 1, 8 and 9, with runtime ID palettes), creating an empty column if none
+    /// is loaded. Version 9 sub-chunks carry their own Y; for older ones `section_y` must be given.
+    /// Only the block layer is kept: waterlogging layers and biomes aren't stored.
+    #[napi]
+    pub fn load_sub_chunk(&self, chunk_x: i32, chunk_z: i32, data_buffer: Buffer, section_y: Option<i32>) -> Result<()> {
+        let runtime_ids = self.bedrock_runtime_ids()?;
+        let mut cursor = Cursor::new(data_buffer.as_ref());
+        let (stored_y, section) = parse_sub_chunk(&mut cursor, &runtime_ids, &self.registry).map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to parse sub-chunk: {}", e),
+        ))?;
+        let section_y = stored_y.or(section_y).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            "Sub-chunk has no Y; pass section_y".to_string(),
+        ))?;
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Section y={} is outside the world", section_y),
+            ));
+        }
+
+        let coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.create_column_if_missing(coords);
+        self.replace_section(coords, section_y, section)
+    }
+
+    /// Loads a column from the sub-chunks at the start of a Bedrock `level_chunk` payload, replacing any column already
+    /// loaded there. `sub_chunk_count` comes from the packet; whatever follows the sub-chunks (biomes, block entities)
+    /// is ignored. Sub-chunks without a stored Y are placed upwards from the bottom of the world.
+    #[napi]
+    pub fn load_bedrock_column(&self, chunk_x: i32, chunk_z: i32, sub_chunk_count: u32, data_buffer: Buffer) -> Result<()> {
+        let runtime_ids = self.bedrock_runtime_ids()?;
+        let mut cursor = Cursor::new(data_buffer.as_ref());
+        let mut column = ChunkColumn::new();
+        for i in 0..sub_chunk_count as i32 {
+            let (stored_y, section) = parse_sub_chunk(&mut cursor, &runtime_ids, &self.registry).map_err(|e| napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Failed to parse sub-chunk {}: {}", i, e),
+            ))?;
+            column.insert_section(stored_y.unwrap_or(MIN_SECTION_Y + i), section);
+        }
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, column);
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+// src/bench.rs
+// Benchmark workloads shared by the Criterion suite (`benches/world.rs`) and `run_self_benchmark`, so both time
+// the same hot paths: chunk parsing, raycasts through terrain, block searches and bulk writes.
+use crate::coords::{ChunkCoords, WorldCoords, MIN_SECTION_Y, SECTION_COUNT, SECTION_VOLUME};
+use crate::palette::test_support::write_container;
+use crate::palette::PaletteContainer;
+use crate::raycast::RaycastSettings;
+use crate::region::Region;
+use crate::world::{ColumnStore, NapiWorld};
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub const FIXTURE_VERSION: &str = "1.21.1";
+pub const FIXTURE_RADIUS: i32 = 2; // Fixture columns span chunks -2..=2 on both axes
+const RAY_COUNT: usize = 1000;
+const RAY_DISTANCE: f64 = 64.0;
+const SEA_LEVEL: i32 = 62;
+
+const FIXTURE_BLOCKS: &[&str] = &[
+    "air", "bedrock", "deepslate", "stone", "dirt", "grass_block", "water", "coal_ore", "iron_ore", "diamond_ore",
+];
+const ORE_BLOCKS: &[&str] = &["coal_ore", "iron_ore", "diamond_ore"];
+
+/// Timing of one self-benchmark workload.
+#[napi(object)]
+pub struct BenchmarkResult {
+    pub name: String, // "chunkParse", "raycast", "findBlocks" or "bulkSet"
+    pub iterations: u32,
+    pub operations: u32, // Columns parsed, rays cast, blocks scanned or blocks written per iteration
+    pub total_ms: f64,
+    pub mean_ms: f64,      // Per iteration
+    pub ns_per_op: f64,
+}
+
+// Cheap deterministic noise, so fixtures are identical on every run
+fn hash(x: i32, y: i32, z: i32) -> u32 {
+    let mut h = (x as u32).wrapping_mul(0x9E37_79B1) ^ (y as u32).wrapping_mul(0x85EB_CA77) ^ (z as u32).wrapping_mul(0xC2B2_AE3D);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^ (h >> 12)
+}
+
+fn surface_height(x: i32, z: i32) -> i32 {
+    let (x, z) = (x as f64, z as f64);
+    56 + ((x * 0.11).sin() * 6.0 + (z * 0.07).cos() * 5.0 + (x * 0.03 + z * 0.05).sin() * 4.0) as i32
+}
+
+/// A world of the fixture version plus the state IDs and packets the workloads use.
+pub struct Fixture {
+    world: NapiWorld,
+    states: Vec<u32>, // FIXTURE_BLOCKS' default states, in order
+    packets: Vec<(ChunkCoords, Vec<u8>)>,
+}
+
+impl Fixture {
+    /// Builds the fixture packets for a version; columns are loaded by `load_columns`.
+    pub fn new(version: &str) -> Result<Self> {
+        Ok(Fixture::for_world(NapiWorld::with_version(version.to_string())?))
+    }
+
+    fn for_world(world: NapiWorld) -> Self {
+        let states = FIXTURE_BLOCKS.iter().map(|name| {
+            world.registry.block_by_name(name).map(|block| block.default_state.unwrap_or(block.id << 4)).unwrap_or(0)
+        }).collect();
+        let mut fixture = Fixture { world, states, packets: Vec::new() };
+        for x in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+            for z in -FIXTURE_RADIUS..=FIXTURE_RADIUS {
+                let coords = ChunkCoords { x, z };
+                let packet = fixture.terrain_packet(coords);
+                fixture.packets.push((coords, packet));
+            }
+        }
+        fixture
+    }
+
+    fn state(&self, name: &str) -> u32 {
+        FIXTURE_BLOCKS.iter().position(|&block| block == name).map_or(0, |index| self.states[index])
+    }
+
+    // Hilly terrain with ores, water below sea level and air above: indirect sections underground and at the
+    // surface, single-value sections in the sky, like a typical overworld chunk
+    fn block_at(&self, x: i32, y: i32, z: i32, surface: i32) -> u32 {
+        let name = match y {
+            -64 => "bedrock",
+            y if y > surface => if y <= SEA_LEVEL { "water" } else { "air" },
+            y if y == surface => if surface < SEA_LEVEL { "dirt" } else { "grass_block" },
+            y if y > surface - 4 => "dirt",
+            y => match hash(x, y, z) % 200 {
+                0..=2 => "coal_ore",
+                3..=4 => "iron_ore",
+                5 if y < 16 => "diamond_ore",
+                _ if y < 0 => "deepslate",
+                _ => "stone",
+            },
+        };
+        self.state(name)
+    }
+
+    fn terrain_packet(&self, coords: ChunkCoords) -> Vec<u8> {
+        let biome = self.world.biomes.biome_id_by_name("plains").unwrap_or(0);
+        let air = self.state("air");
+        let mut out = Vec::new();
+        let mut values = vec![0u32; SECTION_VOLUME];
+        for index in 0..SECTION_COUNT {
+            let section_y = MIN_SECTION_Y + index as i32;
+            for (i, value) in values.iter_mut().enumerate() {
+                let (x, y, z) = (coords.x * 16 + (i & 15) as i32, section_y * 16 + (i >> 8) as i32, coords.z * 16 + ((i >> 4) & 15) as i32);
+                *value = self.block_at(x, y, z, surface_height(x, z));
+            }
+            out.extend_from_slice(&(values.iter().filter(|&&value| value != air).count() as i16).to_be_bytes());
+            write_container(&mut out, &PaletteContainer::from_values(&values, 4, 8, 0));
+            write_container(&mut out, &PaletteContainer::new_single(biome));
+        }
+        out
+    }
+
+    /// Adds a captured packet (the data field of a `map_chunk` packet) to the packets `load_columns` parses.
+    pub fn add_packet(&mut self, chunk_x: i32, chunk_z: i32, data: Vec<u8>) {
+        self.packets.push((ChunkCoords { x: chunk_x, z: chunk_z }, data));
+    }
+
+    /// Adds every `<chunkX>_<chunkZ>.bin` packet dump in a directory. Returns how many were added.
+    pub fn add_packet_dir(&mut self, dir: &Path) -> std::io::Result<usize> {
+        let mut added = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("bin") { continue; }
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            let Some((x, z)) = stem.split_once('_').and_then(|(x, z)| Some((x.parse().ok()?, z.parse().ok()?))) else { continue };
+            self.add_packet(x, z, std::fs::read(&path)?);
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// Parses and loads every fixture packet.
+    pub fn load_columns(&self) -> Result<()> {
+        for (coords, packet) in &self.packets {
+            self.world.load_column(coords.x, coords.z, packet.clone().into())?;
+        }
+        Ok(())
+    }
+
+    // Origins above the terrain looking down at it, spread over the fixture area
+    fn rays(&self) -> impl Iterator<Item = (DVec3, DVec3)> {
+        let span = (FIXTURE_RADIUS * 2 + 1) as f64 * 16.0 - 16.0;
+        (0..RAY_COUNT).map(move |i| {
+            let h = hash(i as i32, 0, 7);
+            let origin = DVec3::new(
+                (h % 1000) as f64 / 1000.0 * span - span / 2.0,
+                80.0 + (h >> 10) as f64 % 10.0,
+                ((h >> 20) % 1000) as f64 / 1000.0 * span - span / 2.0,
+            );
+            let yaw = (i as f64) * 0.618 * std::f64::consts::TAU;
+            (origin, DVec3::new(yaw.cos(), -0.6, yaw.sin()).normalize())
+        })
+    }
+
+    /// Casts `RAY_COUNT` rays down into the terrain. Returns how many hit a block.
+    pub fn raycast_terrain(&self) -> usize {
+        let settings = RaycastSettings::new(RAY_DISTANCE);
+        self.rays().filter(|&(origin, direction)| self.world.cast_ray(origin, direction, &settings).is_some()).count()
+    }
+
+    pub fn ray_count(&self) -> usize {
+        RAY_COUNT
+    }
+
+    fn fixture_region(&self) -> Region {
+        let edge = FIXTURE_RADIUS * 16;
+        Region::new(
+            WorldCoords { x: -edge, y: -64, z: -edge },
+            WorldCoords { x: edge + 15, y: 100, z: edge + 15 },
+        )
+    }
+
+    /// Counts the ores in the fixture area, block by block.
+    pub fn find_blocks(&self) -> u32 {
+        let ores = ORE_BLOCKS.iter().map(|name| self.state(name)).collect();
+        self.world.count_in_region(&self.fixture_region(), ores)
+    }
+
+    pub fn find_blocks_volume(&self) -> usize {
+        self.fixture_region().volume() as usize
+    }
+
+    /// Writes a 32x16x32 slab of blocks (`layer` picks the block so repeated calls keep changing them) through
+    /// `set_block_state_id`. Returns the number of blocks written.
+    pub fn bulk_set(&self, layer: u32) -> Result<usize> {
+        let state = self.states[1 + layer as usize % (self.states.len() - 1)];
+        let mut written = 0;
+        for y in 40..56 {
+            for z in -16..16 {
+                for x in -16..16 {
+                    self.world.set_block_state_id(x, y, z, state)?;
+                    written += 1;
+                }
+            }
+        }
+        Ok(written)
+    }
+}
+
+fn time_workload(name: &str, iterations: u32, operations: usize, mut run: impl FnMut(u32) -> Result<()>) -> Result<BenchmarkResult> {
+    let started = Instant::now();
+    for iteration in 0..iterations {
+        run(iteration)?;
+    }
+    let total_ms = started.elapsed().as_secs_f64() * 1e3;
+    Ok(BenchmarkResult {
+        name: name.to_string(),
+        iterations,
+        operations: operations as u32,
+        total_ms,
+        mean_ms: total_ms / iterations as f64,
+        ns_per_op: total_ms * 1e6 / (iterations as f64 * operations.max(1) as f64),
+    })
+}
+
+#[napi]
+impl NapiWorld {
+    /// Times the native hot paths (chunk parsing, raycasts through terrain, block searches and bulk writes) on
+    /// generated fixture terrain, in a scratch world of this world's version; this world is left untouched.
+    /// `iterations` defaults to 10. Compare the results across builds to spot performance regressions.
+    #[napi]
+    pub fn run_self_benchmark(&self, iterations: Option<u32>) -> Result<Vec<BenchmarkResult>> {
+        let iterations = iterations.unwrap_or(10).max(1);
+        let scratch = NapiWorld::from_parts(ColumnStore::default(), Arc::clone(&self.registry), Arc::clone(&self.biomes));
+        let fixture = Fixture::for_world(scratch);
+
+        Ok(vec![
+            time_workload("chunkParse", iterations, fixture.column_count(), |_| fixture.load_columns())?,
+            time_workload("raycast", iterations, fixture.ray_count(), |_| { fixture.raycast_terrain(); Ok(()) })?,
+            time_workload("findBlocks", iterations, fixture.find_blocks_volume(), |_| { fixture.find_blocks(); Ok(()) })?,
+            time_workload("bulkSet", iterations, 32 * 16 * 32, |i| fixture.bulk_set(i).map(|_| ()))?,
+        ])
+    }
+}
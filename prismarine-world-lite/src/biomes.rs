@@ -0,0 +1,159 @@
+// src/biomes.rs
+use crate::coords::{ChunkCoords, SectionCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::raycast::Vec3Arg;
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+
+// Biome cell range (inclusive) covering two block corners, with Y clamped to the world
+fn biome_cell_bounds(min: WorldCoords, max: WorldCoords) -> (WorldCoords, WorldCoords) {
+    let lo = WorldCoords {
+        x: min.x.min(max.x) >> 2,
+        y: min.y.min(max.y).max(MIN_CHUNK_Y) >> 2,
+        z: min.z.min(max.z) >> 2,
+    };
+    let hi = WorldCoords {
+        x: min.x.max(max.x) >> 2,
+        y: min.y.max(max.y).min(MAX_CHUNK_Y - 1) >> 2,
+        z: min.z.max(max.z) >> 2,
+    };
+    (lo, hi)
+}
+
+impl NapiWorld {
+    // Biome arguments can be given as an ID or a name
+    pub(crate) fn resolve_biome(&self, biome: Either<u32, String>) -> Result<u32> {
+        match biome {
+            Either::A(id) => Ok(id),
+            Either::B(name) => self.biomes.biome_id_by_name(&name).ok_or_else(|| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown biome: {}", name),
+            )),
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Sets the biome of every 4x4x4 biome cell touched by the region (inclusive corners).
+    /// Cells in unloaded chunks are skipped. Returns the number of cells written.
+    #[napi]
+    pub fn set_biomes_in_region(&self, min: WorldCoords, max: WorldCoords, biome_id: u32) -> Result<u32> {
+        let (lo, hi) = biome_cell_bounds(min, max);
+        let mut written = 0;
+        let mut changed_sections = HashSet::new();
+
+        // Lock each column once and write all of its cells
+        for chunk_x in (lo.x >> 2)..=(hi.x >> 2) {
+            for chunk_z in (lo.z >> 2)..=(hi.z >> 2) {
+                let chunk = ChunkCoords { x: chunk_x, z: chunk_z };
+                self.ensure_column(chunk);
+                self.unshare_column(chunk);
+                let Some(entry) = self.columns.get(&chunk) else { continue; };
+                let mut column = entry.value().try_write().map_err(|_| napi::Error::new(
+                    napi::Status::GenericFailure,
+                    "Failed to acquire write lock for chunk".to_string(),
+                ))?;
+
+                for cell_y in lo.y..=hi.y {
+                    for cell_z in lo.z.max(chunk_z << 2)..=hi.z.min((chunk_z << 2) + 3) {
+                        for cell_x in lo.x.max(chunk_x << 2)..=hi.x.min((chunk_x << 2) + 3) {
+                            column.set_biome_id(WorldCoords { x: cell_x << 2, y: cell_y << 2, z: cell_z << 2 }, biome_id);
+                            changed_sections.insert(SectionCoords { x: chunk_x, y: cell_y >> 2, z: chunk_z });
+                            written += 1;
+                        }
+                    }
+                }
+            }
+        }
+        self.mark_sections_dirty(changed_sections);
+        Ok(written)
+    }
+
+    /// Exports the biome IDs of every 4x4x4 biome cell touched by the region (inclusive corners).
+    ///
+    /// Values are ordered Y, then Z, then X (X varies fastest), with the dimensions being the
+    /// number of cells along each axis (`(max >> 2) - (min >> 2) + 1`). Unloaded cells are 0.
+    #[napi]
+    pub fn export_biomes(&self, min: WorldCoords, max: WorldCoords) -> Uint32Array {
+        let (lo, hi) = biome_cell_bounds(min, max);
+        let (size_x, size_z) = ((hi.x - lo.x + 1) as usize, (hi.z - lo.z + 1) as usize);
+        let size_y = (hi.y - lo.y + 1).max(0) as usize;
+        let mut biomes = vec![0u32; size_x * size_y * size_z];
+
+        for chunk_x in (lo.x >> 2)..=(hi.x >> 2) {
+            for chunk_z in (lo.z >> 2)..=(hi.z >> 2) {
+                let chunk = ChunkCoords { x: chunk_x, z: chunk_z };
+                self.ensure_column(chunk);
+                let Some(entry) = self.columns.get(&chunk) else { continue; };
+                let Ok(column) = entry.value().try_read() else { continue; };
+
+                for cell_y in lo.y..=hi.y {
+                    for cell_z in lo.z.max(chunk_z << 2)..=hi.z.min((chunk_z << 2) + 3) {
+                        for cell_x in lo.x.max(chunk_x << 2)..=hi.x.min((chunk_x << 2) + 3) {
+                            let index = ((cell_y - lo.y) as usize * size_z + (cell_z - lo.z) as usize) * size_x + (cell_x - lo.x) as usize;
+                            biomes[index] = column.get_biome_id(WorldCoords { x: cell_x << 2, y: cell_y << 2, z: cell_z << 2 });
+                        }
+                    }
+                }
+            }
+        }
+        Uint32Array::new(biomes)
+    }
+
+    /// Finds the nearest loaded 4x4x4 biome cell whose biome is one of `biomes` (IDs or names, e.g. `"desert"`).
+    ///
+    /// Sections whose biome palette can't contain a match are skipped without unpacking them.
+    /// Returns the center block of the closest cell within `max_distance`, or `null` if there is none.
+    #[napi(ts_args_type = "point: { x: number, y: number, z: number }, biomes: Array<number | string>, max_distance: number")]
+    pub fn find_nearest_biome(&self, point: Vec3Arg, biomes: Vec<Either<u32, String>>, max_distance: f64) -> Result<Option<BiomeSearchResult>> {
+        let mut wanted = HashSet::new();
+        for biome in biomes {
+            wanted.insert(self.resolve_biome(biome)?);
+        }
+
+        let point = DVec3::from(point);
+        let mut best: Option<(f64, WorldCoords, u32)> = None;
+
+        for (chunk, column) in self.all_columns() {
+            // Skip columns that can't beat the current best even at their closest point
+            let chunk_min = DVec3::new((chunk.x << 4) as f64, point.y, (chunk.z << 4) as f64);
+            let closest = point.clamp(chunk_min, chunk_min + DVec3::new(16.0, 0.0, 16.0));
+            let limit = best.map_or(max_distance, |(d, _, _)| d.min(max_distance));
+            if closest.distance(point) > limit { continue; }
+
+            let Ok(column) = column.try_read() else { continue; };
+            for (section_y, section) in column.sections() {
+                if !section.biomes().may_contain_any(&wanted) { continue; }
+
+                for index in 0..64 {
+                    let biome_id = section.biomes().get(index);
+                    if !wanted.contains(&biome_id) { continue; }
+
+                    // Biome indices are ordered Y, Z, X within the section
+                    let cell = WorldCoords {
+                        x: (chunk.x << 4) + ((index & 3) as i32) * 4 + 2,
+                        y: (section_y << 4) + ((index >> 4) as i32) * 4 + 2,
+                        z: (chunk.z << 4) + (((index >> 2) & 3) as i32) * 4 + 2,
+                    };
+                    let distance = DVec3::new(cell.x as f64 + 0.5, cell.y as f64 + 0.5, cell.z as f64 + 0.5).distance(point);
+                    if distance <= max_distance && best.is_none_or(|(d, _, _)| distance < d) {
+                        best = Some((distance, cell, biome_id));
+                    }
+                }
+            }
+        }
+
+        Ok(best.map(|(distance, position, biome_id)| BiomeSearchResult { position, biome_id, distance }))
+    }
+}
+
+// Nearest matching biome cell found by `find_nearest_biome`
+#[napi(object)]
+pub struct BiomeSearchResult {
+    pub position: WorldCoords,
+    pub biome_id: u32,
+    pub distance: f64,
+}
@@ -0,0 +1,104 @@
+// src/block_data.rs
+use crate::mining::can_harvest_block;
+use crate::raycast::AabbArg;
+use crate::registry::ShapeBox;
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// A shape box moved to a block position
+fn shape_aabb(shape: &ShapeBox, x: f64, y: f64, z: f64) -> AabbArg {
+    AabbArg {
+        min: DVec3::new(x + shape[0], y + shape[1], z + shape[2]).into(),
+        max: DVec3::new(x + shape[3], y + shape[4], z + shape[5]).into(),
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    // --- Block Metadata ---
+
+    /// Gets a block state's hardness. Unbreakable blocks report -1; unknown states return null.
+    #[napi]
+    pub fn get_block_hardness(&self, state_id: u32) -> Option<f64> {
+        self.registry.block(state_id).and_then(|b| b.hardness).map(|h| h as f64)
+    }
+
+    /// Gets a block state's material, e.g. "mineable/pickaxe" (null if unknown or not set).
+    #[napi]
+    pub fn get_block_material(&self, state_id: u32) -> Option<String> {
+        self.registry.block(state_id).and_then(|b| b.material.clone())
+    }
+
+    /// Checks whether mining a block state with the given tool (none for an empty hand) drops its item.
+    #[napi]
+    pub fn can_harvest(&self, state_id: u32, tool_name: Option<String>) -> Result<bool> {
+        let block = self.registry.block(state_id).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Unknown block state ID: {}", state_id),
+        ))?;
+        Ok(can_harvest_block(&self.registry, block, tool_name.as_deref()))
+    }
+
+    /// Checks whether a block state is opaque, i.e. not transparent and fully blocking light.
+    #[napi]
+    pub fn is_opaque(&self, state_id: u32) -> bool {
+        !self.registry.is_transparent(state_id) && self.registry.light_filter(state_id) >= 15
+    }
+
+    /// Checks whether a block state is transparent (can be seen through). Unknown states count as transparent.
+    #[napi]
+    pub fn is_transparent(&self, state_id: u32) -> bool {
+        self.registry.is_transparent(state_id)
+    }
+
+    /// Gets how many light levels a block state removes from light passing through it (0-15).
+    #[napi]
+    pub fn get_light_filter(&self, state_id: u32) -> u8 {
+        self.registry.light_filter(state_id)
+    }
+
+    /// Checks whether a block state's collision shape fills the entire block space.
+    /// Full cubes hide the adjacent faces of their neighbors, so renderers can cull those faces.
+    #[napi]
+    pub fn is_full_cube(&self, state_id: u32) -> bool {
+        self.registry.is_full_cube(state_id)
+    }
+
+    // --- Shapes ---
+
+    /// Gets the collision boxes of a block state, relative to the block origin (0-1 on each axis).
+    /// Solid blocks without collision data get a full cube; non-solid blocks without collision data get none.
+    /// Shapes depend only on the state, so neighboring blocks (e.g. fence connections) must already be in its properties.
+    #[napi]
+    pub fn get_collision_shapes(&self, state_id: u32) -> Vec<AabbArg> {
+        self.registry.resolved_shapes(state_id).iter().map(|shape| shape_aabb(shape, 0.0, 0.0, 0.0)).collect()
+    }
+
+    /// Gets the collision boxes of the block at a position, in world coordinates.
+    /// Air and blocks in unloaded chunks have none.
+    #[napi]
+    pub fn get_shape_at(&self, x: i32, y: i32, z: i32) -> Vec<AabbArg> {
+        let state_id = self.get_block_state_id(x, y, z);
+        self.registry
+            .resolved_shapes(state_id)
+            .iter()
+            .map(|shape| shape_aabb(shape, x as f64, y as f64, z as f64))
+            .collect()
+    }
+
+    /// Gets the outline (selection) boxes of the block at a position, in world coordinates: the boxes the game
+    /// draws its black wireframe around, and the ones `raycastEx` tests with `shapeSource: 'outline'`. Derived from
+    /// the collision boxes, with vanilla's outlines for common blocks that differ (plants, torches, rails, fences...).
+    /// Air, fluids and blocks in unloaded chunks have none.
+    #[napi]
+    pub fn get_outline_shape(&self, x: i32, y: i32, z: i32) -> Vec<AabbArg> {
+        let state_id = self.get_block_state_id(x, y, z);
+        self.registry
+            .outline_shapes(state_id)
+            .iter()
+            .map(|shape| shape_aabb(shape, x as f64, y as f64, z as f64))
+            .collect()
+    }
+}
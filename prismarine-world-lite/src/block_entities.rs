@@ -0,0 +1,160 @@
+// src/block_entities.rs
+use crate::coords::WorldCoords;
+use crate::nbt::{read_nbt, write_nbt, Tag};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SignText {
+    pub front: Vec<String>, // One plain-text entry per line
+    pub back: Vec<String>,  // Empty before 1.20, when signs had one side
+    pub waxed: bool,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ContainerItem {
+    pub slot: u32,
+    pub name: String, // Item name without the `minecraft:` prefix
+    pub count: u32,
+}
+
+// Plain text of a JSON text component: its `text` followed by that of its `extra` parts
+fn json_plain_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(parts) => parts.iter().map(json_plain_text).collect(),
+        serde_json::Value::Object(component) => {
+            let text = component.get("text").map(json_plain_text).unwrap_or_default();
+            let extra: String = component.get("extra").map(json_plain_text).unwrap_or_default();
+            text + &extra
+        }
+        _ => String::new(),
+    }
+}
+
+// Plain text of a sign line, stored as JSON text (up to 1.21.4) or as NBT text components (1.21.5+)
+fn line_text(line: &Tag) -> String {
+    match line {
+        Tag::String(json) => serde_json::from_str(json).map(|value| json_plain_text(&value)).unwrap_or_else(|_| json.clone()),
+        Tag::List(parts) => parts.iter().map(line_text).collect(),
+        Tag::Compound(_) => {
+            let text = line.get("text").map(line_text).unwrap_or_default();
+            text + &line.get("extra").map(line_text).unwrap_or_default()
+        }
+        _ => String::new(),
+    }
+}
+
+fn side_lines(side: Option<&Tag>) -> Vec<String> {
+    side.and_then(|side| side.get("messages")).and_then(Tag::as_list).unwrap_or_default().iter().map(line_text).collect()
+}
+
+impl NapiWorld {
+    // Copy of the block entity data at a position, if its chunk is loaded and it has any
+    fn block_entity_at(&self, coords: WorldCoords) -> Option<Tag> {
+        let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().read().ok()?;
+        column.block_entity(coords).cloned()
+    }
+
+    fn block_name_at(&self, x: i32, y: i32, z: i32) -> Option<&str> {
+        self.registry.block(self.get_block_state_id(x, y, z)).map(|block| block.name.as_str())
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Stores the block entity data (sign text, container items...) of the block at the given position, as an
+    /// uncompressed NBT compound with a named root (e.g. from prismarine-nbt's `writeUncompressed`). Block entities
+    /// in chunks loaded from region files or chunk NBT are stored automatically.
+    #[napi]
+    pub fn set_block_entity(&self, x: i32, y: i32, z: i32, nbt_buffer: Buffer) -> Result<()> {
+        let data = read_nbt(&nbt_buffer).map_err(|e| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Invalid block entity NBT: {}", e),
+        ))?;
+        let coords = WorldCoords { x, y, z };
+        let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        self.unshare_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Chunk at {}, {} not loaded", chunk_coords.x, chunk_coords.z),
+        ))?;
+        let mut column = entry.value().write().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire write lock for chunk".to_string(),
+        ))?;
+        column.set_block_entity(coords, data);
+        Ok(())
+    }
+
+    /// Removes the block entity data at the given position. Returns false if there was none.
+    #[napi]
+    pub fn remove_block_entity(&self, x: i32, y: i32, z: i32) -> bool {
+        let coords = WorldCoords { x, y, z };
+        let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        self.unshare_column(chunk_coords);
+        let Some(entry) = self.columns.get(&chunk_coords) else { return false; };
+        let Ok(mut column) = entry.value().write() else { return false; };
+        column.remove_block_entity(coords).is_some()
+    }
+
+    /// Gets the stored block entity data at the given position as uncompressed NBT, or `null` if there is none.
+    #[napi]
+    pub fn get_block_entity(&self, x: i32, y: i32, z: i32) -> Option<Buffer> {
+        self.block_entity_at(WorldCoords { x, y, z }).map(|data| write_nbt(&data).into())
+    }
+
+    /// Gets the text of the sign at the given position as plain text, one entry per line, with formatting dropped.
+    /// Returns `null` if the block isn't a sign or its block entity data isn't known.
+    #[napi]
+    pub fn get_sign_text(&self, x: i32, y: i32, z: i32) -> Option<SignText> {
+        if !self.block_name_at(x, y, z)?.ends_with("_sign") { return None; }
+        let data = self.block_entity_at(WorldCoords { x, y, z })?;
+
+        let waxed = data.get("is_waxed").and_then(Tag::as_i64).is_some_and(|waxed| waxed != 0);
+        if data.get("front_text").is_some() {
+            return Some(SignText { front: side_lines(data.get("front_text")), back: side_lines(data.get("back_text")), waxed });
+        }
+        // Before 1.20: Text1 to Text4
+        let front = (1..=4).map(|line| data.get(&format!("Text{}", line)).map(line_text).unwrap_or_default()).collect();
+        Some(SignText { front, back: Vec::new(), waxed })
+    }
+
+    /// Lists the items of the container (chest, barrel, furnace, hopper, shulker box...) at the given position, by
+    /// slot. Returns `null` if there is no block entity data with items there.
+    #[napi]
+    pub fn get_container_items(&self, x: i32, y: i32, z: i32) -> Option<Vec<ContainerItem>> {
+        let data = self.block_entity_at(WorldCoords { x, y, z })?;
+        let items = data.get("Items").and_then(Tag::as_list)?;
+        Some(items
+            .iter()
+            .filter_map(|item| {
+                let name = item.get("id").and_then(Tag::as_str)?;
+                // `Count` (byte) until 1.20.4, `count` (int) after
+                let count = item.get("count").or_else(|| item.get("Count")).and_then(Tag::as_i64).unwrap_or(1);
+                Some(ContainerItem {
+                    slot: item.get("Slot").and_then(Tag::as_i64)? as u32,
+                    name: name.strip_prefix("minecraft:").unwrap_or(name).to_string(),
+                    count: count as u32,
+                })
+            })
+            .collect())
+    }
+
+    /// Checks whether the chest (or trapped chest) at the given position is half of a double chest. Read from the
+    /// block state, so it works without block entity data. False for single chests and anything that isn't a chest.
+    #[napi]
+    pub fn is_chest_double(&self, x: i32, y: i32, z: i32) -> bool {
+        let state_id = self.get_block_state_id(x, y, z);
+        matches!(self.block_name_at(x, y, z), Some("chest" | "trapped_chest"))
+            && self.registry.state_property(state_id, "type").is_some_and(|kind| kind != "single")
+    }
+}
@@ -0,0 +1,81 @@
+// src/borders.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords, MAX_SECTION_Y, MIN_SECTION_Y};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+// Width of a section with a one-block border on each side
+const BORDERED_WIDTH: i32 = 18;
+
+impl NapiWorld {
+    // Values of a section and the one-block border around it, indexed `((y + 1) * 18 + z + 1) * 18 + x + 1` for
+    // section-relative x, y, z in -1..=16. `value(column, pos)` reads the value at each position from the column
+    // under it; columns that aren't loaded read as `missing`. `None` if the section's own column isn't loaded.
+    pub(crate) fn read_with_border(
+        &self,
+        center: ChunkCoords,
+        section_y: i32,
+        missing: u32,
+        value: impl Fn(&ChunkColumn, WorldCoords) -> u32,
+    ) -> Option<Vec<u32>> {
+        // The 3x3 columns around the section, indexed `(dz + 1) * 3 + dx + 1`. Arcs are cloned out of the map so
+        // no shard stays locked while the column locks are held.
+        let columns: Vec<_> = (-1..=1)
+            .flat_map(|dz| (-1..=1).map(move |dx| ChunkCoords { x: center.x + dx, z: center.z + dz }))
+            .map(|coords| {
+                self.ensure_column(coords);
+                self.columns.get(&coords).map(|entry| Arc::clone(entry.value()))
+            })
+            .collect();
+        let guards: Vec<_> = columns.iter().map(|column| column.as_ref().and_then(|c| c.try_read().ok())).collect();
+        guards[4].as_ref()?;
+
+        let mut values = Vec::with_capacity((BORDERED_WIDTH * BORDERED_WIDTH * BORDERED_WIDTH) as usize);
+        for y in -1..BORDERED_WIDTH - 1 {
+            for z in -1..BORDERED_WIDTH - 1 {
+                for x in -1..BORDERED_WIDTH - 1 {
+                    let (dx, dz) = (x.div_euclid(16), z.div_euclid(16));
+                    let pos = WorldCoords { x: (center.x << 4) + x, y: (section_y << 4) + y, z: (center.z << 4) + z };
+                    values.push(match &guards[((dz + 1) * 3 + dx + 1) as usize] {
+                        Some(column) => value(column, pos),
+                        None => missing,
+                    });
+                }
+            }
+        }
+        Some(values)
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Gets the state IDs of a section's blocks plus a one-block border from the sections and columns around
+    /// it, 18x18x18 values indexed by `((y + 1) * 18 + z + 1) * 18 + x + 1` for section-relative x, y, z from -1
+    /// to 16: what a mesher needs to cull faces on the section's edges. Blocks in unloaded columns, missing
+    /// sections and outside the world read as air. Returns null if the section's own column isn't loaded or the
+    /// section is outside the world.
+    #[napi]
+    pub fn get_section_with_border(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<Uint32Array> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) { return None; }
+        let states = self.read_with_border(ChunkCoords { x: chunk_x, z: chunk_z }, section_y, 0, |column, pos| {
+            column.get_block_state_id(pos)
+        })?;
+        Some(states.into())
+    }
+
+    /// Gets the light of a section's blocks plus a one-block border around it, laid out like
+    /// `getSectionWithBorder`, one byte per block holding sky light in the high four bits and block light in the
+    /// low four (`sky = v >> 4`, `block = v & 15`): enough for smooth lighting and ambient occlusion in one pass.
+    /// Levels are the ones `getBlockLight`/`getSkyLight` report; unloaded columns read as full sky light without
+    /// block light. Returns null if the section's own column isn't loaded or the section is outside the world.
+    #[napi]
+    pub fn get_light_with_border(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<Uint8Array> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) { return None; }
+        let light = self.read_with_border(ChunkCoords { x: chunk_x, z: chunk_z }, section_y, 15 << 4, |column, pos| {
+            ((column.get_sky_light(pos) << 4) | column.get_block_light(pos)) as u32
+        })?;
+        Some(light.into_iter().map(|packed| packed as u8).collect::<Vec<u8>>().into())
+    }
+}
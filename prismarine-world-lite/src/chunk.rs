@@ -1,10 +1,11 @@
 // src/chunk.rs
-use crate::coords::{get_biome_index, get_section_block_index, BiomeCoords, SectionRelCoords, WorldCoords, SECTION_COUNT};
-use crate::palette::PaletteContainer;
+use crate::coords::{get_biome_index, get_section_block_index, section_idx_to_section_y, BiomeCoords, SectionRelCoords, WorldCoords, BIOME_SECTION_VOLUME, SECTION_COUNT, SECTION_HEIGHT, SECTION_VOLUME, SECTION_WIDTH};
+use crate::nbt::Tag;
+use crate::palette::{needed_bits, BitArray, PaletteContainer};
+use crate::registry::BlockRegistry;
+use std::collections::HashMap;
 // Add the Shr trait for the >> operator
 use std::ops::Shr;
-// Optional: For block entities
-// use fastnbt::Value;
 
 #[derive(Debug, Clone)]
 pub struct ChunkSection {
@@ -25,6 +26,20 @@ impl ChunkSection {
         }
     }
 
+    // Builds a section from all of its block states in index order (see `get_section_block_index`), picking the
+    // smallest container that holds them; Direct containers get the registry's global width, so any state of the
+    // version can be written into them later. Biomes are left at 0.
+    pub fn from_block_states(states: &[u32], registry: &BlockRegistry) -> Self {
+        let solid_block_count = states.iter().filter(|&&state| !registry.is_air(state)).count() as i16;
+        let blocks = PaletteContainer::from_values(states, 4, 8, registry.global_bits());
+        ChunkSection::new(blocks, PaletteContainer::new_single(0), solid_block_count)
+    }
+
+    // Replaces all biomes of the section, given in index order (see `get_biome_index`)
+    pub fn set_biomes(&mut self, biomes: &[u32]) {
+        self.biomes = PaletteContainer::from_values(biomes, 1, 3, 0);
+    }
+
     pub fn get_block_state_id(&self, coords: SectionRelCoords) -> u32 {
         let index = get_section_block_index(coords);
         self.block_states.get(index)
@@ -54,9 +69,37 @@ impl ChunkSection {
         self.biomes.get(index)
     }
 
+    pub fn solid_block_count(&self) -> i16 {
+        self.solid_block_count
+    }
+
+    // Which 4x4x4 cells of the section hold a non-air (non-zero) state: bit `(y * 4 + z) * 4 + x` for the cell at
+    // (x, y, z) in cell units. Lets rays skip empty space without reading blocks.
+    pub fn occupancy(&self) -> u64 {
+        if !self.block_states.may_contain(|state_id| state_id != 0) { return 0; }
+        if !self.block_states.may_contain(|state_id| state_id == 0) { return u64::MAX; }
+        let mut states = vec![0u32; SECTION_VOLUME];
+        self.block_states.unpack_into(&mut states);
+        let mut mask = 0u64;
+        for (index, &state_id) in states.iter().enumerate() {
+            if state_id == 0 { continue; }
+            let (x, y, z) = (index & 15, index >> 8, (index >> 4) & 15);
+            mask |= 1 << (((y >> 2) * 4 + (z >> 2)) * 4 + (x >> 2));
+        }
+        mask
+    }
+
+    pub fn block_states(&self) -> &PaletteContainer {
+        &self.block_states
+    }
+
+    pub fn biomes(&self) -> &PaletteContainer {
+        &self.biomes
+    }
+
      pub fn set_biome_id(&mut self, coords: BiomeCoords, biome_id: u32) {
         let index = get_biome_index(coords);
-        self.biomes.set(index, biome_id);
+        self.biomes.set_sized(index, biome_id, BIOME_SECTION_VOLUME, 1);
          // Note: Palette resizing/type change happens within PaletteContainer::set
     }
 }
@@ -65,8 +108,14 @@ impl ChunkSection {
 pub struct ChunkColumn {
     // Sections are stored by their Y index relative to MIN_SECTION_Y (0 to SECTION_COUNT-1)
     sections: Vec<Option<ChunkSection>>,
-    // Optional: Store block entities if needed
-    // block_entities: HashMap<WorldCoords, Value>,
+    // Block entity data (signs, chests...) by position, as the NBT compound the server or save file gave
+    block_entities: HashMap<WorldCoords, Tag>,
+}
+
+impl Default for ChunkColumn {
+    fn default() -> Self {
+        ChunkColumn::new()
+    }
 }
 
 impl ChunkColumn {
@@ -74,7 +123,55 @@ impl ChunkColumn {
         ChunkColumn {
             // Initialize with None for all possible sections
             sections: vec![None; SECTION_COUNT],
-            // block_entities: HashMap::new(),
+            block_entities: HashMap::new(),
+        }
+    }
+
+    // Builds a column of horizontal layers stacked from the bottom of the world, given as (state ID, height)
+    // pairs. Everything above the layers is air; sections containing only air are left out.
+    pub fn from_layers(layers: &[(u32, u32)]) -> Self {
+        let mut state_by_y = Vec::new();
+        for &(state_id, height) in layers {
+            state_by_y.extend(std::iter::repeat_n(state_id, height as usize));
+        }
+        let layer_area = (SECTION_WIDTH * SECTION_WIDTH) as usize;
+
+        let mut column = ChunkColumn::new();
+        for (idx, section_layers) in state_by_y.chunks(SECTION_HEIGHT as usize).take(SECTION_COUNT).enumerate() {
+            let mut layer_states = [0u32; SECTION_HEIGHT as usize];
+            layer_states[..section_layers.len()].copy_from_slice(section_layers);
+            if layer_states.iter().all(|&state| state == 0) { continue; }
+
+            let solid_block_count = (layer_states.iter().filter(|&&state| state != 0).count() * layer_area) as i16;
+            let mut palette: Vec<u32> = Vec::new();
+            for state in layer_states {
+                if !palette.contains(&state) { palette.push(state); }
+            }
+
+            let block_states = if palette.len() == 1 {
+                PaletteContainer::new_single(palette[0])
+            } else {
+                let mut data = BitArray::new(needed_bits(palette.len() - 1).max(4), SECTION_HEIGHT as usize * layer_area);
+                for (y, state) in layer_states.iter().enumerate() {
+                    let palette_index = palette.iter().position(|p| p == state).unwrap() as u32;
+                    for i in 0..layer_area {
+                        data.set(y * layer_area + i, palette_index);
+                    }
+                }
+                PaletteContainer::new_indirect(palette, data)
+            };
+            column.sections[idx] = Some(ChunkSection::new(block_states, PaletteContainer::new_single(0), solid_block_count));
+        }
+        column
+    }
+
+    // Sets the biome of the whole column, creating all-air sections where none exist
+    pub fn fill_biome(&mut self, biome_id: u32) {
+        for section in self.sections.iter_mut() {
+            match section {
+                Some(section) => section.biomes = PaletteContainer::new_single(biome_id),
+                None => *section = Some(ChunkSection::new(PaletteContainer::new_single(0), PaletteContainer::new_single(biome_id), 0)),
+            }
         }
     }
 
@@ -94,6 +191,14 @@ impl ChunkColumn {
             .and_then(|idx| self.sections[idx].as_ref())
     }
 
+    // Iterates over the sections that are present, with their section Y index
+    pub fn sections(&self) -> impl Iterator<Item = (i32, &ChunkSection)> {
+        self.sections
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, section)| section.as_ref().map(|s| (section_idx_to_section_y(idx), s)))
+    }
+
     pub fn insert_section(&mut self, section_y_index: i32, section: ChunkSection) {
         if let Some(idx) = Self::section_y_to_vec_index(section_y_index << 4) { // Convert section Y back to world Y for index calc
              if idx < self.sections.len() {
@@ -102,6 +207,22 @@ impl ChunkColumn {
         }
     }
 
+    pub fn block_entity(&self, coords: WorldCoords) -> Option<&Tag> {
+        self.block_entities.get(&coords)
+    }
+
+    pub fn set_block_entity(&mut self, coords: WorldCoords, data: Tag) {
+        self.block_entities.insert(coords, data);
+    }
+
+    pub fn remove_block_entity(&mut self, coords: WorldCoords) -> Option<Tag> {
+        self.block_entities.remove(&coords)
+    }
+
+    pub fn block_entities(&self) -> impl Iterator<Item = (&WorldCoords, &Tag)> {
+        self.block_entities.iter()
+    }
+
     pub fn get_block_state_id(&self, coords: WorldCoords) -> u32 {
         Self::section_y_to_vec_index(coords.y)
             .and_then(|idx| self.sections[idx].as_ref())
@@ -138,11 +259,17 @@ impl ChunkColumn {
      pub fn set_biome_id(&mut self, coords: WorldCoords, biome_id: u32) {
          if let Some(idx) = Self::section_y_to_vec_index(coords.y) {
              if idx < self.sections.len() {
+                if self.sections[idx].is_none() && biome_id != 0 {
+                    // Create an all-air section so the biome has somewhere to live
+                    self.sections[idx] = Some(ChunkSection::new(
+                        PaletteContainer::new_single(0),
+                        PaletteContainer::new_single(0),
+                        0
+                    ));
+                }
                 if let Some(section) = self.sections[idx].as_mut() {
                     section.set_biome_id(coords.to_biome_coords(), biome_id);
                 }
-                // Optionally create section if it doesn't exist to set biome?
-                // else if biome_id != 0 { ... create section ... }
              }
         }
     }
@@ -166,4 +293,34 @@ impl ChunkColumn {
         // Placeholder - Light data parsing not implemented
         15
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::test_registry;
+
+    fn rel(index: usize) -> SectionRelCoords {
+        SectionRelCoords { x: (index & 15) as i32, y: (index >> 8) as i32, z: ((index >> 4) & 15) as i32 }
+    }
+
+    #[test]
+    fn direct_section_accepts_any_state_of_the_version() {
+        let registry = test_registry();
+        // 300 distinct states overflow the indirect palette, the largest of them needs only 9 bits
+        let states: Vec<u32> = (0..SECTION_VOLUME as u32).map(|i| i % 300).collect();
+        let mut section = ChunkSection::from_block_states(&states, &registry);
+        section.set_block_state_id(rel(5), 1041);
+        assert_eq!(section.get_block_state_id(rel(5)), 1041);
+        assert_eq!(section.get_block_state_id(rel(6)), 6);
+    }
+
+    #[test]
+    fn solid_count_skips_every_kind_of_air() {
+        let registry = test_registry();
+        let mut states = vec![1u32; SECTION_VOLUME];
+        states[0] = 0;
+        states[1] = 3;
+        let section = ChunkSection::from_block_states(&states, &registry);
+        assert_eq!(section.solid_block_count() as usize, SECTION_VOLUME - 2);
+    }
+}
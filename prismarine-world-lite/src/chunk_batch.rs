@@ -0,0 +1,94 @@
+// src/chunk_batch.rs
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::time::Duration;
+
+// Vanilla's ChunkBatchSizeCalculator: starts at 2ms per chunk and keeps a running average over up to 49 batches
+const INITIAL_NANOS_PER_CHUNK: f64 = 2_000_000.0;
+const MAX_SAMPLES: u32 = 49;
+const TARGET_NANOS_PER_TICK: f64 = 7_000_000.0;
+
+#[derive(Debug)]
+pub struct ChunkBatchState {
+    active: bool,
+    chunks: u32,
+    parse_time: Duration,
+    avg_nanos_per_chunk: f64,
+    samples: u32,
+}
+
+impl Default for ChunkBatchState {
+    fn default() -> Self {
+        ChunkBatchState { active: false, chunks: 0, parse_time: Duration::ZERO, avg_nanos_per_chunk: INITIAL_NANOS_PER_CHUNK, samples: 1 }
+    }
+}
+
+#[napi(object)]
+pub struct ChunkBatchResult {
+    pub chunk_count: u32,
+    pub parse_ms: f64, // Time spent parsing the batch's columns
+    pub chunks_per_tick: f64, // Value for the chunk_batch_received packet
+}
+
+impl NapiWorld {
+    // Accounts a parsed column to the open batch, if any
+    pub(crate) fn record_batch_chunk(&self, parse_time: Duration) {
+        let mut batch = self.chunk_batch.lock().unwrap();
+        if batch.active {
+            batch.chunks += 1;
+            batch.parse_time += parse_time;
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Starts a chunk batch (the chunk_batch_start packet). Until `end_chunk_batch`, column load timings are
+    /// collected and block update events are held back.
+    #[napi]
+    pub fn begin_chunk_batch(&self) -> Result<()> {
+        let mut batch = self.chunk_batch.lock().unwrap();
+        if batch.active {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "A chunk batch is already in progress".to_string(),
+            ));
+        }
+        batch.active = true;
+        batch.chunks = 0;
+        batch.parse_time = Duration::ZERO;
+        drop(batch);
+        self.hold_events();
+        Ok(())
+    }
+
+    /// Finishes the chunk batch (the chunk_batch_finished packet), delivering held events. Returns the batch's parse
+    /// timing and the chunks-per-tick rate to answer with, averaged over recent batches the way the vanilla client does.
+    #[napi]
+    pub fn end_chunk_batch(&self) -> Result<ChunkBatchResult> {
+        let mut batch = self.chunk_batch.lock().unwrap();
+        if !batch.active {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "No chunk batch is in progress".to_string(),
+            ));
+        }
+        batch.active = false;
+
+        if batch.chunks > 0 {
+            let nanos_per_chunk = (batch.parse_time.as_nanos() as f64 / batch.chunks as f64)
+                .clamp(batch.avg_nanos_per_chunk / 3.0, batch.avg_nanos_per_chunk * 3.0);
+            batch.avg_nanos_per_chunk = (batch.avg_nanos_per_chunk * batch.samples as f64 + nanos_per_chunk) / (batch.samples + 1) as f64;
+            batch.samples = (batch.samples + 1).min(MAX_SAMPLES);
+        }
+        let result = ChunkBatchResult {
+            chunk_count: batch.chunks,
+            parse_ms: batch.parse_time.as_secs_f64() * 1000.0,
+            chunks_per_tick: TARGET_NANOS_PER_TICK / batch.avg_nanos_per_chunk,
+        };
+        drop(batch);
+        self.release_events();
+        Ok(result)
+    }
+}
@@ -0,0 +1,44 @@
+// src/chunk_keys.rs
+use crate::coords::ChunkCoords;
+use crate::prefetch::ChunkPosition;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// Columns given to a column API either as `(chunkX, chunkZ)` or as one `chunkKey` bigint
+pub(crate) fn column_coords(chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<ChunkCoords> {
+    match (chunk_x, chunk_z) {
+        (Either::A(x), Some(z)) => Ok(ChunkCoords { x, z }),
+        (Either::A(x), None) => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Missing chunkZ for chunkX {}; pass both coordinates or a chunk key", x),
+        )),
+        (Either::B(key), None) => key_coords(&key),
+        (Either::B(_), Some(_)) => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            "A chunk key already holds both coordinates; pass it alone".to_string(),
+        )),
+    }
+}
+
+fn key_coords(key: &BigInt) -> Result<ChunkCoords> {
+    let (value, lossless) = key.get_i64();
+    if !lossless {
+        return Err(napi::Error::new(napi::Status::InvalidArg, "Chunk key doesn't fit in 64 bits".to_string()));
+    }
+    Ok(ChunkCoords::from_key(value))
+}
+
+/// Packs chunk coordinates into a signed 64-bit key, x in the high 32 bits and z in the low 32 bits, i.e.
+/// `(BigInt(x) << 32n) | (BigInt(z) & 0xffffffffn)`. Keys can key `Map`s and `Set`s of columns and be passed to
+/// the column methods in place of `chunkX, chunkZ`.
+#[napi]
+pub fn chunk_key(chunk_x: i32, chunk_z: i32) -> BigInt {
+    BigInt::from(ChunkCoords { x: chunk_x, z: chunk_z }.key())
+}
+
+/// Unpacks a key made by `chunkKey` into its chunk coordinates.
+#[napi]
+pub fn parse_chunk_key(key: BigInt) -> Result<ChunkPosition> {
+    let coords = key_coords(&key)?;
+    Ok(ChunkPosition { chunk_x: coords.x, chunk_z: coords.z })
+}
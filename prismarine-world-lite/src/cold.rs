@@ -0,0 +1,310 @@
+// src/cold.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::nbt::Tag;
+use crate::serialization::{deserialize_column, serialize_column};
+use crate::world::{ColumnStore, NapiWorld};
+use dashmap::DashMap;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+
+// A column compressed after sitting idle: its sections in the `serialize_column` format, deflated, plus its block
+// entities, which that format leaves out
+#[derive(Clone)]
+struct ColdColumn {
+    data: Vec<u8>,
+    block_entities: Vec<(WorldCoords, Tag)>,
+}
+
+impl ColdColumn {
+    fn compress(column: &ChunkColumn) -> ColdColumn {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&serialize_column(column)).expect("writing to a Vec can't fail");
+        let mut data = encoder.finish().expect("writing to a Vec can't fail");
+        data.shrink_to_fit();
+        let block_entities = column.block_entities().map(|(&pos, tag)| (pos, tag.clone())).collect();
+        ColdColumn { data, block_entities }
+    }
+
+    fn decompress(self) -> std::io::Result<ChunkColumn> {
+        let mut serialized = Vec::new();
+        DeflateDecoder::new(self.data.as_slice()).read_to_end(&mut serialized)?;
+        let mut column = deserialize_column(&serialized)?;
+        for (pos, tag) in self.block_entities {
+            column.set_block_entity(pos, tag);
+        }
+        Ok(column)
+    }
+}
+
+// Compressed idle columns and the access times deciding which columns go idle (or get unloaded by
+// `unload_older_than`). Shared by all handles to a world.
+pub struct ColdColumns {
+    idle_ms: AtomicU64, // 0 while the policy is off
+    tracking_ages: AtomicBool, // Access times are kept for `get_column_age` even while the policy is off
+    epoch: Instant,     // Access times are milliseconds since this
+    last_access: DashMap<ChunkCoords, AtomicU64>,
+    columns: DashMap<ChunkCoords, ColdColumn>,
+    last_sweep_ms: AtomicU64,
+    // Held while a column moves back into the loaded columns, so lookups racing the move wait for it instead of
+    // seeing the column in neither map
+    thawing: Mutex<()>,
+    compressions: AtomicU64,
+    thaws: AtomicU64,
+}
+
+impl Default for ColdColumns {
+    fn default() -> Self {
+        ColdColumns {
+            idle_ms: AtomicU64::new(0),
+            tracking_ages: AtomicBool::new(false),
+            epoch: Instant::now(),
+            last_access: DashMap::new(),
+            columns: DashMap::new(),
+            last_sweep_ms: AtomicU64::new(0),
+            thawing: Mutex::new(()),
+            compressions: AtomicU64::new(0),
+            thaws: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ColdColumns {
+    fn now_ms(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    fn tracking(&self) -> bool {
+        self.idle_ms.load(Ordering::Relaxed) != 0 || self.tracking_ages.load(Ordering::Relaxed)
+    }
+
+    // Records an access to a loaded column. Two atomic loads while access times aren't tracked.
+    pub fn touch(&self, coords: ChunkCoords) {
+        if !self.tracking() { return; }
+        let now = self.now_ms();
+        match self.last_access.get(&coords) {
+            Some(time) => time.store(now, Ordering::Relaxed),
+            None => { self.last_access.insert(coords, AtomicU64::new(now)); }
+        }
+    }
+
+    pub fn forget(&self, coords: ChunkCoords) {
+        self.last_access.remove(&coords);
+        self.columns.remove(&coords);
+    }
+
+    // Starts keeping access times for good. Columns without one yet count as accessed now.
+    // Returns whether access times only started being kept now, so no column has an age yet
+    pub fn track_ages(&self, loaded: impl Iterator<Item = ChunkCoords>) -> bool {
+        let was_tracking = self.tracking();
+        if self.tracking_ages.swap(true, Ordering::Relaxed) { return false; }
+        let now = self.now_ms();
+        for coords in loaded {
+            self.last_access.entry(coords).or_insert_with(|| AtomicU64::new(now));
+        }
+        !was_tracking
+    }
+
+    // Milliseconds since a column (loaded or cold) was last accessed, if its access time is known
+    pub fn age_ms(&self, coords: ChunkCoords) -> Option<u64> {
+        let last_access = self.last_access.get(&coords)?.load(Ordering::Relaxed);
+        Some(self.now_ms().saturating_sub(last_access))
+    }
+
+    pub fn cold_coords(&self) -> Vec<ChunkCoords> {
+        self.columns.iter().map(|entry| *entry.key()).collect()
+    }
+
+    // Takes over another world's policy, for `World.clone` and `World.fork`
+    pub fn copy_policy_from(&self, other: &ColdColumns) {
+        self.idle_ms.store(other.idle_ms.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
+#[napi(object)]
+pub struct ColdColumnStats {
+    pub cold_columns: u32,
+    pub compressed_bytes: i64,
+    pub compressions: i64,
+    pub thaws: i64,
+}
+
+impl NapiWorld {
+    // Moves a compressed column back into the loaded columns. Returns whether the column is loaded afterwards.
+    pub(crate) fn thaw_column(&self, coords: ChunkCoords) -> bool {
+        // Most lookups of a missing column are for ones that were never loaded, which don't need the lock
+        if !self.cold.columns.contains_key(&coords) { return self.columns.contains_key(&coords); }
+        let _thawing = self.cold.thawing.lock().unwrap();
+        if self.columns.contains_key(&coords) { return true; } // Thawed by another thread meanwhile
+        let Some((_, cold)) = self.cold.columns.remove(&coords) else { return false; };
+        // The blob was written by `ColdColumn::compress` moments ago, so it can only fail to read on a bug
+        let column = cold.decompress().expect("Failed to decompress a cold column");
+        self.columns.insert(coords, Arc::new(RwLock::new(column)));
+        self.cold.thaws.fetch_add(1, Ordering::Relaxed);
+        self.cold.touch(coords);
+        true
+    }
+
+    // Every column of the world, for whole-world passes like saving and scans: the loaded ones, plus decompressed
+    // copies of the cold ones, which stay compressed. Sorted by x, then z.
+    pub(crate) fn all_columns(&self) -> Vec<(ChunkCoords, Arc<RwLock<ChunkColumn>>)> {
+        // Taken so no column moves from cold to loaded between the two passes; one compressed in between shows up
+        // in both and is kept once
+        let thawing = self.cold.thawing.lock().unwrap();
+        let mut columns: Vec<_> = self.columns.iter().map(|entry| (*entry.key(), Arc::clone(entry.value()))).collect();
+        let cold: Vec<_> = self
+            .cold
+            .columns
+            .iter()
+            .filter(|entry| !self.columns.contains_key(entry.key()))
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+        drop(thawing);
+        for (coords, cold) in cold {
+            let column = cold.decompress().expect("Failed to decompress a cold column");
+            columns.push((coords, Arc::new(RwLock::new(column))));
+        }
+        columns.sort_unstable_by_key(|(coords, _)| (coords.x, coords.z));
+        columns.dedup_by_key(|(coords, _)| *coords);
+        columns
+    }
+
+    // A store for `World.fork`, holding this world's loaded columns themselves (copied once either world writes
+    // them) and copies of its compressed ones
+    pub(crate) fn fork_store(&self) -> ColumnStore {
+        let store = ColumnStore { forked: Arc::new(AtomicBool::new(true)), ..Default::default() };
+        // Taken so no column moves from cold to loaded while they're copied, like in `all_columns`
+        let _thawing = self.cold.thawing.lock().unwrap();
+        for entry in self.columns.iter() {
+            store.columns.insert(*entry.key(), Arc::clone(entry.value()));
+        }
+        for entry in self.cold.columns.iter().filter(|entry| !self.columns.contains_key(entry.key())) {
+            store.cold.columns.insert(*entry.key(), entry.value().clone());
+        }
+        store
+    }
+
+    // Compresses loaded columns idle for longer than the policy allows, skipping ones something holds right now.
+    // Returns the number of columns compressed.
+    pub(crate) fn compress_idle_columns(&self, idle_ms: u64) -> u32 {
+        let now = self.cold.now_ms();
+        self.cold.last_sweep_ms.store(now, Ordering::Relaxed);
+        let coords: Vec<ChunkCoords> = self.columns.iter().map(|entry| *entry.key()).collect();
+        let mut compressed = 0;
+        for coords in coords {
+            let last_access = match self.cold.last_access.get(&coords) {
+                Some(time) => time.load(Ordering::Relaxed),
+                None => {
+                    // Loaded before the policy was on: the idle time starts now
+                    self.cold.last_access.insert(coords, AtomicU64::new(now));
+                    continue;
+                }
+            };
+            if now.saturating_sub(last_access) < idle_ms { continue; }
+            // Compressed while the map shard is locked, so no reader can pick the column up in between and every
+            // lookup finds it either loaded or cold
+            let removed = self.columns.remove_if(&coords, |_, column| {
+                if Arc::strong_count(column) != 1 { return false; }
+                let Ok(column) = column.try_read() else { return false; };
+                self.cold.columns.insert(coords, ColdColumn::compress(&column));
+                true
+            });
+            if removed.is_some() {
+                compressed += 1;
+            }
+        }
+        self.cold.compressions.fetch_add(compressed as u64, Ordering::Relaxed);
+        compressed
+    }
+
+    // Runs a sweep if the policy is on and the last one is older than a quarter of the idle time
+    pub(crate) fn maybe_compress_idle_columns(&self) {
+        let idle_ms = self.cold.idle_ms.load(Ordering::Relaxed);
+        if idle_ms == 0 { return; }
+        let since_sweep = self.cold.now_ms().saturating_sub(self.cold.last_sweep_ms.load(Ordering::Relaxed));
+        if since_sweep >= (idle_ms / 4).max(1000) {
+            self.compress_idle_columns(idle_ms);
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Compresses columns not accessed for `idleSeconds` into compact blobs, decompressing them transparently on
+    /// their next access. Sweeps run as columns get loaded; `null` turns the policy off (cold columns stay
+    /// compressed until accessed). Whole-world passes (`saveToRegionDir`, `scanWorld`, `getWorldHash`, `validate`)
+    /// include cold columns, decompressing copies of them without loading them back.
+    #[napi]
+    pub fn set_cold_column_policy(&self, idle_seconds: Option<f64>) -> Result<()> {
+        let idle_ms = match idle_seconds {
+            Some(seconds) if !seconds.is_finite() || seconds <= 0.0 => return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("idleSeconds must be positive, got {}", seconds),
+            )),
+            Some(seconds) => ((seconds * 1000.0) as u64).max(1),
+            None => 0,
+        };
+        self.cold.idle_ms.store(idle_ms, Ordering::Relaxed);
+        if !self.cold.tracking() {
+            self.cold.last_access.clear();
+        }
+        Ok(())
+    }
+
+    /// Compresses idle columns right away instead of waiting for the next sweep. Returns how many were compressed.
+    /// Does nothing while no policy is set.
+    #[napi]
+    pub fn compress_cold_columns(&self) -> u32 {
+        match self.cold.idle_ms.load(Ordering::Relaxed) {
+            0 => 0,
+            idle_ms => self.compress_idle_columns(idle_ms),
+        }
+    }
+
+    /// Gets the number of compressed columns and their total size, and how often columns were compressed and
+    /// decompressed.
+    #[napi]
+    pub fn get_cold_column_stats(&self) -> ColdColumnStats {
+        let compressed_bytes: usize = self.cold.columns.iter().map(|entry| entry.data.len()).sum();
+        ColdColumnStats {
+            cold_columns: self.cold.columns.len() as u32,
+            compressed_bytes: compressed_bytes as i64,
+            compressions: self.cold.compressions.load(Ordering::Relaxed) as i64,
+            thaws: self.cold.thaws.load(Ordering::Relaxed) as i64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::ColumnStore;
+
+    #[test]
+    fn worlds_on_the_same_columns_find_them_cold() {
+        let store = ColumnStore::default();
+        let (registry, biomes) = (Arc::new(test_registry()), Arc::new(test_biomes()));
+        let world = NapiWorld::from_parts(store.clone(), Arc::clone(&registry), Arc::clone(&biomes));
+        let other = NapiWorld::from_parts(store, registry, biomes);
+
+        let mut column = ChunkColumn::new();
+        column.set_block_state_id(WorldCoords { x: 1, y: 70, z: 2 }, 1);
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, column);
+        world.compress_idle_columns(0); // Starts the column's idle time
+        assert_eq!(world.compress_idle_columns(0), 1);
+        assert!(world.columns.is_empty());
+
+        assert_eq!(other.all_columns().len(), 1);
+        assert_eq!(other.get_block_state_id(1, 70, 2), 1);
+        assert_eq!(world.get_cold_column_stats().cold_columns, 0);
+        assert!(world.columns.contains_key(&ChunkCoords { x: 0, z: 0 }));
+    }
+}
@@ -0,0 +1,85 @@
+// src/column_reads.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::prefetch::ChunkPosition;
+use crate::region::Region;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct BlockBox {
+    pub min: WorldCoords,
+    pub max: WorldCoords,
+}
+
+/// Reads to service under the locks of `withColumns`.
+#[napi(object)]
+pub struct ColumnReads {
+    /// Block positions to read, as x, y, z triples.
+    pub positions: Option<Int32Array>,
+    /// Inclusive block boxes to read whole, e.g. the blocks around an entity's bounding box.
+    pub boxes: Option<Vec<BlockBox>>,
+}
+
+#[napi(object)]
+pub struct ColumnReadResult {
+    /// Whether each requested column was loaded, in request order.
+    pub loaded: Vec<bool>,
+    /// State IDs at `positions`, in order.
+    pub states: Uint32Array,
+    /// State IDs of each box, indexed `((y - min.y) * sizeZ + (z - min.z)) * sizeX + (x - min.x)`.
+    pub boxes: Vec<Uint32Array>,
+}
+
+#[napi]
+impl NapiWorld {
+    /// Reads blocks from a group of columns (e.g. the 3x3 neighborhood of an entity) under one set of read locks
+    /// taken up front, so every read sees the columns in the same state and locks are taken once per column
+    /// instead of once per read. Positions outside the requested columns, or in columns that aren't loaded,
+    /// read as air; check `loaded` to tell the two apart.
+    #[napi]
+    pub fn with_columns(&self, chunks: Vec<ChunkPosition>, reads: ColumnReads) -> Result<ColumnReadResult> {
+        let coords: Vec<ChunkCoords> = chunks.iter().map(|c| ChunkCoords { x: c.chunk_x, z: c.chunk_z }).collect();
+        let columns: Vec<Option<Arc<RwLock<ChunkColumn>>>> = coords
+            .iter()
+            .map(|&coords| {
+                self.ensure_column(coords);
+                self.columns.get(&coords).map(|entry| Arc::clone(entry.value()))
+            })
+            .collect();
+        let mut guards: HashMap<ChunkCoords, RwLockReadGuard<'_, ChunkColumn>> = HashMap::with_capacity(columns.len());
+        for (&coords, column) in coords.iter().zip(&columns) {
+            let Some(column) = column else { continue; };
+            let guard = column.try_read().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire read lock for chunk".to_string(),
+            ))?;
+            guards.insert(coords, guard);
+        }
+        let read = |pos: WorldCoords| guards.get(&pos.to_chunk_coords()).map_or(0, |column| column.get_block_state_id(pos));
+
+        let states: Vec<u32> = match &reads.positions {
+            Some(positions) => {
+                if !positions.len().is_multiple_of(3) {
+                    return Err(napi::Error::new(
+                        napi::Status::InvalidArg,
+                        format!("Position buffer length must be a multiple of 3, got {}", positions.len()),
+                    ));
+                }
+                positions.chunks_exact(3).map(|p| read(WorldCoords { x: p[0], y: p[1], z: p[2] })).collect()
+            }
+            None => Vec::new(),
+        };
+        let boxes = reads
+            .boxes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|block_box| Region::new(block_box.min, block_box.max).positions().map(read).collect::<Vec<u32>>().into())
+            .collect();
+        Ok(ColumnReadResult { loaded: columns.iter().map(Option::is_some).collect(), states: states.into(), boxes })
+    }
+}
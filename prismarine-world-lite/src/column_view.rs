@@ -0,0 +1,109 @@
+// src/column_view.rs
+use crate::chunk::ChunkColumn;
+use crate::chunk_keys::column_coords;
+use crate::coords::{ChunkCoords, WorldCoords, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_COUNT, SECTION_VOLUME};
+use crate::world::{ColumnMap, NapiWorld};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+/// Read-only access to one loaded column, sharing the world's storage instead of copying it. A view keeps
+/// reading the column it was made for, even after the world unloads or replaces it (see `is_current`).
+#[napi]
+pub struct ColumnView {
+    coords: ChunkCoords,
+    column: Arc<RwLock<ChunkColumn>>,
+    // The world's column storage, to tell whether the view is still the loaded column
+    columns: ColumnMap,
+}
+
+impl ColumnView {
+    fn read(&self) -> Result<RwLockReadGuard<'_, ChunkColumn>> {
+        self.column.try_read().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire read lock for chunk".to_string(),
+        ))
+    }
+
+    // Positions in other columns read as air
+    fn in_column(&self, x: i32, z: i32) -> bool {
+        x >> 4 == self.coords.x && z >> 4 == self.coords.z
+    }
+}
+
+#[napi]
+impl ColumnView {
+    #[napi(getter)]
+    pub fn chunk_x(&self) -> i32 {
+        self.coords.x
+    }
+
+    #[napi(getter)]
+    pub fn chunk_z(&self) -> i32 {
+        self.coords.z
+    }
+
+    /// Checks whether the column is still the one the world has loaded at its position.
+    #[napi]
+    pub fn is_current(&self) -> bool {
+        self.columns.get(&self.coords).is_some_and(|entry| Arc::ptr_eq(entry.value(), &self.column))
+    }
+
+    /// Gets the state ID of the block at the given world coordinates. Positions outside this column read as air.
+    #[napi]
+    pub fn get_block_state_id(&self, x: i32, y: i32, z: i32) -> u32 {
+        if !self.in_column(x, z) { return 0; }
+        self.column.try_read().map(|column| column.get_block_state_id(WorldCoords { x, y, z })).unwrap_or(0)
+    }
+
+    /// Gets the biome ID at the given world coordinates. Positions outside this column read as biome 0.
+    #[napi]
+    pub fn get_biome_id(&self, x: i32, y: i32, z: i32) -> u32 {
+        if !self.in_column(x, z) { return 0; }
+        self.column.try_read().map(|column| column.get_biome_id(WorldCoords { x, y, z })).unwrap_or(0)
+    }
+
+    /// Gets the state IDs of all 4096 blocks of a section, indexed by `(y * 16 + z) * 16 + x` (section-relative).
+    /// Missing sections read as air.
+    #[napi]
+    pub fn get_section_blocks(&self, section_y: i32) -> Result<Uint32Array> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Section y={} is outside the world", section_y),
+            ));
+        }
+        let column = self.read()?;
+        let mut states = vec![0u32; SECTION_VOLUME];
+        if let Some(section) = column.get_section(section_y) {
+            section.block_states().unpack_into(&mut states);
+        }
+        Ok(states.into())
+    }
+
+    /// Exports the state IDs of every block of the column, section after section from the bottom of the world,
+    /// each indexed like `get_section_blocks`.
+    #[napi]
+    pub fn get_all_blocks(&self) -> Result<Uint32Array> {
+        let column = self.read()?;
+        let mut states = vec![0u32; SECTION_COUNT * SECTION_VOLUME];
+        for (section_y, section) in column.sections() {
+            let start = (section_y - MIN_SECTION_Y) as usize * SECTION_VOLUME;
+            section.block_states().unpack_into(&mut states[start..start + SECTION_VOLUME]);
+        }
+        Ok(states.into())
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Gets a read-only view of a loaded column that shares its storage instead of copying it, for readers such
+    /// as renderer workers. Takes coordinates or a `chunkKey`; returns `null` if the column isn't loaded.
+    #[napi]
+    pub fn get_column_view(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<Option<ColumnView>> {
+        let coords = column_coords(chunk_x, chunk_z)?;
+        self.ensure_column(coords);
+        let Some(entry) = self.columns.get(&coords) else { return Ok(None); };
+        Ok(Some(ColumnView { coords, column: Arc::clone(entry.value()), columns: Arc::clone(&self.columns) }))
+    }
+}
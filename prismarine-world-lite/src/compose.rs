@@ -0,0 +1,217 @@
+// src/compose.rs
+use crate::chunk::ChunkColumn;
+use crate::chunk_keys::column_coords;
+use crate::coords::ChunkCoords;
+use crate::region::Region;
+use crate::world::{ColumnStore, NapiWorld};
+use dashmap::DashMap;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+
+type SharedColumn = Arc<RwLock<ChunkColumn>>;
+
+impl NapiWorld {
+    // State IDs only mean the same blocks in worlds of the same version
+    fn check_same_version(&self, other: &NapiWorld) -> Result<()> {
+        if self.registry.minecraft_version != other.registry.minecraft_version {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!(
+                    "Cannot copy columns between a {} world and a {} world",
+                    self.registry.minecraft_version, other.registry.minecraft_version,
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    // A loaded column, thawed or read from region files if need be, without holding its map shard
+    fn shared_column(&self, coords: ChunkCoords) -> Option<SharedColumn> {
+        self.ensure_column(coords);
+        self.columns.get(&coords).map(|entry| Arc::clone(entry.value()))
+    }
+
+    // A deep copy of a column, or `None` if it isn't loaded
+    fn copy_of_column(&self, coords: ChunkCoords) -> Result<Option<ChunkColumn>> {
+        let Some(column) = self.shared_column(coords) else { return Ok(None); };
+        let column = column.try_read().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire read lock for chunk".to_string(),
+        ))?;
+        Ok(Some(column.clone()))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Copies a loaded column (by coordinates or `chunkKey`) into another world of the same version, replacing
+    /// any column loaded there, without going through JS. The copy is independent of the original. Returns false
+    /// if the column isn't loaded in this world.
+    #[napi(ts_args_type = "other: World, chunkX: number | bigint, chunkZ?: number | undefined | null")]
+    pub fn copy_column_to(&self, other: &NapiWorld, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<bool> {
+        self.check_same_version(other)?;
+        let coords = column_coords(chunk_x, chunk_z)?;
+        let Some(column) = self.copy_of_column(coords)? else { return Ok(false); };
+        other.insert_column(coords, column);
+        Ok(true)
+    }
+
+    /// Overlays another world of the same version onto this one, e.g. a backup onto the live cache. Without
+    /// `region`, every column loaded in `other` is copied over, replacing the column loaded here. With `region`,
+    /// only the blocks and block entities inside it are copied, into columns loaded in both worlds, and blocks
+    /// that change are reported like any other block change. Returns the number of columns merged.
+    #[napi(ts_args_type = "other: World, region?: Region | null")]
+    pub fn merge_from(&self, other: &NapiWorld, region: Option<&Region>) -> Result<u32> {
+        self.check_same_version(other)?;
+        if Arc::ptr_eq(&self.columns, &other.columns) { return Ok(0); } // Shared worlds already hold the same columns
+
+        let Some(region) = region else {
+            let mut coords: Vec<ChunkCoords> = other.columns.iter().map(|entry| *entry.key()).collect();
+            coords.extend(other.cold.cold_coords());
+            let mut merged = 0;
+            for coords in coords {
+                if let Some(column) = other.copy_of_column(coords)? {
+                    self.insert_column(coords, column);
+                    merged += 1;
+                }
+            }
+            return Ok(merged);
+        };
+
+        let lock_failed = |kind: &str| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to acquire {} lock for chunk", kind),
+        );
+        let mut merged = 0;
+        let mut changed = Vec::new();
+        for chunk in region.chunks() {
+            let Some(source) = other.shared_column(chunk) else { continue; };
+            self.ensure_column(chunk);
+            self.unshare_column(chunk);
+            let Some(target) = self.shared_column(chunk) else { continue; };
+            let source = source.try_read().map_err(|_| lock_failed("read"))?;
+            let mut target = target.try_write().map_err(|_| lock_failed("write"))?;
+
+            let part = region.clip_to_chunk(chunk);
+            for pos in part.positions() {
+                let state_id = source.get_block_state_id(pos);
+                if target.get_block_state_id(pos) != state_id {
+                    target.set_block_state_id(pos, state_id);
+                    changed.push(pos);
+                }
+            }
+            let stale: Vec<_> = target.block_entities().map(|(&pos, _)| pos).filter(|&pos| part.contains(pos)).collect();
+            for pos in stale {
+                target.remove_block_entity(pos);
+            }
+            for (&pos, tag) in source.block_entities().filter(|(&pos, _)| part.contains(pos)) {
+                target.set_block_entity(pos, tag.clone());
+            }
+            merged += 1;
+        }
+        self.blocks_changed(&changed);
+        Ok(merged)
+    }
+
+    /// Creates an independent copy of this world: same version and options (unloaded write mode, write
+    /// deduplication, cold column policy, raycast acceleration), with deep copies of every loaded (and compressed)
+    /// column, so destructive analyses or simulations can't touch the original. Watchers, subscriptions,
+    /// transactions, deferred writes and attached region directories aren't copied. `fork` is cheaper when only a
+    /// few columns will be written.
+    #[napi(js_name = "clone")]
+    pub fn deep_clone(&self) -> Result<NapiWorld> {
+        let columns = DashMap::new();
+        for (coords, column) in self.all_columns() {
+            let column = column.read().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire read lock for chunk".to_string(),
+            ))?;
+            columns.insert(coords, Arc::new(RwLock::new(column.clone())));
+        }
+        Ok(self.copy_with(ColumnStore { columns: Arc::new(columns), ..Default::default() }))
+    }
+
+    /// Creates a copy-on-write fork of this world: like `clone`, but columns aren't copied up front. Both worlds
+    /// keep reading the same columns, and the first write to one of them, from either world, gives the writer its
+    /// own copy of that column, so neither sees the other's writes. Suits read-mostly uses like trying out a few
+    /// placements. Columns shared with a fork aren't compressed by the cold column policy until one side writes
+    /// them.
+    #[napi]
+    pub fn fork(&self) -> NapiWorld {
+        self.forked.store(true, Ordering::Relaxed);
+        self.copy_with(self.fork_store())
+    }
+}
+
+impl NapiWorld {
+    // A world on `store` with this world's version and options
+    fn copy_with(&self, store: ColumnStore) -> NapiWorld {
+        let mut world = NapiWorld::from_parts(store, Arc::clone(&self.registry), Arc::clone(&self.biomes));
+        world.config = Arc::clone(&self.config);
+        world.bedrock_runtime_ids = self.bedrock_runtime_ids.clone();
+        *world.unloaded_writes.write().unwrap() = *self.unloaded_writes.read().unwrap();
+        world.write_dedup.store(self.write_dedup.load(Ordering::Relaxed), Ordering::Relaxed);
+        world.cold.copy_policy_from(&self.cold);
+        world.set_raycast_acceleration(self.raycast_acceleration());
+        world
+    }
+
+    // Copy-on-write for forks: swaps a loaded column this world still shares with a fork (or the world it was
+    // forked from) for a private copy, before writing it. A snapshot holding the column also counts as sharing it.
+    pub(crate) fn unshare_column(&self, coords: ChunkCoords) {
+        if !self.forked.load(Ordering::Relaxed) { return; }
+        let Some(shared) = self.columns.get(&coords).map(|entry| Arc::clone(entry.value())) else { return; };
+        if Arc::strong_count(&shared) <= 2 { return; } // Only this world's map and `shared`
+        let copy = shared.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        // Left alone if the column was replaced meanwhile
+        if let Some(mut entry) = self.columns.get_mut(&coords) {
+            if Arc::ptr_eq(entry.value(), &shared) {
+                *entry.value_mut() = Arc::new(RwLock::new(copy));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ChunkColumn;
+    use crate::coords::ChunkCoords;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::{ColumnStore, NapiWorld};
+    use std::sync::Arc;
+
+    fn world_with_column() -> NapiWorld {
+        let world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(test_registry()), Arc::new(test_biomes()));
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, ChunkColumn::new());
+        world.set_block_state_id(1, 70, 1, 1).unwrap();
+        world
+    }
+
+    #[test]
+    fn forks_copy_columns_on_write() {
+        let world = world_with_column();
+        let fork = world.fork();
+        assert_eq!(fork.get_block_state_id(1, 70, 1), 1);
+
+        fork.set_block_state_id(1, 70, 1, 2).unwrap();
+        world.set_block_state_id(2, 70, 2, 1).unwrap();
+        assert_eq!((world.get_block_state_id(1, 70, 1), fork.get_block_state_id(1, 70, 1)), (1, 2));
+        assert_eq!((world.get_block_state_id(2, 70, 2), fork.get_block_state_id(2, 70, 2)), (1, 0));
+    }
+
+    #[test]
+    fn clones_keep_cold_columns_and_options() {
+        let world = world_with_column();
+        world.set_write_dedup(true);
+        world.set_raycast_acceleration(true);
+        world.compress_idle_columns(0); // Starts the column's idle time
+        assert_eq!(world.compress_idle_columns(0), 1);
+
+        let copy = world.deep_clone().unwrap();
+        assert_eq!(copy.get_block_state_id(1, 70, 1), 1);
+        assert!(copy.write_dedup.load(std::sync::atomic::Ordering::Relaxed));
+        assert!(copy.raycast_acceleration());
+    }
+}
@@ -0,0 +1,65 @@
+// src/cone.rs
+use crate::raycast::{RaycastResult, RaycastSettings, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+#[napi(object)]
+pub struct ConeRay {
+    pub direction: Vec3Arg, // Normalized
+    pub hit: Option<RaycastResult>,
+}
+
+// `count` unit directions spread evenly over the cap of the unit sphere within `half_angle` (radians) of `axis`,
+// along a Fibonacci spiral starting at the axis
+fn cone_directions(axis: DVec3, half_angle: f64, count: u32) -> impl Iterator<Item = DVec3> {
+    let (side, up) = axis.any_orthonormal_pair();
+    let golden_angle = std::f64::consts::PI * (3.0 - 5f64.sqrt());
+    let min_cos = half_angle.cos();
+    (0..count).map(move |i| {
+        let fraction = if count > 1 { i as f64 / (count - 1) as f64 } else { 0.0 };
+        // Uniform in solid angle: cos(theta) goes linearly from 1 (the axis) to the edge of the cone
+        let cos_theta = 1.0 - (1.0 - min_cos) * fraction;
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = golden_angle * i as f64;
+        axis * cos_theta + (side * phi.cos() + up * phi.sin()) * sin_theta
+    })
+}
+
+#[napi]
+impl NapiWorld {
+    /// Casts `rays` rays spread evenly within a cone around `direction`, whose half-angle is `angle` degrees,
+    /// e.g. for field-of-view checks, explosion shielding or rough area scans. The first ray goes straight along
+    /// `direction`, the last along the edge of the cone. Returns each ray's direction with its closest hit, if any,
+    /// in the order they were cast.
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, angle: number, rays: number, max_distance: number, intersect_non_solid_block_names?: string[] | null")]
+    pub fn cone_cast(
+        &self,
+        origin: Vec3Arg,
+        direction: Vec3Arg,
+        angle: f64,
+        rays: u32,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+    ) -> Result<Vec<ConeRay>> {
+        let axis = DVec3::from(direction).normalize_or_zero();
+        if axis == DVec3::ZERO {
+            return Err(napi::Error::new(napi::Status::InvalidArg, "Cone direction must not be zero".to_string()));
+        }
+        if !(0.0..=180.0).contains(&angle) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Cone angle must be between 0 and 180 degrees, got {}", angle),
+            ));
+        }
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, None, None, None)?;
+        let origin = DVec3::from(origin);
+        Ok(cone_directions(axis, angle.to_radians(), rays)
+            .map(|direction| ConeRay {
+                direction: direction.into(),
+                hit: self.cast_ray(origin, direction, &settings).map(RaycastResult::from),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,146 @@
+// src/config.rs
+use crate::coords::{ChunkCoords, MAX_CHUNK_Y, MIN_CHUNK_Y, SECTION_HEIGHT, WORLD_HEIGHT};
+use crate::world::{load_registries, ColumnStore, NapiWorld};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Construction-time settings of a world, for `World.withOptions`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct WorldOptions {
+    pub version: String,
+    pub min_y: Option<i32>,             // Bottom of the dimension (default -64), a multiple of 16
+    pub dimension_height: Option<u32>,  // Height of the dimension (default 384), a multiple of 16; must fit in -64..320
+    pub strict_parsing: Option<bool>,   // Reject chunk data that ends before every section was read
+    pub max_loaded_columns: Option<u32>, // Unload the farthest columns when loading more than this
+    pub emit_events: Option<bool>,      // Notify watchers and subscribers of block changes (default true)
+}
+
+// The settings a world was created with
+#[derive(Debug, Clone)]
+pub struct WorldConfig {
+    pub version: String,
+    pub min_y: i32,
+    pub height: i32,
+    pub strict_parsing: bool,
+    pub max_loaded_columns: Option<usize>,
+    pub emit_events: bool,
+}
+
+impl WorldConfig {
+    pub fn new(version: &str) -> Self {
+        WorldConfig {
+            version: version.to_string(),
+            min_y: MIN_CHUNK_Y,
+            height: WORLD_HEIGHT,
+            strict_parsing: false,
+            max_loaded_columns: None,
+            emit_events: true,
+        }
+    }
+
+    // Sets the dimension's Y range, which must be section aligned and fit in the range columns store
+    pub fn set_dimension(&mut self, min_y: i32, height: i32) -> Result<()> {
+        if min_y < MIN_CHUNK_Y || height <= 0 || min_y.saturating_add(height) > MAX_CHUNK_Y {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Dimension from y={} with height {} doesn't fit in {}..{}", min_y, height, MIN_CHUNK_Y, MAX_CHUNK_Y),
+            ));
+        }
+        if min_y % SECTION_HEIGHT != 0 || height % SECTION_HEIGHT != 0 {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Dimension from y={} with height {} isn't aligned to sections of {}", min_y, height, SECTION_HEIGHT),
+            ));
+        }
+        self.min_y = min_y;
+        self.height = height;
+        Ok(())
+    }
+
+    // Whether a block Y is inside the dimension
+    pub fn contains_y(&self, y: i32) -> bool {
+        (self.min_y..self.min_y + self.height).contains(&y)
+    }
+
+    // The section Ys of the dimension, the sections chunk packets carry
+    pub fn section_range(&self) -> Range<i32> {
+        self.min_y / SECTION_HEIGHT..(self.min_y + self.height) / SECTION_HEIGHT
+    }
+}
+
+impl NapiWorld {
+    // Makes room for a column about to be loaded at `coords` by unloading the farthest ones over the limit
+    pub(crate) fn enforce_column_limit(&self, coords: ChunkCoords) {
+        let Some(limit) = self.config.max_loaded_columns else { return; };
+        while self.columns.len() >= limit.max(1) {
+            let farthest = self
+                .columns
+                .iter()
+                .map(|entry| *entry.key())
+                .filter(|&other| other != coords)
+                .max_by_key(|other| (other.x - coords.x).pow(2) + (other.z - coords.z).pow(2));
+            let Some(farthest) = farthest else { return; };
+            self.remove_column(farthest);
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Creates a world from an options object collecting the construction-time settings: the version, the
+    /// dimension's Y range (within the -64..320 range columns store), strict chunk parsing, a cap on loaded
+    /// columns and whether block changes are reported to watchers and subscribers. `loadColumn` and `loadSection`
+    /// read chunk packets as carrying the dimension's sections only, and writes outside it are rejected.
+    #[napi(factory)]
+    pub fn with_options(options: WorldOptions) -> Result<Self> {
+        let mut config = WorldConfig::new(&options.version);
+        config.set_dimension(
+            options.min_y.unwrap_or(MIN_CHUNK_Y),
+            options.dimension_height.map_or(WORLD_HEIGHT, |height| height.min(i32::MAX as u32) as i32),
+        )?;
+        config.strict_parsing = options.strict_parsing.unwrap_or(false);
+        config.max_loaded_columns = options.max_loaded_columns.map(|limit| limit as usize);
+        config.emit_events = options.emit_events.unwrap_or(true);
+
+        let (registry, biomes) = load_registries(&options.version)?;
+        let mut world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(registry), Arc::new(biomes));
+        world.config = Arc::new(config);
+        Ok(world)
+    }
+
+    /// Version string the world was created for.
+    #[napi(getter)]
+    pub fn version(&self) -> String {
+        self.config.version.clone()
+    }
+
+    /// Lowest block Y of the dimension.
+    #[napi(getter)]
+    pub fn min_y(&self) -> i32 {
+        self.config.min_y
+    }
+
+    /// Height of the dimension in blocks.
+    #[napi(getter)]
+    pub fn dimension_height(&self) -> u32 {
+        self.config.height as u32
+    }
+
+    #[napi(getter)]
+    pub fn strict_parsing(&self) -> bool {
+        self.config.strict_parsing
+    }
+
+    #[napi(getter)]
+    pub fn max_loaded_columns(&self) -> Option<u32> {
+        self.config.max_loaded_columns.map(|limit| limit as u32)
+    }
+
+    #[napi(getter)]
+    pub fn emit_events(&self) -> bool {
+        self.config.emit_events
+    }
+}
@@ -2,6 +2,7 @@
 // Add Shr and BitAnd traits for the >> and & operators
 use std::ops::Shr;
 
+#[cfg(feature = "napi")]
 use napi_derive::napi; // Make sure these are imported
 
 pub const SECTION_WIDTH: i32 = 16;
@@ -13,9 +14,11 @@ pub const MAX_CHUNK_Y: i32 = MIN_CHUNK_Y + WORLD_HEIGHT;
 pub const SECTION_COUNT: usize = (WORLD_HEIGHT / SECTION_HEIGHT) as usize;
 pub const MIN_SECTION_Y: i32 = MIN_CHUNK_Y >> 4;
 pub const MAX_SECTION_Y: i32 = (MAX_CHUNK_Y >> 4) -1; // Inclusive max section index
+pub const SECTION_VOLUME: usize = (SECTION_WIDTH * SECTION_WIDTH * SECTION_HEIGHT) as usize;
+pub const BIOME_SECTION_VOLUME: usize = 4 * 4 * 4; // Biomes are stored per 4x4x4 cell
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[napi(object)] // Add napi object derive if passing this struct directly
+#[cfg_attr(feature = "napi", napi(object))] // Add napi object derive if passing this struct directly
 pub struct WorldCoords {
     pub x: i32,
     pub y: i32,
@@ -49,6 +52,18 @@ pub struct BiomeCoords {
     pub z: i32,
 }
 
+impl ChunkCoords {
+    // Packs the coordinates into one signed 64-bit key: x in the high 32 bits, z (two's complement) in the low
+    // 32 bits, i.e. `(BigInt(x) << 32n) | (BigInt(z) & 0xffffffffn)` in JS
+    pub fn key(self) -> i64 {
+        ((self.x as i64) << 32) | (self.z as u32 as i64)
+    }
+
+    pub fn from_key(key: i64) -> Self {
+        ChunkCoords { x: (key >> 32) as i32, z: key as i32 }
+    }
+}
+
 impl WorldCoords {
     pub fn to_chunk_coords(&self) -> ChunkCoords {
         ChunkCoords {
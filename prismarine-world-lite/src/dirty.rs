@@ -0,0 +1,67 @@
+// src/dirty.rs
+use crate::coords::{ChunkCoords, SectionCoords, WorldCoords, MAX_SECTION_Y, MIN_SECTION_Y};
+use crate::world::NapiWorld;
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct SectionPosition {
+    pub chunk_x: i32,
+    pub section_y: i32,
+    pub chunk_z: i32,
+}
+
+impl From<SectionCoords> for SectionPosition {
+    fn from(coords: SectionCoords) -> Self {
+        SectionPosition { chunk_x: coords.x, section_y: coords.y, chunk_z: coords.z }
+    }
+}
+
+impl NapiWorld {
+    // Marks the sections holding the positions dirty. Blocks on a section border also dirty the
+    // neighboring section, whose mesh shows (or culls) the face touching the changed block.
+    pub(crate) fn mark_blocks_dirty(&self, positions: &[WorldCoords]) {
+        let mut dirty = self.dirty_sections.lock().unwrap();
+        for pos in positions {
+            let section = pos.to_section_coords();
+            let rel = pos.to_section_rel_coords();
+            dirty.insert(section);
+
+            for (axis_rel, offset) in [(rel.x, [1, 0, 0]), (rel.y, [0, 1, 0]), (rel.z, [0, 0, 1])] {
+                let sign = match axis_rel { 0 => -1, 15 => 1, _ => continue };
+                let neighbor = SectionCoords {
+                    x: section.x + offset[0] * sign,
+                    y: section.y + offset[1] * sign,
+                    z: section.z + offset[2] * sign,
+                };
+                if (MIN_SECTION_Y..=MAX_SECTION_Y).contains(&neighbor.y) {
+                    dirty.insert(neighbor);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn mark_sections_dirty(&self, sections: impl IntoIterator<Item = SectionCoords>) {
+        self.dirty_sections.lock().unwrap().extend(sections);
+    }
+
+    pub(crate) fn mark_column_dirty(&self, chunk: ChunkCoords) {
+        self.mark_sections_dirty((MIN_SECTION_Y..=MAX_SECTION_Y).map(|y| SectionCoords { x: chunk.x, y, z: chunk.z }));
+    }
+
+    // Unloaded columns have nothing left to rebuild
+    pub(crate) fn forget_dirty_column(&self, chunk: ChunkCoords) {
+        self.dirty_sections.lock().unwrap().retain(|s| s.x != chunk.x || s.z != chunk.z);
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Returns every section changed since the last call (block, biome or light writes, or loading its column)
+    /// and clears the list. Renderers can call this once per frame to know exactly which meshes to rebuild.
+    #[napi]
+    pub fn take_dirty_sections(&self) -> Vec<SectionPosition> {
+        let dirty = std::mem::take(&mut *self.dirty_sections.lock().unwrap());
+        dirty.into_iter().map(SectionPosition::from).collect()
+    }
+}
@@ -0,0 +1,119 @@
+// src/entities.rs
+use crate::raycast::{inside_hit, intersect_aabb, BlockFace, RaycastSettings, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::{DMat3, DVec3};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// An entity to test rays against. Its hitbox is `width` wide on X and Z, centered on `position`, and `height` tall
+/// from there up.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EntityHitbox {
+    pub id: i32,
+    pub position: Vec3Arg,        // Feet position
+    pub name: Option<String>,     // Entity type, used for the hitbox size when `width`/`height` aren't given
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub yaw: Option<f64>,         // Degrees, Minecraft convention; turns the hitbox into a box rotated around Y
+}
+
+#[napi(object)]
+#[derive(Debug)]
+pub struct EntityRaycastResult {
+    pub id: i32,                  // `id` of the entity that was hit
+    pub intersect_point: Vec3Arg,
+    pub distance: f64,
+    pub face: u32,                // BlockFace of the hitbox side that was hit (the closest axis for rotated boxes)
+    pub entered_inside: bool,     // True if the origin was inside the hitbox (the hit is at the origin)
+}
+
+// Distance along the ray to an entity's hitbox, with the face hit and whether the origin was inside
+fn intersect_entity(entity: &EntityHitbox, width: f64, height: f64, origin: DVec3, direction: DVec3) -> Option<(f64, BlockFace, bool)> {
+    let feet = DVec3::from(entity.position);
+    let half = DVec3::new(width / 2.0, 0.0, width / 2.0);
+    let yaw = entity.yaw.unwrap_or(0.0).to_radians();
+
+    if yaw == 0.0 {
+        let (t, face) = intersect_aabb(feet - half, feet + half + DVec3::Y * height, origin, direction.recip())?;
+        return Some(inside_hit(t, face, direction));
+    }
+
+    // Test in the entity's frame, where the box is axis-aligned. Minecraft yaw turns +Z towards -X, the opposite
+    // of a positive rotation around Y.
+    let to_world = DMat3::from_rotation_y(-yaw);
+    let to_local = to_world.transpose();
+    let local_origin = to_local * (origin - feet);
+    let local_direction = to_local * direction;
+    let (t, face) = intersect_aabb(-half, half + DVec3::Y * height, local_origin, local_direction.recip())?;
+    let (t, face, inside) = inside_hit(t, face, local_direction);
+    Some((t, BlockFace::from_vector(to_world * face.normal()), inside))
+}
+
+impl NapiWorld {
+    // Every entity hitbox the ray (with a normalized direction) hits within `reach`, as (distance, face, whether the
+    // origin is inside, entity id)
+    pub(crate) fn entity_hits(&self, origin: DVec3, direction: DVec3, reach: f64, entities: &[EntityHitbox]) -> Result<Vec<(f64, BlockFace, bool, i32)>> {
+        let mut hits = Vec::new();
+        for entity in entities {
+            let size = match (entity.width, entity.height) {
+                (Some(width), Some(height)) => Some((width, height)),
+                (width, height) => entity.name.as_deref().and_then(|name| self.registry.entity_size(name))
+                    .map(|(w, h)| (width.unwrap_or(w), height.unwrap_or(h))),
+            };
+            let Some((width, height)) = size else {
+                return Err(napi::Error::new(
+                    napi::Status::InvalidArg,
+                    format!("No hitbox size for entity {} ({})", entity.id, entity.name.as_deref().unwrap_or("no name")),
+                ));
+            };
+
+            if let Some((t, face, inside)) = intersect_entity(entity, width, height, origin, direction) {
+                if t <= reach {
+                    hits.push((t, face, inside, entity.id));
+                }
+            }
+        }
+        Ok(hits)
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Casts a ray against entity hitboxes and returns the nearest entity hit within `max_distance`, or `null`.
+    /// Hitbox sizes come from `width`/`height`, else from minecraft-data by `name`; entities with `yaw` set are
+    /// tested as boxes rotated to it. Unless `ignore_blocks` is set, entities behind the first block the ray hits
+    /// don't count, like on the server.
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, entities: EntityHitbox[], ignore_blocks?: boolean | null")]
+    pub fn raycast_entities(
+        &self,
+        origin: Vec3Arg,
+        direction: Vec3Arg,
+        max_distance: f64,
+        entities: Vec<EntityHitbox>,
+        ignore_blocks: Option<bool>,
+    ) -> Result<Option<EntityRaycastResult>> {
+        let origin = DVec3::from(origin);
+        let direction = DVec3::from(direction).normalize_or_zero();
+        if direction == DVec3::ZERO { return Ok(None); }
+
+        let mut reach = max_distance;
+        if !ignore_blocks.unwrap_or(false) {
+            let settings = RaycastSettings::from_args(max_distance, None, None, None, None)?;
+            if let Some(block_hit) = self.cast_ray(origin, direction, &settings) {
+                reach = reach.min(block_hit.t);
+            }
+        }
+
+        let closest = self.entity_hits(origin, direction, reach, &entities)?
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(closest.map(|(t, face, inside, id)| EntityRaycastResult {
+            id,
+            intersect_point: (origin + direction * t).into(),
+            distance: t,
+            face: face as u32,
+            entered_inside: inside,
+        }))
+    }
+}
@@ -0,0 +1,220 @@
+// src/events.rs
+use crate::coords::WorldCoords;
+use crate::raycast::Vec3Arg;
+use crate::watchers::BlockUpdate;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+use std::sync::atomic::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// An inclusive range of chunk coordinates.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkRange {
+    pub min_x: i32,
+    pub min_z: i32,
+    pub max_x: i32,
+    pub max_z: i32,
+}
+
+impl ChunkRange {
+    fn contains(&self, pos: WorldCoords) -> bool {
+        let (chunk_x, chunk_z) = (pos.x >> 4, pos.z >> 4);
+        (self.min_x.min(self.max_x)..=self.min_x.max(self.max_x)).contains(&chunk_x)
+            && (self.min_z.min(self.max_z)..=self.min_z.max(self.max_z)).contains(&chunk_z)
+    }
+}
+
+// Horizontal area around a movable point (e.g. the player) outside of which no events are sent
+#[derive(Debug, Clone, Copy)]
+pub struct EventCenter {
+    x: f64,
+    z: f64,
+    radius: f64,
+}
+
+impl EventCenter {
+    fn contains(&self, pos: WorldCoords) -> bool {
+        let dx = pos.x as f64 + 0.5 - self.x;
+        let dz = pos.z as f64 + 0.5 - self.z;
+        dx * dx + dz * dz <= self.radius * self.radius
+    }
+}
+
+#[derive(Default)]
+struct ChannelState {
+    pending: Vec<BlockUpdate>,
+    // Index in `pending` of each queued position, kept while updates are coalesced (see `set_write_dedup`)
+    queued: HashMap<(i32, i32, i32), usize>,
+    scheduled: bool, // A delivery is queued on the JS thread
+}
+
+// Coalesces block updates for one JS callback. Updates queued before the JS thread gets around to a
+// delivery are all passed in that one call, so a large rewrite costs one callback per macrotask
+// instead of one per block.
+pub struct UpdateChannel {
+    state: Arc<Mutex<ChannelState>>,
+    callback: ThreadsafeFunction<(), ErrorStrategy::Fatal>,
+}
+
+impl UpdateChannel {
+    pub fn new(env: Env, callback: JsFunction) -> Result<Self> {
+        let state = Arc::new(Mutex::new(ChannelState::default()));
+        let delivery_state = state.clone();
+        let mut callback: ThreadsafeFunction<(), ErrorStrategy::Fatal> = callback.create_threadsafe_function(0, move |_ctx| {
+            let mut state = delivery_state.lock().unwrap();
+            state.scheduled = false;
+            state.queued.clear();
+            Ok(vec![std::mem::take(&mut state.pending)])
+        })?;
+        // Listeners shouldn't keep the process alive on their own
+        callback.unref(&env)?;
+        Ok(UpdateChannel { state, callback })
+    }
+
+    // Queues updates, scheduling a delivery unless one is pending or events are held. With `coalesce`, an update
+    // for a position that's already queued replaces the queued one.
+    pub fn push(&self, updates: Vec<BlockUpdate>, held: bool, coalesce: bool) {
+        let mut state = self.state.lock().unwrap();
+        if coalesce {
+            for update in updates {
+                let next = state.pending.len();
+                let index = *state.queued.entry((update.x, update.y, update.z)).or_insert(next);
+                if index == next {
+                    state.pending.push(update);
+                } else {
+                    state.pending[index] = update;
+                }
+            }
+        } else {
+            state.queued.clear(); // Positions may repeat from here on
+            state.pending.extend(updates);
+        }
+        if !held {
+            self.schedule(&mut state);
+        }
+    }
+
+    pub fn flush(&self) {
+        self.schedule(&mut self.state.lock().unwrap());
+    }
+
+    // Only called with a non-empty queue, so deliveries never carry an empty array
+    fn schedule(&self, state: &mut ChannelState) {
+        if !state.scheduled && !state.pending.is_empty() {
+            state.scheduled = true;
+            self.callback.call((), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+}
+
+pub struct BlockUpdateSubscriber {
+    chunk_ranges: Vec<ChunkRange>,
+    channel: UpdateChannel,
+}
+
+impl BlockUpdateSubscriber {
+    fn accepts(&self, pos: WorldCoords) -> bool {
+        self.chunk_ranges.is_empty() || self.chunk_ranges.iter().any(|range| range.contains(pos))
+    }
+}
+
+impl NapiWorld {
+    // Sends the changed blocks that pass the event center and each subscriber's chunk ranges
+    pub(crate) fn emit_block_updates(&self, positions: &[WorldCoords]) {
+        if self.subscribers.is_empty() { return; }
+
+        let center = *self.event_center.read().unwrap();
+        let updates: Vec<BlockUpdate> = positions
+            .iter()
+            .filter(|pos| center.is_none_or(|c| c.contains(**pos)))
+            .map(|pos| BlockUpdate { x: pos.x, y: pos.y, z: pos.z, state_id: self.get_block_state_id(pos.x, pos.y, pos.z) })
+            .collect();
+        if updates.is_empty() { return; }
+
+        let (held, coalesce) = (self.events_held(), self.write_dedup.load(Ordering::Relaxed));
+        for subscriber in self.subscribers.iter() {
+            let accepted: Vec<BlockUpdate> = updates
+                .iter()
+                .filter(|u| subscriber.accepts(WorldCoords { x: u.x, y: u.y, z: u.z }))
+                .copied()
+                .collect();
+            if !accepted.is_empty() {
+                subscriber.channel.push(accepted, held, coalesce);
+            }
+        }
+    }
+
+    pub(crate) fn events_held(&self) -> bool {
+        self.event_holds.load(Ordering::Acquire) > 0
+    }
+
+    // Holds back event delivery until the matching `release_events`; holds nest
+    pub(crate) fn hold_events(&self) {
+        self.event_holds.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn release_events(&self) {
+        let released = self.event_holds.fetch_update(Ordering::AcqRel, Ordering::Acquire, |holds| holds.checked_sub(1));
+        if released == Ok(1) {
+            self.flush_events();
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Subscribes to block updates, optionally only those inside the given chunk ranges.
+    /// Updates outside the event center (see `set_event_center`) are never sent. Returns a subscription ID.
+    #[napi(ts_args_type = "callback: (updates: BlockUpdate[]) => void, chunkRanges?: ChunkRange[] | null")]
+    pub fn on_block_update(&self, env: Env, callback: JsFunction, chunk_ranges: Option<Vec<ChunkRange>>) -> Result<u32> {
+        let channel = UpdateChannel::new(env, callback)?;
+        let id = self.next_watcher_id.fetch_add(1, Ordering::Relaxed);
+        self.subscribers.insert(id, BlockUpdateSubscriber { chunk_ranges: chunk_ranges.unwrap_or_default(), channel });
+        Ok(id)
+    }
+
+    /// Removes a block update subscription. Returns false if no subscription had this ID.
+    #[napi]
+    pub fn off_block_update(&self, subscription_id: u32) -> bool {
+        self.subscribers.remove(&subscription_id).is_some()
+    }
+
+    /// Only sends block update events within `radius` blocks (horizontally) of `center`, for every subscriber.
+    /// Call again as the center moves, or with no arguments to send updates from everywhere again.
+    #[napi(ts_args_type = "center?: { x: number, y: number, z: number } | null, radius?: number | null")]
+    pub fn set_event_center(&self, center: Option<Vec3Arg>, radius: Option<f64>) {
+        *self.event_center.write().unwrap() = match (center, radius) {
+            (Some(center), Some(radius)) => Some(EventCenter { x: center.x, z: center.z, radius }),
+            _ => None,
+        };
+    }
+
+    /// Pauses block update delivery to watchers and subscribers; updates queue up until `resume_events` or `flush_events`.
+    #[napi]
+    pub fn pause_events(&self) {
+        self.hold_events();
+    }
+
+    /// Undoes one `pause_events`, delivering everything queued once no pause is left.
+    #[napi]
+    pub fn resume_events(&self) {
+        self.release_events();
+    }
+
+    /// Delivers all queued block updates now, even while paused, instead of at the next macrotask.
+    /// Each watcher and subscriber receives its updates as a single array.
+    #[napi]
+    pub fn flush_events(&self) {
+        for watcher in self.watchers.iter() {
+            watcher.channel.flush();
+        }
+        for subscriber in self.subscribers.iter() {
+            subscriber.channel.flush();
+        }
+    }
+}
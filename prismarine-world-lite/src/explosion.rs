@@ -0,0 +1,55 @@
+// src/explosion.rs
+use crate::raycast::{AabbArg, RaycastSettings, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi_derive::napi;
+
+#[napi]
+impl NapiWorld {
+    /// Calculates how exposed a bounding box is to an explosion, matching vanilla's `Explosion.getSeenPercent`.
+    ///
+    /// Rays are cast from a grid of points across the box towards the explosion; the result is the
+    /// fraction (0 to 1) of rays that reach it without hitting a block. Damage scales linearly with this value.
+    #[napi(ts_args_type = "explosion_pos: { x: number, y: number, z: number }, target_aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }")]
+    pub fn get_explosion_exposure(&self, explosion_pos: Vec3Arg, target_aabb: AabbArg) -> f64 {
+        let explosion = DVec3::from(explosion_pos);
+        let min = DVec3::from(target_aabb.min);
+        let max = DVec3::from(target_aabb.max);
+
+        // Grid spacing, one sample per half block along each axis
+        let step = DVec3::ONE / ((max - min) * 2.0 + DVec3::ONE);
+        if step.min_element() < 0.0 { return 0.0; }
+
+        // Vanilla centers the grid horizontally but not vertically
+        let offset_x = (1.0 - (1.0 / step.x).floor() * step.x) / 2.0;
+        let offset_z = (1.0 - (1.0 / step.z).floor() * step.z) / 2.0;
+
+        let mut unobstructed = 0u32;
+        let mut total = 0u32;
+        let mut fx = 0.0;
+        while fx <= 1.0 {
+            let mut fy = 0.0;
+            while fy <= 1.0 {
+                let mut fz = 0.0;
+                while fz <= 1.0 {
+                    let sample = DVec3::new(
+                        min.x + (max.x - min.x) * fx + offset_x,
+                        min.y + (max.y - min.y) * fy,
+                        min.z + (max.z - min.z) * fz + offset_z,
+                    );
+                    let to_explosion = explosion - sample;
+                    let settings = RaycastSettings::new(to_explosion.length());
+                    if self.cast_ray(sample, to_explosion, &settings).is_none() {
+                        unobstructed += 1;
+                    }
+                    total += 1;
+                    fz += step.z;
+                }
+                fy += step.y;
+            }
+            fx += step.x;
+        }
+
+        if total == 0 { 0.0 } else { unobstructed as f64 / total as f64 }
+    }
+}
@@ -0,0 +1,41 @@
+// src/faces.rs
+use crate::coords::WorldCoords;
+use crate::raycast::BlockFace;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// The BlockFace tables as free functions, for scripts building on raycast hits, placement and neighbor results
+
+/// Unit offset to the neighbor on the given side of a block, e.g. `{ x: 0, y: 1, z: 0 }` for `Top`.
+#[napi]
+pub fn face_to_offset(face: BlockFace) -> WorldCoords {
+    face.offset()
+}
+
+/// The position `distance` blocks (1 by default) out through the given face of `pos`, e.g. the block a
+/// placement against a raycast hit's face lands in.
+#[napi(ts_args_type = "pos: { x: number, y: number, z: number }, face: BlockFace, distance?: number | null")]
+pub fn offset_position(pos: WorldCoords, face: BlockFace, distance: Option<i32>) -> WorldCoords {
+    face.offset_position(pos, distance.unwrap_or(1))
+}
+
+/// The face on the other side of a block: `Top` for `Bottom`, `West` for `East` and so on.
+#[napi]
+pub fn opposite_face(face: BlockFace) -> BlockFace {
+    face.opposite()
+}
+
+/// The face whose normal points closest to the given vector, e.g. the side of a block facing a viewer when
+/// passed the direction to them. Ties go to the X faces, then the Y faces.
+#[napi]
+pub fn face_from_vector(dx: f64, dy: f64, dz: f64) -> Result<BlockFace> {
+    let vector = DVec3::new(dx, dy, dz);
+    if !vector.is_finite() || vector == DVec3::ZERO {
+        return Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Cannot pick a face for vector ({}, {}, {})", dx, dy, dz),
+        ));
+    }
+    Ok(BlockFace::from_vector(vector))
+}
@@ -0,0 +1,75 @@
+// src/farming.rs
+use crate::coords::WorldCoords;
+use crate::region::Region;
+use crate::world::NapiWorld;
+use napi_derive::napi;
+use std::collections::{HashMap, HashSet};
+
+// Crops harvested by breaking or using them once their `age` is at its maximum. Melon and pumpkin stems aren't
+// listed: their fruit is what gets harvested, and it doesn't age.
+const AGED_CROPS: &[&str] = &[
+    "wheat", "carrots", "potatoes", "beetroots", "nether_wart", "cocoa", "sweet_berry_bush", "pitcher_crop",
+    "torchflower_crop",
+];
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct HarvestableCrop {
+    pub position: WorldCoords,
+    pub crop: String, // Block name, e.g. "wheat"
+    pub state_id: u32,
+}
+
+impl NapiWorld {
+    // State IDs of fully grown crops, with the crop's block name
+    fn mature_crop_states(&self) -> HashMap<u32, &str> {
+        let mut mature = HashMap::new();
+        for &name in AGED_CROPS {
+            let Some(block) = self.registry.block_by_name(name) else { continue; };
+            let Some(age) = block.states.iter().flatten().find(|state| state.name == "age") else { continue; };
+            let max_age = (age.num_values.max(1) - 1).to_string();
+
+            let min_state_id = block.min_state_id.unwrap_or(block.id << 4);
+            for state_id in min_state_id..=block.max_state_id.unwrap_or(min_state_id + 15) {
+                if self.registry.state_property(state_id, "age").as_deref() == Some(max_age.as_str()) {
+                    mature.insert(state_id, name);
+                }
+            }
+        }
+        mature
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Finds the fully grown crops in the region (wheat, carrots, potatoes, beetroots, nether wart, cocoa, sweet
+    /// berries, pitcher and torchflower crops): those whose `age` property is at its maximum. Sections without any
+    /// grown crop are skipped without reading their blocks. Unloaded chunks are left out.
+    #[napi]
+    pub fn find_harvestable_crops(&self, region: &Region) -> Vec<HarvestableCrop> {
+        let mature = self.mature_crop_states();
+        let mature_ids: HashSet<u32> = mature.keys().copied().collect();
+        let mut crops = Vec::new();
+
+        self.with_region_columns(region, |column, part| {
+            let (min, max) = (part.min(), part.max());
+            for section_y in min.y >> 4..=max.y >> 4 {
+                let Some(section) = column.get_section(section_y) else { continue; };
+                if !section.block_states().may_contain_any(&mature_ids) { continue; }
+
+                for y in (section_y << 4).max(min.y)..=((section_y << 4) + 15).min(max.y) {
+                    for z in min.z..=max.z {
+                        for x in min.x..=max.x {
+                            let position = WorldCoords { x, y, z };
+                            let state_id = column.get_block_state_id(position);
+                            if let Some(&crop) = mature.get(&state_id) {
+                                crops.push(HarvestableCrop { position, crop: crop.to_string(), state_id });
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        crops
+    }
+}
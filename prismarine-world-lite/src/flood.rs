@@ -0,0 +1,150 @@
+// src/flood.rs
+use crate::coords::{WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::{HashSet, VecDeque};
+
+// Face neighbors, then the edge and corner neighbors `diagonal` fills also step to
+const FACE_OFFSETS: [(i32, i32, i32); 6] = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+
+fn all_offsets(diagonal: bool) -> Vec<(i32, i32, i32)> {
+    if !diagonal { return FACE_OFFSETS.to_vec(); }
+    (-1..=1)
+        .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+        .filter(|&offset| offset != (0, 0, 0))
+        .collect()
+}
+
+/// The blocks making up a tree found by `detect_tree`
+#[napi(object)]
+#[derive(Debug, Default)]
+pub struct TreeBlocks {
+    pub logs: Vec<WorldCoords>,
+    pub leaves: Vec<WorldCoords>,
+}
+
+// Logs and nether stems as they grow in trees and huge fungi, with their all-bark forms; stripped ones only come from
+// players. Melon and pumpkin stems are crops.
+fn is_tree_log(name: &str) -> bool {
+    let log = name.ends_with("_log")
+        || name.ends_with("_wood")
+        || matches!(name, "crimson_stem" | "warped_stem" | "crimson_hyphae" | "warped_hyphae");
+    log && !name.starts_with("stripped_")
+}
+
+impl NapiWorld {
+    // Breadth-first fill from `seeds` (which must already be accepted), stepping to a neighbor when
+    // `accept(from_state, neighbor_state)` allows it. Blocks outside the world or in unloaded columns are never
+    // entered. Stops after `max_blocks` blocks; returns the blocks in visiting order with their states.
+    pub(crate) fn flood_fill(
+        &self,
+        seeds: &[WorldCoords],
+        max_blocks: usize,
+        diagonal: bool,
+        mut accept: impl FnMut(u32, u32) -> bool,
+    ) -> Vec<(WorldCoords, u32)> {
+        let offsets = all_offsets(diagonal);
+        let mut seen: HashSet<WorldCoords> = seeds.iter().copied().collect();
+        let mut queue: VecDeque<(WorldCoords, u32)> = seeds
+            .iter()
+            .map(|&pos| (pos, self.get_block_state_id(pos.x, pos.y, pos.z)))
+            .collect();
+        let mut filled = Vec::new();
+
+        while let Some((pos, state_id)) = queue.pop_front() {
+            if filled.len() >= max_blocks { break; }
+            filled.push((pos, state_id));
+
+            for &(dx, dy, dz) in &offsets {
+                let neighbor = WorldCoords { x: pos.x + dx, y: pos.y + dy, z: pos.z + dz };
+                if neighbor.y < MIN_CHUNK_Y || neighbor.y >= MAX_CHUNK_Y || seen.contains(&neighbor) { continue; }
+                if !self.ensure_column(neighbor.to_chunk_coords()) { continue; }
+                let neighbor_state = self.get_block_state_id(neighbor.x, neighbor.y, neighbor.z);
+                if accept(state_id, neighbor_state) {
+                    seen.insert(neighbor);
+                    queue.push_back((neighbor, neighbor_state));
+                }
+            }
+        }
+        filled
+    }
+
+    // Distance of a naturally grown leaf block from the nearest log (1-7), or None for other blocks and leaves
+    // placed by players
+    fn natural_leaf_distance(&self, state_id: u32) -> Option<u32> {
+        let block = self.registry.block(state_id)?;
+        if !block.name.ends_with("_leaves") || self.registry.state_property(state_id, "persistent").as_deref() == Some("true") {
+            return None;
+        }
+        self.registry.state_property(state_id, "distance")?.parse().ok()
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Collects the connected region of blocks matching `blocks` (state IDs, block names or `#` block tags) that
+    /// contains `start`, e.g. a lava lake or a vein of ore. Blocks connect through faces, or also through edges and corners with
+    /// `diagonal`. At most `max_blocks` are returned, nearest (in steps) first. Returns an empty list if the start
+    /// block doesn't match or isn't loaded.
+    #[napi(ts_args_type = "start: WorldCoords, blocks: Array<number | string>, max_blocks: number, diagonal?: boolean | null")]
+    pub fn flood_fill_blocks(
+        &self,
+        start: WorldCoords,
+        blocks: Vec<Either<u32, String>>,
+        max_blocks: u32,
+        diagonal: Option<bool>,
+    ) -> Result<Vec<WorldCoords>> {
+        let matcher = self.block_matcher(blocks)?;
+        if !self.ensure_column(start.to_chunk_coords()) || !matcher.matches(self.get_block_state_id(start.x, start.y, start.z)) {
+            return Ok(Vec::new());
+        }
+        let filled = self.flood_fill(&[start], max_blocks as usize, diagonal.unwrap_or(false), |_, state_id| matcher.matches(state_id));
+        Ok(filled.into_iter().map(|(pos, _)| pos).collect())
+    }
+
+    /// Finds the tree a log belongs to: its logs (connected through faces, edges and corners, so branches count)
+    /// and the naturally grown leaves held by them. Leaves are followed outwards only while their `distance`
+    /// grows by one per block, like the game's leaf decay, so leaves of neighboring trees and player-placed leaves
+    /// are left out. At most `max_blocks` (default 4096) blocks are collected. Returns `null` if there's no log
+    /// at the position.
+    #[napi]
+    pub fn detect_tree(&self, log_pos: WorldCoords, max_blocks: Option<u32>) -> Option<TreeBlocks> {
+        let max_blocks = max_blocks.unwrap_or(4096) as usize;
+        let is_log = |state_id: u32| self.registry.block(state_id).is_some_and(|b| is_tree_log(&b.name));
+        if !self.ensure_column(log_pos.to_chunk_coords()) || !is_log(self.get_block_state_id(log_pos.x, log_pos.y, log_pos.z)) {
+            return None;
+        }
+
+        let logs: Vec<WorldCoords> = self
+            .flood_fill(&[log_pos], max_blocks, true, |_, state_id| is_log(state_id))
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect();
+        let leaves = self
+            .flood_fill(&logs, max_blocks, false, |from_state, state_id| {
+                let from_distance = if is_log(from_state) { Some(0) } else { self.natural_leaf_distance(from_state) };
+                matches!((from_distance, self.natural_leaf_distance(state_id)), (Some(from), Some(to)) if to == from + 1)
+            })
+            .into_iter()
+            .skip(logs.len())
+            .map(|(pos, _)| pos)
+            .collect();
+        Some(TreeBlocks { logs, leaves })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_logs() {
+        for name in ["oak_log", "dark_oak_wood", "crimson_stem", "warped_hyphae", "mangrove_log"] {
+            assert!(is_tree_log(name), "{}", name);
+        }
+        for name in ["melon_stem", "attached_pumpkin_stem", "stripped_oak_log", "stripped_warped_stem", "mushroom_stem", "oak_planks"] {
+            assert!(!is_tree_log(name), "{}", name);
+        }
+    }
+}
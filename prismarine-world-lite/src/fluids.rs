@@ -0,0 +1,127 @@
+// src/fluids.rs
+use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::raycast::AabbArg;
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// Blocks that are always filled with water, regardless of their properties
+const ALWAYS_WATERLOGGED: &[&str] = &["kelp", "kelp_plant", "seagrass", "tall_seagrass", "bubble_column"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluidKind {
+    Water,
+    Lava,
+}
+
+impl FluidKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            FluidKind::Water => "water",
+            FluidKind::Lava => "lava",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "water" => Some(FluidKind::Water),
+            "lava" => Some(FluidKind::Lava),
+            _ => None,
+        }
+    }
+}
+
+// The fluid in a block state and the height of its surface within the block (0 to 1)
+pub fn fluid_at_state(registry: &BlockRegistry, state_id: u32) -> Option<(FluidKind, f64)> {
+    let block = registry.block(state_id)?;
+    let kind = match block.name.as_str() {
+        "water" => FluidKind::Water,
+        "lava" => FluidKind::Lava,
+        name if ALWAYS_WATERLOGGED.contains(&name) => return Some((FluidKind::Water, 1.0)),
+        _ => {
+            // Waterlogged blocks hold a water source
+            return (registry.state_property(state_id, "waterlogged").as_deref() == Some("true"))
+                .then_some((FluidKind::Water, 8.0 / 9.0));
+        }
+    };
+
+    // Level 0 is a source, 1-7 flow away from it and 8+ is falling fluid
+    let level = registry
+        .state_property(state_id, "level")
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(0);
+    let height = if level >= 8 { 1.0 } else { (8 - level) as f64 / 9.0 };
+    Some((kind, height))
+}
+
+#[napi]
+impl NapiWorld {
+    /// Finds the surface of the highest water or lava column at the given X/Z position.
+    /// Returns `null` if the chunk isn't loaded or there's no fluid in the column.
+    #[napi]
+    pub fn get_fluid_level(&self, x: i32, z: i32) -> Option<FluidLevel> {
+        let chunk_coords = WorldCoords { x, y: 0, z }.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().try_read().ok()?;
+
+        for y in (MIN_CHUNK_Y..MAX_CHUNK_Y).rev() {
+            let state_id = column.get_block_state_id(WorldCoords { x, y, z });
+            if state_id == 0 { continue; }
+            if let Some((kind, height)) = fluid_at_state(&self.registry, state_id) {
+                // Fluid with more of the same on top fills the whole block
+                let above = column.get_block_state_id(WorldCoords { x, y: y + 1, z });
+                let full = fluid_at_state(&self.registry, above).is_some_and(|(k, _)| k == kind);
+                return Some(FluidLevel {
+                    block_y: y,
+                    surface_y: y as f64 + if full { 1.0 } else { height },
+                    fluid: kind.name().to_string(),
+                });
+            }
+        }
+        None
+    }
+
+    /// Checks whether any part of the bounding box is inside a fluid, optionally only `'water'` or `'lava'`.
+    #[napi(ts_args_type = "aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }, fluid?: 'water' | 'lava' | null")]
+    pub fn is_submerged(&self, aabb: AabbArg, fluid: Option<String>) -> Result<bool> {
+        let only = match fluid.as_deref() {
+            None => None,
+            Some(name) => Some(FluidKind::from_name(name).ok_or_else(|| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown fluid: {}", name),
+            ))?),
+        };
+
+        let min = WorldCoords { x: aabb.min.x.floor() as i32, y: aabb.min.y.floor() as i32, z: aabb.min.z.floor() as i32 };
+        let max = WorldCoords { x: aabb.max.x.floor() as i32, y: aabb.max.y.floor() as i32, z: aabb.max.z.floor() as i32 };
+
+        for x in min.x..=max.x {
+            for z in min.z..=max.z {
+                let chunk = ChunkCoords { x: x >> 4, z: z >> 4 };
+                self.ensure_column(chunk);
+                let Some(entry) = self.columns.get(&chunk) else { continue; };
+                let Ok(column) = entry.value().try_read() else { continue; };
+                for y in min.y..=max.y {
+                    let state_id = column.get_block_state_id(WorldCoords { x, y, z });
+                    if state_id == 0 { continue; }
+                    if let Some((kind, height)) = fluid_at_state(&self.registry, state_id) {
+                        if only.is_none_or(|k| k == kind) && aabb.min.y < y as f64 + height {
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+// Fluid surface found by `get_fluid_level`
+#[napi(object)]
+pub struct FluidLevel {
+    pub block_y: i32,    // Y of the topmost fluid block
+    pub surface_y: f64,  // Exact height of the fluid surface
+    pub fluid: String,   // "water" or "lava"
+}
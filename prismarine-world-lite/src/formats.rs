@@ -0,0 +1,370 @@
+// src/formats.rs
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::coords::{ChunkCoords, BIOME_SECTION_VOLUME, MAX_SECTION_Y, SECTION_COUNT, SECTION_VOLUME};
+use crate::palette::{read_length, read_long_array, read_varint};
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use byteorder::{BigEndian, ReadBytesExt};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::io::{Cursor, Error, ErrorKind};
+
+// Layouts of the section data in chunk packets:
+//   Modern (1.18+): i16 block count, block states container, biomes container; every section of the column is sent
+//   Padded (1.16-1.17): i16 block count, u8 bits, palette (bits <= 8 only), data longs; biomes are sent elsewhere
+//   Compact (1.14-1.15): same as Padded, but values are packed across long boundaries instead of padding each long
+//   Lit (1.13): no block count; u8 bits, palette, data longs packed like Compact, then the section's block light and,
+//     in dimensions with a sky, its sky light (2048 bytes each)
+// Sections of the older layouts are only sent where the packet's bit mask says so. Before 1.15, full chunks end with
+// their biomes (256 i32s) after the sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkFormat {
+    Modern,
+    Padded,
+    Compact,
+    Lit { sky_light: bool },
+}
+
+impl ChunkFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            ChunkFormat::Modern => "1.18",
+            ChunkFormat::Padded => "1.16",
+            ChunkFormat::Compact => "1.14",
+            ChunkFormat::Lit { .. } => "1.13",
+        }
+    }
+
+    // Bytes of light following each section's blocks
+    fn light_bytes(self) -> usize {
+        match self {
+            ChunkFormat::Lit { sky_light: true } => 2 * LIGHT_BYTES,
+            ChunkFormat::Lit { sky_light: false } => LIGHT_BYTES,
+            _ => 0,
+        }
+    }
+}
+
+const LIGHT_BYTES: usize = SECTION_VOLUME / 2;
+const LEGACY_BIOME_BYTES: usize = 256 * 4;
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+fn padded_longs(bits: usize, capacity: usize) -> usize {
+    capacity.div_ceil(64 / bits)
+}
+
+fn compact_longs(bits: usize, capacity: usize) -> usize {
+    (capacity * bits).div_ceil(64)
+}
+
+// Skips `count` bytes, failing if the buffer is too short
+fn skip_bytes(cursor: &mut Cursor<&[u8]>, count: usize) -> std::io::Result<()> {
+    let end = cursor.position() + count as u64;
+    if end > cursor.get_ref().len() as u64 { return Err(Error::new(ErrorKind::UnexpectedEof, "Data array past end of buffer")); }
+    cursor.set_position(end);
+    Ok(())
+}
+
+fn skip_palette(cursor: &mut Cursor<&[u8]>, bits: usize) -> std::io::Result<()> {
+    let palette_len = read_varint(cursor)?;
+    if palette_len < 1 || palette_len as usize > 1 << bits { return Err(invalid("Palette length doesn't fit the bits")); }
+    for _ in 0..palette_len {
+        read_varint(cursor)?;
+    }
+    Ok(())
+}
+
+// Walks a 1.18+ paletted container, checking its data length matches its bits
+fn check_modern_container(cursor: &mut Cursor<&[u8]>, capacity: usize, max_indirect_bits: usize) -> std::io::Result<()> {
+    let bits = cursor.read_u8()? as usize;
+    if bits == 0 {
+        read_varint(cursor)?;
+        if read_varint(cursor)? != 0 { return Err(invalid("Single value container with data")); }
+        return Ok(());
+    }
+    if bits > 32 { return Err(invalid("Too many bits per value")); }
+    if bits <= max_indirect_bits { skip_palette(cursor, bits)?; }
+    let longs = read_varint(cursor)? as usize;
+    if longs != padded_longs(bits, capacity) { return Err(invalid("Data length doesn't match the bits")); }
+    skip_bytes(cursor, longs.saturating_mul(8))
+}
+
+// Walks a pre-1.18 section of a Padded/Compact layout (or of `Lit`, whose light is skipped too), returning whether
+// its data length fits the padded and the compact packing
+fn check_legacy_section(cursor: &mut Cursor<&[u8]>, format: ChunkFormat) -> std::io::Result<(bool, bool)> {
+    if !matches!(format, ChunkFormat::Lit { .. }) {
+        let block_count = cursor.read_i16::<BigEndian>()?;
+        if !(0..=SECTION_VOLUME as i16).contains(&block_count) { return Err(invalid("Block count out of range")); }
+    }
+    let bits = cursor.read_u8()? as usize;
+    if !(4..=32).contains(&bits) { return Err(invalid("Bits per block out of range")); }
+    if bits <= 8 { skip_palette(cursor, bits)?; }
+    let longs = read_varint(cursor)? as usize;
+    let fits = (longs == padded_longs(bits, SECTION_VOLUME), longs == compact_longs(bits, SECTION_VOLUME));
+    if fits == (false, false) { return Err(invalid("Data length doesn't match the bits")); }
+    skip_bytes(cursor, longs * 8)?;
+    skip_bytes(cursor, format.light_bytes())?;
+    Ok(fits)
+}
+
+// Walks the sections of pre-1.18 data, returning whether every one fits the padded and the compact packing. Data
+// left over that isn't a section but has the size of the biomes of a full chunk is taken for them.
+fn check_legacy_sections(data: &[u8], format: ChunkFormat) -> Option<(bool, bool)> {
+    let mut cursor = Cursor::new(data);
+    let (mut padded, mut compact) = (true, true);
+    let mut sections = 0;
+    while (cursor.position() as usize) < data.len() {
+        let remaining = data.len() - cursor.position() as usize;
+        match check_legacy_section(&mut cursor, format) {
+            Ok((fits_padded, fits_compact)) => {
+                padded &= fits_padded;
+                compact &= fits_compact;
+            }
+            Err(_) if remaining == LEGACY_BIOME_BYTES => break,
+            Err(_) => return None,
+        }
+        sections += 1;
+    }
+    (sections <= SECTION_COUNT).then_some((padded, compact))
+}
+
+fn is_modern(data: &[u8]) -> bool {
+    let mut cursor = Cursor::new(data);
+    let mut sections = 0;
+    while (cursor.position() as usize) < data.len() {
+        let valid = (|| {
+            let block_count = cursor.read_i16::<BigEndian>()?;
+            if !(0..=SECTION_VOLUME as i16).contains(&block_count) { return Err(invalid("Block count out of range")); }
+            check_modern_container(&mut cursor, SECTION_VOLUME, 8)?;
+            check_modern_container(&mut cursor, BIOME_SECTION_VOLUME, 3)
+        })();
+        if valid.is_err() { return false; }
+        sections += 1;
+    }
+    (1..=SECTION_COUNT).contains(&sections)
+}
+
+// Works out which layout the section data of a chunk packet uses, or `None` if it fits none of them.
+// Padded and Compact data look the same when every section uses 4, 8 or 16 bits; that data reads the same either
+// way and is reported as Padded.
+pub fn detect_format(data: &[u8]) -> Option<ChunkFormat> {
+    if data.is_empty() { return None; }
+    if is_modern(data) { return Some(ChunkFormat::Modern); }
+
+    if let Some((padded, compact)) = check_legacy_sections(data, ChunkFormat::Compact) {
+        if padded { return Some(ChunkFormat::Padded); }
+        if compact { return Some(ChunkFormat::Compact); }
+    }
+    [ChunkFormat::Lit { sky_light: true }, ChunkFormat::Lit { sky_light: false }]
+        .into_iter()
+        .find(|&format| check_legacy_sections(data, format).is_some_and(|(_, compact)| compact))
+}
+
+// Reads a pre-1.18 section into a section of this world
+fn parse_legacy_section(cursor: &mut Cursor<&[u8]>, format: ChunkFormat, registry: &BlockRegistry) -> std::io::Result<ChunkSection> {
+    if !matches!(format, ChunkFormat::Lit { .. }) {
+        cursor.read_i16::<BigEndian>()?; // Recounted from the blocks
+    }
+    let bits = cursor.read_u8()? as usize;
+    if !(4..=32).contains(&bits) { return Err(invalid("Bits per block out of range")); }
+    let palette = if bits <= 8 {
+        let palette_len = read_length(cursor, 1)?;
+        (0..palette_len).map(|_| read_varint(cursor).map(|id| id as u32)).collect::<std::io::Result<Vec<u32>>>()?
+    } else {
+        Vec::new()
+    };
+    let long_count = read_length(cursor, 8)?;
+    let longs = read_long_array(cursor, long_count)?;
+    skip_bytes(cursor, format.light_bytes())?;
+
+    let mask = (1u64 << bits) - 1;
+    let values_per_long = 64 / bits;
+    let mut states = vec![0u32; SECTION_VOLUME];
+    for (index, state) in states.iter_mut().enumerate() {
+        let value = if format == ChunkFormat::Padded {
+            let long = longs.get(index / values_per_long).copied().unwrap_or(0);
+            (long >> ((index % values_per_long) * bits)) & mask
+        } else {
+            let bit = index * bits;
+            let (long, offset) = (bit / 64, bit % 64);
+            let mut value = longs.get(long).copied().unwrap_or(0) >> offset;
+            if offset + bits > 64 {
+                value |= longs.get(long + 1).copied().unwrap_or(0) << (64 - offset);
+            }
+            value & mask
+        };
+        *state = if palette.is_empty() { value as u32 } else { palette.get(value as usize).copied().unwrap_or(0) };
+    }
+    Ok(ChunkSection::from_block_states(&states, registry))
+}
+
+#[napi]
+impl NapiWorld {
+    /// Guesses the layout of a chunk packet's section data from its structure: `'1.18'` (1.18 and later),
+    /// `'1.16'` (1.16-1.17), `'1.14'` (1.14-1.15) or `'1.13'`, or `null` if it matches none of them.
+    #[napi(ts_return_type = "'1.18' | '1.16' | '1.14' | '1.13' | null")]
+    pub fn detect_format(&self, data_buffer: Buffer) -> Option<String> {
+        detect_format(data_buffer.as_ref()).map(|format| format.name().to_string())
+    }
+
+    /// Loads a column like `load_column`, first detecting which version's layout the data uses (see `detect_format`).
+    /// Before 1.18 only some sections are sent: bit `i` of `section_mask` (from the packet) marks section Y `i`,
+    /// and without it the sections are placed upwards from Y 0. Biomes sent along before 1.15 and 1.13's in-band
+    /// light are skipped. Returns the detected format.
+    #[napi(ts_return_type = "'1.18' | '1.16' | '1.14' | '1.13'")]
+    pub fn load_column_auto(&self, chunk_x: i32, chunk_z: i32, data_buffer: Buffer, section_mask: Option<u32>) -> Result<String> {
+        let format = detect_format(data_buffer.as_ref()).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            "Unrecognized chunk data format".to_string(),
+        ))?;
+        if format == ChunkFormat::Modern {
+            self.load_column(chunk_x, chunk_z, data_buffer)?;
+            return Ok(format.name().to_string());
+        }
+
+        let mut section_ys = (0..=MAX_SECTION_Y).filter(|&y| section_mask.is_none_or(|mask| y < 32 && mask & (1 << y) != 0));
+        let mut cursor = Cursor::new(data_buffer.as_ref());
+        let mut column = ChunkColumn::new();
+        while (cursor.position() as usize) < data_buffer.len() {
+            // Biomes of a full chunk, found in the same place by `detect_format`
+            let remaining = data_buffer.len() - cursor.position() as usize;
+            if remaining == LEGACY_BIOME_BYTES && check_legacy_section(&mut cursor.clone(), format).is_err() { break; }
+            let section_y = section_ys.next().ok_or_else(|| napi::Error::new(
+                napi::Status::InvalidArg,
+                "Chunk data has more sections than the section mask".to_string(),
+            ))?;
+            let section = parse_legacy_section(&mut cursor, format, &self.registry).map_err(|e| napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Failed to parse chunk section at y={}: {}", section_y, e),
+            ))?;
+            column.insert_section(section_y, section);
+        }
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, column);
+        Ok(format.name().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::SectionRelCoords;
+    use crate::palette::test_support::write_varint;
+    use crate::registry::test_support::test_registry;
+
+    // 20 distinct states, so sections need 5 bits, where padded and compact packing differ
+    fn sample_states(seed: u32) -> Vec<u32> {
+        (0..SECTION_VOLUME as u32).map(|i| (i.wrapping_mul(2_654_435_761).wrapping_add(seed) >> 7) % 20).collect()
+    }
+
+    // One section in a pre-1.18 layout, with an indirect palette, longs in the byte order `read_long_array` reads
+    fn write_legacy_section(out: &mut Vec<u8>, format: ChunkFormat, states: &[u32]) {
+        let bits = 5;
+        if !matches!(format, ChunkFormat::Lit { .. }) {
+            out.extend_from_slice(&(states.iter().filter(|&&s| s != 0).count() as i16).to_be_bytes());
+        }
+        out.push(bits as u8);
+        write_varint(out, 20);
+        (0..20).for_each(|state| write_varint(out, state));
+
+        let mut longs = vec![0u64; if format == ChunkFormat::Padded { padded_longs(bits, SECTION_VOLUME) } else { compact_longs(bits, SECTION_VOLUME) }];
+        for (index, &state) in states.iter().enumerate() {
+            if format == ChunkFormat::Padded {
+                let per_long = 64 / bits;
+                longs[index / per_long] |= (state as u64) << ((index % per_long) * bits);
+            } else {
+                let bit = index * bits;
+                longs[bit / 64] |= (state as u64) << (bit % 64);
+                if bit % 64 + bits > 64 { longs[bit / 64 + 1] |= (state as u64) >> (64 - bit % 64); }
+            }
+        }
+        write_varint(out, longs.len() as i32);
+        longs.iter().for_each(|long| out.extend_from_slice(&long.to_le_bytes()));
+        out.extend(std::iter::repeat_n(0xAB, format.light_bytes()));
+    }
+
+    fn write_legacy_chunk(format: ChunkFormat, sections: usize, biomes: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        for section in 0..sections {
+            write_legacy_section(&mut out, format, &sample_states(section as u32));
+        }
+        if biomes { out.extend(std::iter::repeat_n(0, LEGACY_BIOME_BYTES)); }
+        out
+    }
+
+    const LEGACY_FORMATS: [ChunkFormat; 4] =
+        [ChunkFormat::Padded, ChunkFormat::Compact, ChunkFormat::Lit { sky_light: true }, ChunkFormat::Lit { sky_light: false }];
+
+    #[test]
+    fn legacy_layouts_are_detected() {
+        for format in LEGACY_FORMATS {
+            for sections in [1, 3, 16] {
+                for biomes in [false, true] {
+                    let data = write_legacy_chunk(format, sections, biomes);
+                    assert_eq!(detect_format(&data), Some(format), "{:?}, {} sections, biomes {}", format, sections, biomes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn legacy_sections_round_trip() {
+        let registry = test_registry();
+        for format in LEGACY_FORMATS {
+            let data = write_legacy_chunk(format, 2, false);
+            let mut cursor = Cursor::new(data.as_slice());
+            for section_index in 0..2 {
+                let section = parse_legacy_section(&mut cursor, format, &registry).unwrap();
+                let states = sample_states(section_index);
+                for index in [0, 1, 12, 13, 255, 4095] {
+                    let coords = SectionRelCoords { x: (index & 15) as i32, y: (index >> 8) as i32, z: ((index >> 4) & 15) as i32 };
+                    assert_eq!(section.get_block_state_id(coords), states[index], "{:?} block {}", format, index);
+                }
+            }
+            assert_eq!(cursor.position() as usize, data.len(), "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn malformed_data_is_rejected_without_panicking() {
+        let registry = test_registry();
+        let data = write_legacy_chunk(ChunkFormat::Compact, 2, false);
+        // Truncated data
+        for len in (0..data.len()).step_by(61).chain(data.len() - 16..data.len()) {
+            let prefix = &data[..len];
+            let _ = detect_format(prefix);
+            for format in LEGACY_FORMATS {
+                let _ = parse_legacy_section(&mut Cursor::new(prefix), format, &registry);
+            }
+        }
+
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..2_000 {
+            let len = (seed % 600) as usize;
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    seed as u8
+                })
+                .collect();
+            let _ = detect_format(&bytes);
+            for format in LEGACY_FORMATS {
+                let _ = parse_legacy_section(&mut Cursor::new(bytes.as_slice()), format, &registry);
+            }
+        }
+
+        // Lengths that don't fit the bits
+        let mut bad = Vec::new();
+        bad.extend_from_slice(&0i16.to_be_bytes());
+        bad.push(5);
+        write_varint(&mut bad, 1);
+        write_varint(&mut bad, 0);
+        write_varint(&mut bad, 7);
+        bad.extend_from_slice(&[0; 56]);
+        assert_eq!(detect_format(&bad), None);
+    }
+}
@@ -0,0 +1,66 @@
+// src/frustum.rs
+use crate::dirty::SectionPosition;
+use crate::raycast::Vec3Arg;
+use crate::world::NapiWorld;
+use glam::{DMat4, DVec3, DVec4};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// Six planes (a, b, c, d) with the inside where a*x + b*y + c*z + d >= 0
+type Planes = [DVec4; 6];
+
+// Frustum planes from a column-major view-projection matrix with OpenGL clip space (-w..w on every axis)
+fn planes_from_view_proj(values: &[f64; 16]) -> Planes {
+    let m = DMat4::from_cols_array(values);
+    let (r0, r1, r2, r3) = (m.row(0), m.row(1), m.row(2), m.row(3));
+    [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r3 + r2, r3 - r2]
+}
+
+// Whether any part of the box is on the inside of every plane. Conservative: boxes near a frustum corner
+// can pass while being just outside, which only costs drawing a section that isn't visible.
+fn aabb_in_frustum(planes: &Planes, min: DVec3, max: DVec3) -> bool {
+    planes.iter().all(|plane| {
+        let normal = plane.truncate();
+        // The corner furthest along the plane normal
+        let corner = DVec3::select(normal.cmpge(DVec3::ZERO), max, min);
+        normal.dot(corner) + plane.w >= 0.0
+    })
+}
+
+#[napi]
+impl NapiWorld {
+    /// Lists the loaded sections that contain blocks and are at least partly inside a camera frustum,
+    /// sorted front-to-back (by distance from `camera_pos` to the section center).
+    ///
+    /// `frustum` is either a 16-number column-major view-projection matrix (OpenGL clip space, as in three.js or
+    /// gl-matrix) or 24 numbers giving six planes `a, b, c, d`, with the inside where `a*x + b*y + c*z + d >= 0`.
+    #[napi(ts_args_type = "camera_pos: { x: number, y: number, z: number }, frustum: number[]")]
+    pub fn get_sections_in_frustum(&self, camera_pos: Vec3Arg, frustum: Vec<f64>) -> Result<Vec<SectionPosition>> {
+        let planes = match frustum.len() {
+            16 => planes_from_view_proj(frustum.as_slice().try_into().unwrap()),
+            24 => std::array::from_fn(|i| DVec4::from_slice(&frustum[i * 4..i * 4 + 4])),
+            len => return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Frustum must be a 16-number matrix or 24 plane coefficients, got {} numbers", len),
+            )),
+        };
+        let camera = DVec3::from(camera_pos);
+
+        let mut visible = Vec::new();
+        for (chunk, column) in self.all_columns() {
+            let Ok(column) = column.read() else { continue; };
+            for (section_y, section) in column.sections() {
+                if section.solid_block_count() <= 0 { continue; }
+                let min = DVec3::new((chunk.x << 4) as f64, (section_y << 4) as f64, (chunk.z << 4) as f64);
+                let max = min + DVec3::splat(16.0);
+                if aabb_in_frustum(&planes, min, max) {
+                    let center = min + DVec3::splat(8.0);
+                    visible.push((center.distance_squared(camera), SectionPosition { chunk_x: chunk.x, section_y, chunk_z: chunk.z }));
+                }
+            }
+        }
+
+        visible.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(visible.into_iter().map(|(_, section)| section).collect())
+    }
+}
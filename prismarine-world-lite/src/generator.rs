@@ -0,0 +1,90 @@
+// src/generator.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WORLD_HEIGHT};
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// Parses a superflat layer string such as "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block"
+// (bottom layer first) into (default state ID, height) pairs.
+pub fn parse_layer_spec(registry: &BlockRegistry, spec: &str) -> std::result::Result<Vec<(u32, u32)>, String> {
+    let mut layers = Vec::new();
+    let mut total_height: u32 = 0;
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (height, name) = match entry.split_once('*') {
+            Some((count, name)) => (count.trim().parse::<u32>().map_err(|_| format!("Invalid layer height in '{}'", entry))?, name.trim()),
+            None => (1, entry),
+        };
+        let block = registry.block_by_name(name).ok_or_else(|| format!("Unknown block: {}", name))?;
+        total_height = match total_height.checked_add(height) {
+            Some(total) if total <= WORLD_HEIGHT as u32 => total,
+            _ => return Err(format!("Layers are taller than the world ({} blocks)", WORLD_HEIGHT)),
+        };
+        layers.push((block.default_state.unwrap_or(block.id << 4), height));
+    }
+    Ok(layers)
+}
+
+pub fn generate_flat(layers: &[(u32, u32)], biome_id: u32) -> ChunkColumn {
+    let mut column = ChunkColumn::from_layers(layers);
+    column.fill_biome(biome_id);
+    column
+}
+
+pub fn generate_void(biome_id: u32) -> ChunkColumn {
+    let mut column = ChunkColumn::new();
+    column.fill_biome(biome_id);
+    column
+}
+
+impl NapiWorld {
+    fn generator_biome(&self, biome: Option<Either<u32, String>>, default_name: &str) -> Result<u32> {
+        match biome {
+            Some(biome) => self.resolve_biome(biome),
+            None => Ok(self.biomes.biome_id_by_name(default_name).unwrap_or(0)),
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Generates and loads a superflat column from a layer string such as
+    /// `"minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block"` (bottom layer first, starting at the bottom
+    /// of the world). The biome defaults to plains.
+    #[napi(ts_args_type = "chunkX: number, chunkZ: number, layerSpec: string, biome?: number | string | null")]
+    pub fn generate_flat(&self, chunk_x: i32, chunk_z: i32, layer_spec: String, biome: Option<Either<u32, String>>) -> Result<()> {
+        let layers = parse_layer_spec(&self.registry, &layer_spec).map_err(|e| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Invalid layer spec: {}", e),
+        ))?;
+        let biome_id = self.generator_biome(biome, "plains")?;
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, generate_flat(&layers, biome_id));
+        Ok(())
+    }
+
+    /// Generates and loads an empty column. The biome defaults to the void.
+    #[napi(ts_args_type = "chunkX: number, chunkZ: number, biome?: number | string | null")]
+    pub fn generate_void(&self, chunk_x: i32, chunk_z: i32, biome: Option<Either<u32, String>>) -> Result<()> {
+        let biome_id = self.generator_biome(biome, "the_void")?;
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, generate_void(biome_id));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::test_registry;
+
+    #[test]
+    fn layer_spec_heights() {
+        let registry = test_registry();
+        assert_eq!(parse_layer_spec(&registry, "minecraft:stone, 2*dirt").unwrap(), vec![(1, 1), (2, 2)]);
+        assert!(parse_layer_spec(&registry, "384*stone").is_ok());
+        assert!(parse_layer_spec(&registry, "384*stone,dirt").is_err());
+        assert!(parse_layer_spec(&registry, "4294967295*stone,2*dirt").is_err());
+        assert!(parse_layer_spec(&registry, "x*stone").is_err());
+        assert!(parse_layer_spec(&registry, "unobtainium").is_err());
+    }
+}
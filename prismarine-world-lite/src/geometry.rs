@@ -0,0 +1,95 @@
+// src/geometry.rs
+use crate::coords::WorldCoords;
+use crate::raycast::{RaycastIterator, Vec3Arg};
+use crate::region::Region;
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+impl NapiWorld {
+    // Packs positions as flat [x, y, z, ...] triples, or [x, y, z, stateId, ...] quads when states are included
+    fn pack_positions(&self, positions: impl Iterator<Item = WorldCoords>, include_states: bool) -> Int32Array {
+        let mut packed = Vec::new();
+        for pos in positions {
+            packed.extend_from_slice(&[pos.x, pos.y, pos.z]);
+            if include_states {
+                packed.push(self.get_block_state_id(pos.x, pos.y, pos.z) as i32);
+            }
+        }
+        Int32Array::new(packed)
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Lists every block the straight line between two points passes through, in order from `from` to `to`.
+    ///
+    /// This is a full voxel traversal (no block is skipped, even when the line only clips a corner), and
+    /// doesn't look at the world. Pass block centers (`x + 0.5`) to walk between two block positions.
+    #[napi(ts_args_type = "from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }")]
+    pub fn blocks_on_line(&self, from: Vec3Arg, to: Vec3Arg) -> Vec<WorldCoords> {
+        let from = DVec3::from(from);
+        let delta = DVec3::from(to) - from;
+        let distance = delta.length();
+
+        let mut iterator = RaycastIterator::new(from, delta.normalize_or_zero(), distance);
+        // The line isn't limited to the world height
+        iterator.min_y = i32::MIN;
+        iterator.max_y = i32::MAX;
+
+        let mut blocks = Vec::new();
+        while let Some((block_pos, _)) = iterator.next() {
+            blocks.push(block_pos);
+            if distance == 0.0 { break; }
+        }
+        blocks
+    }
+
+    /// Lists the blocks whose centers lie within `radius` of the center block's center.
+    ///
+    /// Returns a flat `Int32Array` of `[x, y, z]` triples, or `[x, y, z, stateId]` quads when `include_states` is set.
+    #[napi]
+    pub fn blocks_in_sphere(&self, center: WorldCoords, radius: f64, include_states: Option<bool>) -> Int32Array {
+        let r = radius.max(0.0).floor() as i32;
+        let radius_sq = radius * radius;
+        let lo = WorldCoords { x: center.x - r, y: center.y - r, z: center.z - r };
+        let hi = WorldCoords { x: center.x + r, y: center.y + r, z: center.z + r };
+        let inside = Region::new(lo, hi).positions().filter(|p| {
+            let (dx, dy, dz) = ((p.x - center.x) as f64, (p.y - center.y) as f64, (p.z - center.z) as f64);
+            dx * dx + dy * dy + dz * dz <= radius_sq
+        });
+        self.pack_positions(inside, include_states.unwrap_or(false))
+    }
+
+    /// Lists the blocks of a vertical cylinder whose base is centered on `center`, extending `height` blocks upwards.
+    /// A block is included when its center column lies within `radius` of the center column.
+    ///
+    /// Returns the same packed layout as `blocks_in_sphere`.
+    #[napi]
+    pub fn blocks_in_cylinder(&self, center: WorldCoords, radius: f64, height: u32, include_states: Option<bool>) -> Int32Array {
+        if height == 0 { return Int32Array::new(Vec::new()); }
+        let r = radius.max(0.0).floor() as i32;
+        let radius_sq = radius * radius;
+        let lo = WorldCoords { x: center.x - r, y: center.y, z: center.z - r };
+        let hi = WorldCoords { x: center.x + r, y: center.y + height as i32 - 1, z: center.z + r };
+        let inside = Region::new(lo, hi).positions().filter(|p| {
+            let (dx, dz) = ((p.x - center.x) as f64, (p.z - center.z) as f64);
+            dx * dx + dz * dz <= radius_sq
+        });
+        self.pack_positions(inside, include_states.unwrap_or(false))
+    }
+
+    /// Lists the blocks on the outer surface of the cuboid between two corners (inclusive).
+    ///
+    /// Returns the same packed layout as `blocks_in_sphere`.
+    #[napi]
+    pub fn cuboid_shell(&self, min: WorldCoords, max: WorldCoords, include_states: Option<bool>) -> Int32Array {
+        let region = Region::new(min, max);
+        let (lo, hi) = (region.min(), region.max());
+        let shell = region.positions().filter(|p| {
+            p.x == lo.x || p.x == hi.x || p.y == lo.y || p.y == hi.y || p.z == lo.z || p.z == hi.z
+        });
+        self.pack_positions(shell, include_states.unwrap_or(false))
+    }
+}
@@ -0,0 +1,88 @@
+// src/hashing.rs
+use crate::chunk::ChunkSection;
+use crate::coords::{ChunkCoords, BIOME_SECTION_VOLUME, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_VOLUME};
+use crate::world::NapiWorld;
+use napi_derive::napi;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// 64-bit FNV-1a. Unlike std's hasher its output is fixed, so hashes can be compared across processes and builds.
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv64(u64);
+
+impl Fnv64 {
+    pub fn new() -> Self {
+        Fnv64(FNV_OFFSET_BASIS)
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_u32(value as u32);
+        self.write_u32((value >> 32) as u32);
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for Fnv64 {
+    fn default() -> Self {
+        Fnv64::new()
+    }
+}
+
+// Hashes the decoded block states and biomes rather than the raw palette, so equal content
+// hashes equally regardless of palette layout. Missing sections hash as all air.
+pub fn hash_section(section: Option<&ChunkSection>) -> u64 {
+    let mut hasher = Fnv64::new();
+    for index in 0..SECTION_VOLUME {
+        hasher.write_u32(section.map_or(0, |s| s.block_states().get(index)));
+    }
+    for index in 0..BIOME_SECTION_VOLUME {
+        hasher.write_u32(section.map_or(0, |s| s.biomes().get(index)));
+    }
+    hasher.finish()
+}
+
+#[napi]
+impl NapiWorld {
+    /// Gets a content hash of a section's block states and biomes as a 16-digit hex string, or null if its column
+    /// isn't loaded or the section is outside the world. Equal sections hash equally in every process, so this is
+    /// a cheap change/divergence check.
+    #[napi]
+    pub fn get_section_hash(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<String> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) { return None; }
+        let chunk_coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().try_read().ok()?;
+        Some(format!("{:016x}", hash_section(column.get_section(section_y))))
+    }
+
+    /// Gets a digest of every loaded column (coordinates plus the hash of each section) as a 16-digit hex string.
+    /// Two worlds with the same columns loaded and the same content produce the same digest, whatever order the
+    /// columns were loaded in. Hashes every section of every column, so avoid calling it every tick.
+    #[napi]
+    pub fn get_world_hash(&self) -> String {
+        let columns = self.all_columns();
+
+        let mut hasher = Fnv64::new();
+        for (coords, column) in columns {
+            hasher.write_u32(coords.x as u32);
+            hasher.write_u32(coords.z as u32);
+            let column = column.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+            for section_y in MIN_SECTION_Y..=MAX_SECTION_Y {
+                hasher.write_u64(hash_section(column.get_section(section_y)));
+            }
+        }
+        format!("{:016x}", hasher.finish())
+    }
+}
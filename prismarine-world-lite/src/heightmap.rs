@@ -0,0 +1,94 @@
+// src/heightmap.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::surface::highest_matching;
+use crate::world::NapiWorld;
+use std::collections::HashSet;
+
+// Y of the topmost solid block of each (x, z) of a column, indexed `z * 16 + x` (chunk-relative);
+// `MIN_CHUNK_Y - 1` where the column has none
+pub struct Heightmap {
+    heights: Vec<i32>,
+}
+
+impl Heightmap {
+    pub fn height(&self, x: i32, z: i32) -> i32 {
+        self.heights[(z.rem_euclid(16) * 16 + x.rem_euclid(16)) as usize]
+    }
+}
+
+impl NapiWorld {
+    fn top_solid_y(&self, column: &ChunkColumn, x: i32, z: i32) -> i32 {
+        highest_matching(column, x, z, MIN_CHUNK_Y, MAX_CHUNK_Y - 1, |state_id| self.registry.is_solid(state_id))
+            .map_or(MIN_CHUNK_Y - 1, |(y, _)| y)
+    }
+
+    fn build_heightmap(&self, coords: ChunkCoords, column: &ChunkColumn) -> Heightmap {
+        let mut heights = vec![MIN_CHUNK_Y - 1; 256];
+        for z in 0..16 {
+            for x in 0..16 {
+                heights[(z * 16 + x) as usize] = self.top_solid_y(column, (coords.x << 4) + x, (coords.z << 4) + z);
+            }
+        }
+        Heightmap { heights }
+    }
+
+    // Y of the topmost solid block at (x, z) of `column`, building the column's heightmap on first use
+    pub(crate) fn solid_height(&self, column: &ChunkColumn, x: i32, z: i32) -> i32 {
+        let coords = WorldCoords { x, y: 0, z }.to_chunk_coords();
+        if let Some(heightmap) = self.heightmaps.get(&coords) {
+            return heightmap.height(x, z);
+        }
+        let heightmap = self.build_heightmap(coords, column);
+        let height = heightmap.height(x, z);
+        self.heightmaps.insert(coords, heightmap);
+        height
+    }
+
+    // Keeps cached heightmaps in step with block changes. Only changes at or above a column's height can move it.
+    pub(crate) fn update_heightmaps(&self, changed: &[WorldCoords]) {
+        if self.heightmaps.is_empty() { return; }
+
+        let mut affected = HashSet::new();
+        for pos in changed {
+            let chunk = pos.to_chunk_coords();
+            let Some(heightmap) = self.heightmaps.get(&chunk) else { continue; };
+            if pos.y >= heightmap.height(pos.x, pos.z) {
+                affected.insert((chunk, pos.x, pos.z));
+            }
+        }
+        for (chunk, x, z) in affected {
+            let Some(entry) = self.columns.get(&chunk) else { continue; };
+            let Ok(column) = entry.value().read() else { continue; };
+            let height = self.top_solid_y(&column, x, z);
+            if let Some(mut heightmap) = self.heightmaps.get_mut(&chunk) {
+                heightmap.heights[(z.rem_euclid(16) * 16 + x.rem_euclid(16)) as usize] = height;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ChunkColumn;
+    use crate::coords::ChunkCoords;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::{ColumnStore, NapiWorld};
+    use std::sync::Arc;
+
+    #[test]
+    fn writes_through_other_worlds_move_the_surface() {
+        let store = ColumnStore::default();
+        let (registry, biomes) = (Arc::new(test_registry()), Arc::new(test_biomes()));
+        let world = NapiWorld::from_parts(store.clone(), Arc::clone(&registry), Arc::clone(&biomes));
+        let other = NapiWorld::from_parts(store, registry, biomes);
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, ChunkColumn::new());
+        world.set_block_state_id(3, 60, 4, 1).unwrap();
+        assert_eq!(world.surface_raycast(3, 4, None, None).map(|hit| hit.y), Some(60));
+
+        other.set_block_state_id(3, 90, 4, 2).unwrap();
+        assert_eq!(world.surface_raycast(3, 4, None, None).map(|hit| hit.y), Some(90));
+        other.set_block_state_id(3, 90, 4, 0).unwrap();
+        assert_eq!(world.surface_raycast(3, 4, None, None).map(|hit| hit.y), Some(60));
+    }
+}
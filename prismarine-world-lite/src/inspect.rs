@@ -0,0 +1,259 @@
+// src/inspect.rs
+use crate::chunk::ChunkSection;
+use crate::chunk_keys::column_coords;
+use crate::coords::{ChunkCoords, BIOME_SECTION_VOLUME, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_VOLUME};
+use crate::palette::PaletteContainer;
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+#[napi(object)]
+pub struct SectionStats {
+    pub section_y: i32,
+    pub palette_type: String, // "single", "indirect" or "direct"
+    pub palette_size: u32, // Distinct palette entries (0 for direct storage)
+    pub bits_per_value: u32,
+    pub non_air_count: u32, // Counted from the block data
+    pub solid_block_count: i32, // As stored (sent by the server and updated on writes)
+    pub biome_palette_type: String,
+    pub biome_palette_size: u32,
+}
+
+#[napi(object)]
+pub struct ColumnStats {
+    pub section_count: u32,
+    pub sections: Vec<SectionStats>,
+}
+
+#[napi(object)]
+pub struct PaletteDump {
+    pub palette_type: String,
+    pub bits_per_value: u32,
+    pub palette: Vec<u32>, // The single value for single containers, empty for direct ones
+    pub data: Vec<String>, // Raw storage longs as hex strings
+}
+
+#[napi(object)]
+pub struct SectionPalette {
+    pub palette_type: String, // "single", "indirect" or "direct"
+    pub palette: Vec<u32>,    // State IDs; the single state for single containers, empty for direct ones
+    pub bits_per_value: u32,
+}
+
+#[napi(object)]
+pub struct SectionDump {
+    pub solid_block_count: i32,
+    pub block_states: PaletteDump,
+    pub biomes: PaletteDump,
+}
+
+#[napi(object)]
+pub struct ValidationIssue {
+    pub chunk_x: i32,
+    pub section_y: i32,
+    pub chunk_z: i32,
+    pub kind: String, // "paletteIndex", "bitsPerValue", "dataLength", "emptyPalette", "unknownState" or "solidCount"
+    pub message: String,
+}
+
+#[napi(object)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub columns_checked: u32,
+    pub sections_checked: u32,
+    pub issues: Vec<ValidationIssue>,
+}
+
+// Storage invariants of one palette container, as (kind, message) problems
+fn check_container(container: &PaletteContainer, capacity: usize, indirect_bits: (usize, usize), problems: &mut Vec<(&'static str, String)>) {
+    let data = match container {
+        PaletteContainer::Single(_) => return,
+        PaletteContainer::Indirect { palette, data } => {
+            let bits = data.get_bits_per_value();
+            if palette.is_empty() {
+                problems.push(("emptyPalette", "Indirect palette has no entries".to_string()));
+            }
+            if bits < indirect_bits.0 || bits > indirect_bits.1 {
+                problems.push(("bitsPerValue", format!("Indirect palette uses {} bits per value, expected {}-{}", bits, indirect_bits.0, indirect_bits.1)));
+            }
+            if let Some(index) = (0..capacity).map(|i| data.get(i) as usize).find(|&i| i >= palette.len()) {
+                problems.push(("paletteIndex", format!("Palette index {} out of bounds for {} entries", index, palette.len())));
+            }
+            data
+        }
+        PaletteContainer::Direct(data) => {
+            if data.get_bits_per_value() <= indirect_bits.1 {
+                problems.push(("bitsPerValue", format!("Direct storage uses only {} bits per value", data.get_bits_per_value())));
+            }
+            data
+        }
+    };
+
+    let bits = data.get_bits_per_value();
+    let expected_longs = capacity.div_ceil(64 / bits);
+    if data.len() != expected_longs {
+        problems.push(("dataLength", format!("{} data longs for {} bits per value, expected {}", data.len(), bits, expected_longs)));
+    }
+}
+
+fn check_section(registry: &BlockRegistry, section: &ChunkSection) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+    let blocks = section.block_states();
+    check_container(blocks, SECTION_VOLUME, (4, 8), &mut problems);
+    check_container(section.biomes(), BIOME_SECTION_VOLUME, (1, 3), &mut problems);
+
+    let mut non_air = 0;
+    let mut unknown_state = None;
+    for index in 0..SECTION_VOLUME {
+        let state_id = blocks.get(index);
+        if registry.block(state_id).is_none() {
+            unknown_state.get_or_insert(state_id);
+        }
+        if !registry.is_air(state_id) {
+            non_air += 1;
+        }
+    }
+    if let Some(state_id) = unknown_state {
+        problems.push(("unknownState", format!("Unknown block state ID {}", state_id)));
+    }
+    if non_air != section.solid_block_count() as i32 {
+        problems.push(("solidCount", format!("Stored block count {} but {} non-air blocks", section.solid_block_count(), non_air)));
+    }
+    problems
+}
+
+fn palette_size(container: &PaletteContainer) -> u32 {
+    match container {
+        PaletteContainer::Single(_) => 1,
+        PaletteContainer::Indirect { palette, .. } => palette.len() as u32,
+        PaletteContainer::Direct(_) => 0,
+    }
+}
+
+fn bits_per_value(container: &PaletteContainer) -> u32 {
+    match container {
+        PaletteContainer::Single(_) => 0,
+        PaletteContainer::Indirect { data, .. } | PaletteContainer::Direct(data) => data.get_bits_per_value() as u32,
+    }
+}
+
+fn dump_palette(container: &PaletteContainer) -> PaletteDump {
+    let (palette, data) = match container {
+        PaletteContainer::Single(value) => (vec![*value], Vec::new()),
+        PaletteContainer::Indirect { palette, data } => (palette.clone(), data.get_data().clone()),
+        PaletteContainer::Direct(data) => (Vec::new(), data.get_data().clone()),
+    };
+    PaletteDump {
+        palette_type: container.kind_name().to_string(),
+        bits_per_value: bits_per_value(container),
+        palette,
+        data: data.iter().map(|long| format!("{:#018x}", long)).collect(),
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Describes how each present section of a loaded column is stored: palette type and size, bits per value,
+    /// and counted vs. stored non-air blocks. Takes coordinates or a `chunkKey`; returns null if the column isn't loaded.
+    #[napi]
+    pub fn get_column_stats(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<Option<ColumnStats>> {
+        let chunk_coords = column_coords(chunk_x, chunk_z)?;
+        self.ensure_column(chunk_coords);
+        let Some(entry) = self.columns.get(&chunk_coords) else { return Ok(None); };
+        let Ok(column) = entry.value().try_read() else { return Ok(None); };
+
+        let sections: Vec<SectionStats> = column.sections().map(|(section_y, section)| {
+            let blocks = section.block_states();
+            SectionStats {
+                section_y,
+                palette_type: blocks.kind_name().to_string(),
+                palette_size: palette_size(blocks),
+                bits_per_value: bits_per_value(blocks),
+                non_air_count: (0..SECTION_VOLUME).filter(|&i| blocks.get(i) != 0).count() as u32,
+                solid_block_count: section.solid_block_count() as i32,
+                biome_palette_type: section.biomes().kind_name().to_string(),
+                biome_palette_size: palette_size(section.biomes()),
+            }
+        }).collect();
+        Ok(Some(ColumnStats { section_count: sections.len() as u32, sections }))
+    }
+
+    /// Dumps a section's raw storage (palettes and data longs) for debugging. Returns null if the column
+    /// isn't loaded or has no such section.
+    #[napi]
+    pub fn dump_section(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<SectionDump> {
+        let chunk_coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().try_read().ok()?;
+        let section = column.get_section(section_y)?;
+        Some(SectionDump {
+            solid_block_count: section.solid_block_count() as i32,
+            block_states: dump_palette(section.block_states()),
+            biomes: dump_palette(section.biomes()),
+        })
+    }
+
+    /// Gets the state IDs of all 4096 blocks of a section at once, indexed by `(y * 16 + z) * 16 + x`
+    /// (section-relative). Missing sections read as air. Returns null if the column isn't loaded or the section is
+    /// outside the world.
+    #[napi]
+    pub fn get_section_blocks(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<Uint32Array> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) { return None; }
+        let chunk_coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().try_read().ok()?;
+
+        let mut states = vec![0u32; SECTION_VOLUME];
+        if let Some(section) = column.get_section(section_y) {
+            section.block_states().unpack_into(&mut states);
+        }
+        Some(states.into())
+    }
+
+    /// Gets how a section's blocks are stored: container type and palette entries (state IDs). Palettes can hold
+    /// states no block uses anymore, but a state missing from them is certainly absent from the section. Missing
+    /// sections read as a single air entry. Returns null if the column isn't loaded or the section is outside the
+    /// world.
+    #[napi]
+    pub fn get_section_palette(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> Option<SectionPalette> {
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) { return None; }
+        let chunk_coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().try_read().ok()?;
+
+        let Some(section) = column.get_section(section_y) else {
+            return Some(SectionPalette { palette_type: "single".to_string(), palette: vec![0], bits_per_value: 0 });
+        };
+        let blocks = section.block_states();
+        let palette = match blocks {
+            PaletteContainer::Single(value) => vec![*value],
+            PaletteContainer::Indirect { palette, .. } => palette.clone(),
+            PaletteContainer::Direct(_) => Vec::new(),
+        };
+        Some(SectionPalette { palette_type: blocks.kind_name().to_string(), palette, bits_per_value: bits_per_value(blocks) })
+    }
+
+    /// Checks every loaded section for storage corruption: palette indices out of bounds, invalid bits per value
+    /// or data lengths, unknown block states and block counts that don't match the data. Lookups silently treat
+    /// corrupt data as air, so run this when a bot sees blocks that shouldn't be there.
+    #[napi]
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport { valid: true, columns_checked: 0, sections_checked: 0, issues: Vec::new() };
+        for (coords, column) in self.all_columns() {
+            let column = column.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+            report.columns_checked += 1;
+            for (section_y, section) in column.sections() {
+                report.sections_checked += 1;
+                for (kind, message) in check_section(&self.registry, section) {
+                    report.issues.push(ValidationIssue { chunk_x: coords.x, section_y, chunk_z: coords.z, kind: kind.to_string(), message });
+                }
+            }
+        }
+        report.valid = report.issues.is_empty();
+        report
+    }
+}
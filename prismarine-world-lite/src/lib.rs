@@ -1,14 +1,153 @@
 #![deny(clippy::all)]
+// Without the Node binding, parts of the core only it uses go unused
+#![cfg_attr(not(feature = "napi"), allow(dead_code, unused_imports))]
+// Free #[napi] functions are only exported outside of test builds
+#![cfg_attr(test, allow(dead_code))]
 
 // Removed #[macro_use] as it wasn't needed for napi_derive
+#[cfg(feature = "napi")]
 extern crate napi_derive;
 
+// Modules without a cfg form the N-API-free core (storage, parsing, raycasting) shared with the WASM facade.
+// The rest make up the Node binding; keep declarations in this order, #[napi] impls need their struct expanded
+// first.
 mod chunk;
 mod coords;
 mod palette;
 mod parsing;
+#[cfg(feature = "napi")]
 mod world;
 mod raycast;
+#[cfg(feature = "napi")]
+mod raycast_batch;
+mod registry;
+mod outline;
+#[cfg(feature = "napi")]
+mod explosion;
+#[cfg(feature = "napi")]
+mod spawning;
+#[cfg(feature = "napi")]
+mod biomes;
+#[cfg(feature = "napi")]
+mod fluids;
+#[cfg(feature = "napi")]
+mod geometry;
+#[cfg(feature = "napi")]
+mod region;
+#[cfg(feature = "napi")]
+mod pathfinding;
+#[cfg(feature = "napi")]
+mod movement;
+#[cfg(feature = "napi")]
+mod navmesh;
+#[cfg(feature = "napi")]
+mod mining;
+#[cfg(feature = "napi")]
+mod block_data;
+#[cfg(feature = "napi")]
+mod dirty;
+#[cfg(feature = "napi")]
+mod hashing;
+#[cfg(feature = "napi")]
+mod watchers;
+#[cfg(feature = "napi")]
+mod events;
+#[cfg(feature = "napi")]
+mod transaction;
+#[cfg(feature = "napi")]
+mod serialization;
+#[cfg(feature = "napi")]
+mod sharing;
+#[cfg(feature = "napi")]
+mod chunk_batch;
+#[cfg(feature = "napi")]
+mod generator;
+#[cfg(feature = "napi")]
+mod inspect;
+#[cfg(feature = "napi")]
+mod frustum;
+#[cfg(feature = "napi")]
+mod surface;
+#[cfg(feature = "napi")]
+mod lighting;
+#[cfg(feature = "napi")]
+mod bedrock;
+#[cfg(feature = "napi")]
+mod formats;
+mod nbt;
+#[cfg(feature = "napi")]
+mod anvil;
+#[cfg(feature = "napi")]
+mod prefetch;
+#[cfg(feature = "napi")]
+mod universe;
+#[cfg(feature = "napi")]
+mod entities;
+#[cfg(feature = "napi")]
+mod placement;
+#[cfg(feature = "napi")]
+mod schematic;
+#[cfg(feature = "napi")]
+mod transform;
+mod matcher;
+#[cfg(feature = "napi")]
+mod flood;
+mod tags;
+#[cfg(feature = "napi")]
+mod farming;
+#[cfg(feature = "napi")]
+mod block_entities;
+#[cfg(feature = "napi")]
+mod writes;
+#[cfg(feature = "napi")]
+mod column_view;
+#[cfg(feature = "napi")]
+mod raycast_options;
+#[cfg(feature = "napi")]
+mod config;
+#[cfg(feature = "napi")]
+mod perf;
+#[cfg(feature = "napi")]
+mod cold;
+#[cfg(feature = "napi")]
+mod scan;
+#[cfg(feature = "napi")]
+mod column_reads;
+#[cfg(feature = "napi")]
+mod heightmap;
+#[cfg(feature = "napi")]
+mod occupancy;
+#[cfg(feature = "napi")]
+mod visibility;
+#[cfg(feature = "napi")]
+mod cone;
+#[cfg(feature = "napi")]
+mod faces;
+#[cfg(feature = "napi")]
+mod look;
+#[cfg(feature = "napi")]
+mod chunk_keys;
+#[cfg(feature = "napi")]
+mod borders;
+#[cfg(feature = "napi")]
+mod aging;
+#[cfg(feature = "napi")]
+mod compose;
+#[cfg(feature = "napi")]
+#[doc(hidden)]
+pub mod bench;
+
+#[cfg(feature = "rust-api")]
+pub mod api;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+// Pure-Rust parser entry points for fuzzing (see fuzz/)
+#[doc(hidden)]
+pub mod fuzz {
+    pub use crate::parsing::parse_column_bytes;
+}
 
 // No functions needed at the top level for this example,
 // everything is exposed via the NapiWorld class.
\ No newline at end of file
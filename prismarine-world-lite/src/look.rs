@@ -0,0 +1,32 @@
+// src/look.rs
+use crate::raycast::{self, look_angles, Vec3Arg};
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct LookAngles {
+    pub yaw: f64,   // Degrees, -180 to 180; 0 faces +Z (south), 90 faces -X (west)
+    pub pitch: f64, // Degrees, -90 (straight up) to 90 (straight down)
+}
+
+/// Unit view direction for a Minecraft yaw and pitch in degrees (yaw 0 faces +Z and 90 faces -X, positive pitch
+/// looks down), e.g. to raycast along an entity's line of sight.
+#[napi]
+pub fn direction_from_look(yaw: f64, pitch: f64) -> Vec3Arg {
+    raycast::direction_from_look(yaw, pitch).into()
+}
+
+/// Yaw and pitch in Minecraft's conventions (see `directionFromLook`) to look from one point at another, e.g.
+/// the eye position and a block face center to aim at. Looking straight up or down gives a yaw of -90, like
+/// the game.
+#[napi(ts_args_type = "from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }")]
+pub fn look_at(from: Vec3Arg, to: Vec3Arg) -> Result<LookAngles> {
+    let direction = DVec3::from(to) - DVec3::from(from);
+    if !direction.is_finite() || direction == DVec3::ZERO {
+        return Err(napi::Error::new(napi::Status::InvalidArg, "Cannot look at the point looked from".to_string()));
+    }
+    let (yaw, pitch) = look_angles(direction);
+    Ok(LookAngles { yaw, pitch })
+}
@@ -0,0 +1,66 @@
+// src/matcher.rs
+use crate::registry::BlockRegistry;
+#[cfg(feature = "napi")]
+use crate::world::NapiWorld;
+#[cfg(feature = "napi")]
+use napi::bindgen_prelude::*;
+use std::collections::HashSet;
+
+// A set of block states to look for, built from the state IDs and block names JS passes in.
+// A block name matches every state of the block, a `#tag` every state of every block in the tag.
+#[derive(Debug, Clone, Default)]
+pub struct BlockMatcher {
+    states: HashSet<u32>,
+}
+
+impl BlockMatcher {
+    pub fn matches(&self, state_id: u32) -> bool {
+        self.states.contains(&state_id)
+    }
+
+    // All states of the named block, if there is such a block
+    pub fn from_block_name(registry: &BlockRegistry, name: &str) -> Option<Self> {
+        let block = registry.block_by_name(name)?;
+        let min_state_id = block.min_state_id.unwrap_or(block.id << 4);
+        let max_state_id = block.max_state_id.unwrap_or(min_state_id + 15);
+        Some(BlockMatcher { states: (min_state_id..=max_state_id).collect() })
+    }
+
+    // All states of the blocks in a tag (`#minecraft:logs`, `#logs`), if the tag is known
+    pub fn from_tag(registry: &BlockRegistry, tag: &str) -> Option<Self> {
+        if !registry.has_tag(tag) { return None; }
+        let states = registry
+            .blocks_in_tag(tag)
+            .into_iter()
+            .flat_map(|block| {
+                let min_state_id = block.min_state_id.unwrap_or(block.id << 4);
+                min_state_id..=block.max_state_id.unwrap_or(min_state_id + 15)
+            })
+            .collect();
+        Some(BlockMatcher { states })
+    }
+
+    pub fn extend(&mut self, other: BlockMatcher) {
+        self.states.extend(other.states);
+    }
+}
+
+#[cfg(feature = "napi")]
+impl NapiWorld {
+    // Builds a matcher from state IDs, block names (with or without the `minecraft:` prefix) and `#` block tags
+    pub(crate) fn block_matcher(&self, blocks: Vec<Either<u32, String>>) -> Result<BlockMatcher> {
+        let mut matcher = BlockMatcher::default();
+        for block in blocks {
+            match block {
+                Either::A(state_id) => { matcher.states.insert(state_id); }
+                Either::B(tag) if tag.starts_with('#') => matcher.extend(BlockMatcher::from_tag(&self.registry, &tag).ok_or_else(|| {
+                    napi::Error::new(napi::Status::InvalidArg, format!("Unknown block tag: {}", tag))
+                })?),
+                Either::B(name) => matcher.extend(BlockMatcher::from_block_name(&self.registry, &name).ok_or_else(|| {
+                    napi::Error::new(napi::Status::InvalidArg, format!("Unknown block: {}", name))
+                })?),
+            }
+        }
+        Ok(matcher)
+    }
+}
@@ -0,0 +1,128 @@
+// src/mining.rs
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use minecraft_data_rs::models::block::Block;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+const TICK_MS: f64 = 50.0;
+
+// Mining speed of each tool tier when used on a block it's meant for
+const TOOL_TIER_SPEEDS: &[(&str, f64)] = &[
+    ("wooden", 2.0),
+    ("stone", 4.0),
+    ("iron", 6.0),
+    ("diamond", 8.0),
+    ("netherite", 9.0),
+    ("golden", 12.0),
+];
+
+// Speed multiplier of Mining Fatigue by level (1 to 4; higher levels count as 4)
+const FATIGUE_MULTIPLIERS: [f64; 4] = [0.3, 0.09, 0.0027, 0.00081];
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct EnchantmentArg {
+    pub name: String,
+    pub level: u32,
+}
+
+// Conditions for `get_dig_time`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct DigOptions {
+    pub tool_name: Option<String>,                  // Held item, e.g. "diamond_pickaxe" (default: empty hand)
+    pub enchantments: Option<Vec<EnchantmentArg>>,  // Only `efficiency` and `aqua_affinity` matter
+    pub efficiency_level: Option<u32>,              // Shortcut for an efficiency enchantment
+    pub haste_level: Option<u32>,                   // Haste / conduit power amplifier + 1
+    pub mining_fatigue_level: Option<u32>,          // Mining fatigue amplifier + 1
+    pub in_water: Option<bool>,                     // Head underwater (default false)
+    pub on_ground: Option<bool>,                    // Default true
+}
+
+// Whether the block can drop its item when mined with the tool (blocks without harvest tools always can)
+pub fn can_harvest_block(registry: &BlockRegistry, block: &Block, tool_name: Option<&str>) -> bool {
+    match &block.harvest_tools {
+        None => true,
+        Some(tools) => tool_name
+            .and_then(|name| registry.item_id(name))
+            .is_some_and(|id| tools.get(&id).copied().unwrap_or(false)),
+    }
+}
+
+// Speed multiplier of the tool against the block, before enchantments and effects
+pub fn tool_speed(block: &Block, tool_name: Option<&str>) -> f64 {
+    let Some(tool) = tool_name.map(|name| name.strip_prefix("minecraft:").unwrap_or(name)) else { return 1.0; };
+    let material = block.material.as_deref().unwrap_or("");
+
+    if tool == "shears" {
+        return match block.name.as_str() {
+            "cobweb" => 15.0,
+            name if name.ends_with("_wool") => 5.0,
+            _ if material.contains("leaves") => 15.0,
+            _ if material.contains("plant") || material.contains("vine_or_glow_lichen") => 2.0,
+            _ => 1.0,
+        };
+    }
+    if tool.ends_with("_sword") {
+        return if block.name == "cobweb" { 15.0 } else if material.contains("plant") || material.contains("gourd") { 1.5 } else { 1.0 };
+    }
+
+    // Tools are "<tier>_<kind>", effective on blocks tagged "mineable/<kind>"
+    let Some((tier, kind)) = tool.rsplit_once('_') else { return 1.0; };
+    if !material.split(';').any(|tag| tag == format!("mineable/{}", kind)) {
+        return 1.0;
+    }
+    TOOL_TIER_SPEEDS.iter().find(|(name, _)| *name == tier).map_or(1.0, |(_, speed)| *speed)
+}
+
+#[napi]
+impl NapiWorld {
+    /// Calculates how long breaking a block takes in milliseconds, using vanilla's formula with the block's hardness,
+    /// the held tool (tier speed, efficiency, whether it can harvest the block), haste/mining fatigue and the
+    /// underwater/airborne penalties. Instantly broken blocks take 0 ms; unbreakable ones return `Infinity`.
+    #[napi]
+    pub fn get_dig_time(&self, state_id: u32, options: Option<DigOptions>) -> Result<f64> {
+        let options = options.unwrap_or_default();
+        let block = self.registry.block(state_id).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Unknown block state ID: {}", state_id),
+        ))?;
+
+        let hardness = match block.hardness {
+            Some(h) if h >= 0.0 => h as f64,
+            _ => return Ok(f64::INFINITY), // Bedrock, barriers, ...
+        };
+        if hardness == 0.0 { return Ok(0.0); }
+
+        let tool = options.tool_name.as_deref();
+        let enchantment_level = |name: &str| {
+            options.enchantments.iter().flatten()
+                .find(|e| e.name.strip_prefix("minecraft:").unwrap_or(&e.name) == name)
+                .map_or(0, |e| e.level)
+        };
+
+        let mut speed = tool_speed(block, tool);
+        let efficiency = options.efficiency_level.unwrap_or(0).max(enchantment_level("efficiency"));
+        if speed > 1.0 && efficiency > 0 {
+            speed += (efficiency * efficiency + 1) as f64;
+        }
+        if let Some(haste) = options.haste_level.filter(|&l| l > 0) {
+            speed *= 1.0 + 0.2 * haste as f64;
+        }
+        if let Some(fatigue) = options.mining_fatigue_level.filter(|&l| l > 0) {
+            speed *= FATIGUE_MULTIPLIERS[fatigue.min(4) as usize - 1];
+        }
+        if options.in_water.unwrap_or(false) && enchantment_level("aqua_affinity") == 0 {
+            speed /= 5.0;
+        }
+        if !options.on_ground.unwrap_or(true) {
+            speed /= 5.0;
+        }
+
+        let divisor = if can_harvest_block(&self.registry, block, tool) { 30.0 } else { 100.0 };
+        let damage_per_tick = speed / hardness / divisor;
+        if damage_per_tick >= 1.0 { return Ok(0.0); }
+        Ok((1.0 / damage_per_tick).ceil() * TICK_MS)
+    }
+}
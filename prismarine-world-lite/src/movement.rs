@@ -0,0 +1,237 @@
+// src/movement.rs
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::raycast::Vec3Arg;
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi_derive::napi;
+
+// Player hitbox, the default agent size
+pub const PLAYER_WIDTH: f64 = 0.6;
+pub const PLAYER_HEIGHT: f64 = 1.8;
+// Vanilla step height, the tallest ledge walked up without jumping
+pub const PLAYER_STEP_HEIGHT: f64 = 0.6;
+// Distance between the samples `can_walk_line` checks
+const WALK_SAMPLE_SPACING: f64 = 0.1;
+// Tolerance for boxes that merely touch
+const COLLISION_EPSILON: f64 = 1e-7;
+
+// Size of the agent for standability checks
+#[napi(object)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AgentSize {
+    pub width: Option<f64>,  // Default 0.6
+    pub height: Option<f64>, // Default 1.8
+}
+
+impl AgentSize {
+    pub fn dimensions(size: Option<AgentSize>) -> (f64, f64) {
+        let size = size.unwrap_or_default();
+        (size.width.unwrap_or(PLAYER_WIDTH), size.height.unwrap_or(PLAYER_HEIGHT))
+    }
+}
+
+// Agent size and step height for `can_walk_line`
+#[napi(object)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalkOptions {
+    pub width: Option<f64>,       // Default 0.6
+    pub height: Option<f64>,      // Default 1.8
+    pub step_height: Option<f64>, // Default 0.6, also the largest drop allowed
+}
+
+#[napi(object)]
+pub struct StandPosition {
+    pub position: WorldCoords, // Block containing the agent's feet
+    pub feet_y: f64,           // Exact height the agent stands at
+}
+
+#[napi(object)]
+pub struct LandingPosition {
+    pub position: Vec3Arg,   // Where the falling box's bottom center comes to rest
+    pub block: WorldCoords,  // Block whose collision box it lands on
+    pub fall_distance: f64,
+}
+
+// Whether two boxes overlap by more than touching
+pub fn boxes_overlap(a_min: DVec3, a_max: DVec3, b_min: DVec3, b_max: DVec3) -> bool {
+    a_min.x < b_max.x - COLLISION_EPSILON && a_max.x > b_min.x + COLLISION_EPSILON
+        && a_min.y < b_max.y - COLLISION_EPSILON && a_max.y > b_min.y + COLLISION_EPSILON
+        && a_min.z < b_max.z - COLLISION_EPSILON && a_max.z > b_min.z + COLLISION_EPSILON
+}
+
+impl NapiWorld {
+    // World-space collision boxes of every block overlapping the given box
+    pub(crate) fn collision_boxes_in(&self, min: DVec3, max: DVec3) -> Vec<(DVec3, DVec3)> {
+        let lo = min.floor().as_ivec3();
+        let hi = (max - DVec3::splat(COLLISION_EPSILON)).floor().as_ivec3();
+        let mut boxes = Vec::new();
+        for x in lo.x..=hi.x {
+            for y in lo.y..=hi.y {
+                for z in lo.z..=hi.z {
+                    let state_id = self.get_block_state_id(x, y, z);
+                    if state_id == 0 { continue; }
+                    let origin = DVec3::new(x as f64, y as f64, z as f64);
+                    for shape in self.registry.collision_shapes(state_id) {
+                        boxes.push((origin + DVec3::new(shape[0], shape[1], shape[2]), origin + DVec3::new(shape[3], shape[4], shape[5])));
+                    }
+                }
+            }
+        }
+        boxes
+    }
+
+    // Height the agent's feet rest at when standing centered in the block `pos`, if it can stand there.
+    // The supporting surface has to lie within the feet block (e.g. full block below, slab or carpet in it, fence below).
+    pub(crate) fn standing_height(&self, pos: WorldCoords, width: f64, height: f64) -> Option<f64> {
+        let center = DVec3::new(pos.x as f64 + 0.5, pos.y as f64, pos.z as f64 + 0.5);
+        let half = width / 2.0;
+        let foot_min = DVec3::new(center.x - half, pos.y as f64 - 1.0, center.z - half);
+        let foot_max = DVec3::new(center.x + half, pos.y as f64 + 1.0, center.z + half);
+
+        let feet_y = self
+            .collision_boxes_in(foot_min, foot_max)
+            .into_iter()
+            .map(|(_, max)| max.y)
+            .filter(|&top| top >= pos.y as f64 - COLLISION_EPSILON && top < pos.y as f64 + 1.0)
+            .fold(None, |best: Option<f64>, top| Some(best.map_or(top, |b| b.max(top))))?;
+
+        let body_min = DVec3::new(center.x - half, feet_y, center.z - half);
+        let body_max = DVec3::new(center.x + half, feet_y + height, center.z + half);
+        let blocked = self
+            .collision_boxes_in(body_min, body_max)
+            .into_iter()
+            .any(|(min, max)| boxes_overlap(body_min, body_max, min, max));
+        (!blocked).then_some(feet_y)
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Checks whether an agent can walk in a straight line between two feet positions without jumping or falling.
+    ///
+    /// The agent's bounding box is swept along the ground in small steps: at every step there has to be ground under it
+    /// that is at most `stepHeight` above or below the previous step, and nothing may intersect its body.
+    /// Used for smoothing paths (string pulling) after `find_path`.
+    #[napi(ts_args_type = "from: { x: number, y: number, z: number }, to: { x: number, y: number, z: number }, options?: WalkOptions | null")]
+    pub fn can_walk_line(&self, from: Vec3Arg, to: Vec3Arg, options: Option<WalkOptions>) -> bool {
+        let options = options.unwrap_or_default();
+        let half = options.width.unwrap_or(PLAYER_WIDTH) / 2.0;
+        let height = options.height.unwrap_or(PLAYER_HEIGHT);
+        let step_height = options.step_height.unwrap_or(PLAYER_STEP_HEIGHT);
+
+        let from = DVec3::from(from);
+        let to = DVec3::from(to);
+        let horizontal = DVec3::new(to.x - from.x, 0.0, to.z - from.z);
+        let steps = (horizontal.length() / WALK_SAMPLE_SPACING).ceil().max(1.0) as usize;
+
+        let mut feet_y = from.y;
+        for i in 0..=steps {
+            let p = from + horizontal * (i as f64 / steps as f64);
+            let foot_min = DVec3::new(p.x - half, feet_y - step_height, p.z - half);
+            let foot_max = DVec3::new(p.x + half, feet_y + step_height, p.z + half);
+
+            // Highest ground under the footprint within step range
+            let ground = self
+                .collision_boxes_in(foot_min, foot_max)
+                .into_iter()
+                .filter(|(min, max)| min.x < foot_max.x && max.x > foot_min.x && min.z < foot_max.z && max.z > foot_min.z)
+                .map(|(_, max)| max.y)
+                .filter(|&top| top >= feet_y - step_height - COLLISION_EPSILON && top <= feet_y + step_height + COLLISION_EPSILON)
+                .fold(None, |best: Option<f64>, top| Some(best.map_or(top, |b| b.max(top))));
+            let Some(ground) = ground else { return false; };
+            feet_y = ground;
+
+            let body_min = DVec3::new(p.x - half, feet_y, p.z - half);
+            let body_max = DVec3::new(p.x + half, feet_y + height, p.z + half);
+            if self
+                .collision_boxes_in(body_min, body_max)
+                .into_iter()
+                .any(|(min, max)| boxes_overlap(body_min, body_max, min, max))
+            {
+                return false;
+            }
+        }
+        // The line has to end at the requested height, not just above or below it
+        (feet_y - to.y).abs() <= step_height
+    }
+
+    /// Checks whether an agent (default 0.6 wide, 1.8 tall) centered in the block can stand with its feet in it:
+    /// a collision surface inside the feet block to stand on, and no collision boxes intersecting its body.
+    #[napi]
+    pub fn can_stand_at(&self, x: i32, y: i32, z: i32, size: Option<AgentSize>) -> bool {
+        let (width, height) = AgentSize::dimensions(size);
+        self.standing_height(WorldCoords { x, y, z }, width, height).is_some()
+    }
+
+    /// Finds where a box (default 0.6 wide, like a player) falling straight down from the bottom center position
+    /// comes to rest: the highest collision surface under its footprint, including partial blocks like slabs,
+    /// carpets and fences. Liquids and blocks without collision are fallen through. Returns `null` if it would fall
+    /// out of the world or into an unloaded column, or if it starts below the world. Positions above the world
+    /// fall from its top. Only `width` of the size is used.
+    #[napi]
+    pub fn get_landing_position(&self, x: f64, y: f64, z: f64, size: Option<AgentSize>) -> Option<LandingPosition> {
+        if y.is_nan() || y < self.config.min_y as f64 { return None; }
+        let (width, _) = AgentSize::dimensions(size);
+        let half = width / 2.0;
+        let (foot_min_x, foot_max_x, foot_min_z, foot_max_z) = (x - half, x + half, z - half, z + half);
+
+        for chunk_x in (foot_min_x.floor() as i32) >> 4..=((foot_max_x - COLLISION_EPSILON).floor() as i32) >> 4 {
+            for chunk_z in (foot_min_z.floor() as i32) >> 4..=((foot_max_z - COLLISION_EPSILON).floor() as i32) >> 4 {
+                if !self.ensure_column(ChunkCoords { x: chunk_x, z: chunk_z }) { return None; }
+            }
+        }
+
+        // Walk down a block layer at a time, looking for surfaces whose top lies in the layer. The layer below is
+        // included for boxes taller than a block (fences, walls).
+        let mut layer = (y.floor() as i32).min(self.config.min_y + self.config.height - 1);
+        while layer >= self.config.min_y {
+            let query_min = DVec3::new(foot_min_x, layer as f64 - 1.0, foot_min_z);
+            let query_max = DVec3::new(foot_max_x, layer as f64 + 1.0, foot_max_z);
+            let landing = self
+                .collision_boxes_in(query_min, query_max)
+                .into_iter()
+                .filter(|(min, max)| {
+                    min.x < foot_max_x - COLLISION_EPSILON && max.x > foot_min_x + COLLISION_EPSILON
+                        && min.z < foot_max_z - COLLISION_EPSILON && max.z > foot_min_z + COLLISION_EPSILON
+                })
+                .filter(|(_, max)| max.y >= layer as f64 && max.y < layer as f64 + 1.0 && max.y <= y + COLLISION_EPSILON)
+                .max_by(|(_, a), (_, b)| a.y.total_cmp(&b.y));
+
+            if let Some((min, max)) = landing {
+                return Some(LandingPosition {
+                    position: Vec3Arg { x, y: max.y, z },
+                    block: WorldCoords { x: min.x.floor() as i32, y: min.y.floor() as i32, z: min.z.floor() as i32 },
+                    fall_distance: y - max.y,
+                });
+            }
+            layer -= 1;
+        }
+        None
+    }
+
+    /// Finds the standable block closest to `point` within `radius` blocks (at most 64; see `can_stand_at`).
+    #[napi]
+    pub fn find_nearest_standable(&self, point: WorldCoords, radius: u32, size: Option<AgentSize>) -> Option<StandPosition> {
+        let (width, height) = AgentSize::dimensions(size);
+        let r = radius.min(64) as i64;
+
+        let mut candidates = Vec::new();
+        for dx in -r..=r {
+            for dy in -r..=r {
+                for dz in -r..=r {
+                    let distance_sq = dx * dx + dy * dy + dz * dz;
+                    if distance_sq > r * r { continue; }
+                    let coords = (i32::try_from(point.x as i64 + dx), i32::try_from(point.y as i64 + dy), i32::try_from(point.z as i64 + dz));
+                    if let (Ok(x), Ok(y), Ok(z)) = coords {
+                        candidates.push((distance_sq, WorldCoords { x, y, z }));
+                    }
+                }
+            }
+        }
+        candidates.sort_by_key(|(distance_sq, _)| *distance_sq);
+
+        candidates.into_iter().find_map(|(_, pos)| {
+            self.standing_height(pos, width, height).map(|feet_y| StandPosition { position: pos, feet_y })
+        })
+    }
+}
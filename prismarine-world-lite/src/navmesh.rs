@@ -0,0 +1,133 @@
+// src/navmesh.rs
+use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::movement::{PLAYER_HEIGHT, PLAYER_WIDTH};
+use crate::pathfinding::{MovementRules, PathOptions};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::{HashMap, HashSet};
+
+// A standable position and the moves leading out of it
+#[derive(Debug, Clone)]
+pub struct NavNode {
+    pub position: WorldCoords,
+    pub feet_y: f64,
+    pub links: Vec<(WorldCoords, f64)>,
+}
+
+// Walkable cells of one chunk, grouped by block column so updates can recompute single columns
+#[derive(Debug, Clone, Default)]
+pub struct ChunkNavmesh {
+    columns: HashMap<(i32, i32), Vec<NavNode>>,
+}
+
+#[napi(object)]
+pub struct NavCell {
+    pub position: WorldCoords, // Block containing the agent's feet
+    pub feet_y: f64,
+}
+
+#[napi(object)]
+pub struct NavLink {
+    pub from: u32,        // Index into `cells`
+    pub to: WorldCoords,  // Target cell, which may lie in a neighboring chunk
+    pub cost: f64,
+}
+
+#[napi(object)]
+pub struct NavmeshData {
+    pub cells: Vec<NavCell>,
+    pub links: Vec<NavLink>,
+}
+
+impl NapiWorld {
+    // Walkable nodes in a single block column, with their outgoing moves
+    fn build_navmesh_column(&self, x: i32, z: i32) -> Vec<NavNode> {
+        let rules = MovementRules::from(&PathOptions::default());
+        let mut nodes = Vec::new();
+        for y in MIN_CHUNK_Y..MAX_CHUNK_Y {
+            // Only positions with something to stand on in or below the feet block can be walkable
+            let pos = WorldCoords { x, y, z };
+            if self.is_passable(WorldCoords { y: y - 1, ..pos }) && self.is_passable(pos) { continue; }
+
+            if let Some(feet_y) = self.standing_height(pos, PLAYER_WIDTH, PLAYER_HEIGHT) {
+                nodes.push(NavNode { position: pos, feet_y, links: self.movement_neighbors(pos, &rules) });
+            }
+        }
+        nodes
+    }
+
+    fn build_chunk_navmesh(&self, chunk: ChunkCoords) -> ChunkNavmesh {
+        let mut navmesh = ChunkNavmesh::default();
+        for x in (chunk.x << 4)..(chunk.x << 4) + 16 {
+            for z in (chunk.z << 4)..(chunk.z << 4) + 16 {
+                navmesh.columns.insert((x, z), self.build_navmesh_column(x, z));
+            }
+        }
+        navmesh
+    }
+
+    // Recomputes the cached navmesh columns affected by block changes. Moves only span one block
+    // horizontally, so the changed columns and their direct neighbors are enough.
+    pub(crate) fn update_navmeshes(&self, changed: &[WorldCoords]) {
+        if self.navmeshes.is_empty() { return; }
+
+        let mut affected = HashSet::new();
+        for pos in changed {
+            for dx in -1..=1 {
+                for dz in -1..=1 {
+                    affected.insert((pos.x + dx, pos.z + dz));
+                }
+            }
+        }
+        for (x, z) in affected {
+            let chunk = ChunkCoords { x: x >> 4, z: z >> 4 };
+            if !self.navmeshes.contains_key(&chunk) { continue; }
+            let nodes = self.build_navmesh_column(x, z);
+            if let Some(mut navmesh) = self.navmeshes.get_mut(&chunk) {
+                navmesh.columns.insert((x, z), nodes);
+            }
+        }
+    }
+
+    // Drops cached navmeshes whose links may cross into a chunk that was loaded or unloaded
+    pub(crate) fn invalidate_navmeshes_near(&self, chunk: ChunkCoords) {
+        for (dx, dz) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+            self.navmeshes.remove(&ChunkCoords { x: chunk.x + dx, z: chunk.z + dz });
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Builds (or returns the cached) navigation mesh of a chunk: every cell a player-sized agent can stand in,
+    /// plus the walk/jump/fall links between cells using `find_path`'s default movement rules.
+    ///
+    /// Cached meshes are updated column by column as blocks change, and dropped when the chunk or a neighbor is (un)loaded.
+    #[napi]
+    pub fn build_navmesh(&self, chunk_x: i32, chunk_z: i32) -> Result<NavmeshData> {
+        let chunk = ChunkCoords { x: chunk_x, z: chunk_z };
+        if !self.ensure_column(chunk) {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Chunk at {}, {} not loaded", chunk_x, chunk_z),
+            ));
+        }
+
+        if !self.navmeshes.contains_key(&chunk) {
+            let navmesh = self.build_chunk_navmesh(chunk);
+            self.navmeshes.insert(chunk, navmesh);
+        }
+        let navmesh = self.navmeshes.get(&chunk).map(|n| n.clone()).unwrap_or_default();
+
+        let mut data = NavmeshData { cells: Vec::new(), links: Vec::new() };
+        for node in navmesh.columns.values().flatten() {
+            let index = data.cells.len() as u32;
+            data.cells.push(NavCell { position: node.position, feet_y: node.feet_y });
+            for &(to, cost) in &node.links {
+                data.links.push(NavLink { from: index, to, cost });
+            }
+        }
+        Ok(data)
+    }
+}
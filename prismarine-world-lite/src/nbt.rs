@@ -0,0 +1,299 @@
+// src/nbt.rs
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+// Java Edition NBT (big endian), as used by region files.
+// Compounds keep their entries in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+// Deeper nesting than this is rejected rather than risking a stack overflow on hostile input
+const MAX_DEPTH: usize = 512;
+
+impl Tag {
+    // Entry of a compound by name
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(entries) => entries.iter().find(|(key, _)| key == name).map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    // Any integer tag, widened
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Tag::Byte(v) => Some(v as i64),
+            Tag::Short(v) => Some(v as i64),
+            Tag::Int(v) => Some(v as i64),
+            Tag::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self {
+            Tag::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&[(String, Tag)]> {
+        match self {
+            Tag::Compound(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_long_array(&self) -> Option<&[i64]> {
+        match self {
+            Tag::LongArray(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn type_id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(_) => 9,
+            Tag::Compound(_) => 10,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+// Reads an array length, refusing lengths that can't fit in the rest of the buffer
+fn read_len(cursor: &mut Cursor<&[u8]>, element_size: usize) -> std::io::Result<usize> {
+    let len = cursor.read_i32::<BigEndian>()?;
+    let remaining = cursor.get_ref().len() as u64 - cursor.position().min(cursor.get_ref().len() as u64);
+    if len < 0 || len as u64 * element_size as u64 > remaining { return Err(invalid("NBT array length past end of data")); }
+    Ok(len as usize)
+}
+
+fn read_string(cursor: &mut Cursor<&[u8]>) -> std::io::Result<String> {
+    let len = cursor.read_u16::<BigEndian>()? as usize;
+    let mut bytes = vec![0u8; len];
+    cursor.read_exact(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn read_payload(cursor: &mut Cursor<&[u8]>, type_id: u8, depth: usize) -> std::io::Result<Tag> {
+    if depth > MAX_DEPTH { return Err(invalid("NBT nested too deeply")); }
+    Ok(match type_id {
+        1 => Tag::Byte(cursor.read_i8()?),
+        2 => Tag::Short(cursor.read_i16::<BigEndian>()?),
+        3 => Tag::Int(cursor.read_i32::<BigEndian>()?),
+        4 => Tag::Long(cursor.read_i64::<BigEndian>()?),
+        5 => Tag::Float(cursor.read_f32::<BigEndian>()?),
+        6 => Tag::Double(cursor.read_f64::<BigEndian>()?),
+        7 => {
+            let len = read_len(cursor, 1)?;
+            Tag::ByteArray((0..len).map(|_| cursor.read_i8()).collect::<std::io::Result<_>>()?)
+        }
+        8 => Tag::String(read_string(cursor)?),
+        9 => {
+            let item_type = cursor.read_u8()?;
+            let len = read_len(cursor, if item_type == 0 { 0 } else { 1 })?;
+            if item_type == 0 {
+                Tag::List(Vec::new())
+            } else {
+                Tag::List((0..len).map(|_| read_payload(cursor, item_type, depth + 1)).collect::<std::io::Result<_>>()?)
+            }
+        }
+        10 => {
+            let mut entries = Vec::new();
+            loop {
+                let entry_type = cursor.read_u8()?;
+                if entry_type == 0 { break; }
+                let name = read_string(cursor)?;
+                entries.push((name, read_payload(cursor, entry_type, depth + 1)?));
+            }
+            Tag::Compound(entries)
+        }
+        11 => {
+            let len = read_len(cursor, 4)?;
+            Tag::IntArray((0..len).map(|_| cursor.read_i32::<BigEndian>()).collect::<std::io::Result<_>>()?)
+        }
+        12 => {
+            let len = read_len(cursor, 8)?;
+            Tag::LongArray((0..len).map(|_| cursor.read_i64::<BigEndian>()).collect::<std::io::Result<_>>()?)
+        }
+        _ => return Err(invalid("Unknown NBT tag type")),
+    })
+}
+
+// Reads an uncompressed NBT document, whose root must be a named compound
+pub fn read_nbt(data: &[u8]) -> std::io::Result<Tag> {
+    let mut cursor = Cursor::new(data);
+    if cursor.read_u8()? != 10 { return Err(invalid("NBT root is not a compound")); }
+    read_string(&mut cursor)?;
+    read_payload(&mut cursor, 10, 0)
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(u16::MAX as usize);
+    out.write_u16::<BigEndian>(len as u16).unwrap();
+    out.extend_from_slice(&bytes[..len]);
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(v) => out.push(*v as u8),
+        Tag::Short(v) => out.write_i16::<BigEndian>(*v).unwrap(),
+        Tag::Int(v) => out.write_i32::<BigEndian>(*v).unwrap(),
+        Tag::Long(v) => out.write_i64::<BigEndian>(*v).unwrap(),
+        Tag::Float(v) => out.write_f32::<BigEndian>(*v).unwrap(),
+        Tag::Double(v) => out.write_f64::<BigEndian>(*v).unwrap(),
+        Tag::ByteArray(values) => {
+            out.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            out.extend(values.iter().map(|&v| v as u8));
+        }
+        Tag::String(s) => write_string(out, s),
+        Tag::List(items) => {
+            out.push(items.first().map_or(0, Tag::type_id));
+            out.write_i32::<BigEndian>(items.len() as i32).unwrap();
+            for item in items {
+                write_payload(out, item);
+            }
+        }
+        Tag::Compound(entries) => {
+            for (name, entry) in entries {
+                out.push(entry.type_id());
+                write_string(out, name);
+                write_payload(out, entry);
+            }
+            out.push(0);
+        }
+        Tag::IntArray(values) => {
+            out.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            for v in values {
+                out.write_i32::<BigEndian>(*v).unwrap();
+            }
+        }
+        Tag::LongArray(values) => {
+            out.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            for v in values {
+                out.write_i64::<BigEndian>(*v).unwrap();
+            }
+        }
+    }
+}
+
+// Writes an uncompressed NBT document with an unnamed root compound
+pub fn write_nbt(root: &Tag) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.push(10);
+    write_string(&mut out, "");
+    write_payload(&mut out, root);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One tag of every type, nested
+    fn sample() -> Tag {
+        Tag::Compound(vec![
+            ("byte".to_string(), Tag::Byte(-3)),
+            ("short".to_string(), Tag::Short(-300)),
+            ("int".to_string(), Tag::Int(70_000)),
+            ("long".to_string(), Tag::Long(-5_000_000_000)),
+            ("float".to_string(), Tag::Float(1.5)),
+            ("double".to_string(), Tag::Double(-0.25)),
+            ("bytes".to_string(), Tag::ByteArray(vec![1, -1, 0])),
+            ("string".to_string(), Tag::String("héllo".to_string())),
+            ("empty".to_string(), Tag::List(Vec::new())),
+            ("list".to_string(), Tag::List(vec![Tag::Compound(vec![("a".to_string(), Tag::Int(1))]), Tag::Compound(Vec::new())])),
+            ("ints".to_string(), Tag::IntArray(vec![i32::MIN, 0, i32::MAX])),
+            ("longs".to_string(), Tag::LongArray(vec![i64::MIN, 1])),
+        ])
+    }
+
+    #[test]
+    fn documents_round_trip() {
+        let data = write_nbt(&sample());
+        assert_eq!(read_nbt(&data).unwrap(), sample());
+        assert_eq!(write_nbt(&read_nbt(&data).unwrap()), data);
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected_without_panicking() {
+        let data = write_nbt(&sample());
+        for len in 0..data.len() {
+            assert!(read_nbt(&data[..len]).is_err(), "prefix of {} bytes", len);
+        }
+
+        let document = |payload: &[u8]| {
+            let mut out = vec![10, 0, 0];
+            out.extend_from_slice(payload);
+            out
+        };
+        assert!(read_nbt(&[8, 0, 0, 0, 0]).is_err(), "root is not a compound");
+        assert!(read_nbt(&document(&[13, 0, 1, b'x', 0])).is_err(), "unknown tag type");
+        assert!(read_nbt(&document(&[7, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0])).is_err(), "negative length");
+        assert!(read_nbt(&document(&[12, 0, 0, 0x7F, 0xFF, 0xFF, 0xFF, 0])).is_err(), "length past the end");
+        assert!(read_nbt(&document(&[9, 0, 0, 10, 0x7F, 0xFF, 0xFF, 0xFF, 0])).is_err(), "list length past the end");
+        assert_eq!(read_nbt(&document(&[9, 0, 0, 0, 0x7F, 0xFF, 0xFF, 0xFF, 0])).unwrap(), Tag::Compound(vec![(String::new(), Tag::List(Vec::new()))]));
+
+        // Nesting is limited rather than overflowing the stack
+        let nested_lists = |depth: usize| {
+            let mut out = document(&[9, 0, 0]);
+            for _ in 0..depth {
+                out.extend_from_slice(&[9, 0, 0, 0, 1]);
+            }
+            out.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+            out
+        };
+        assert!(read_nbt(&nested_lists(MAX_DEPTH - 1)).is_ok());
+        assert!(read_nbt(&nested_lists(100_000)).is_err());
+
+        let mut seed = 0x6a09_e667_f3bc_c908u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..2_000 {
+            let mut corrupted = data.clone();
+            for _ in 0..1 + next() % 4 {
+                let index = (next() % corrupted.len() as u64) as usize;
+                corrupted[index] = next() as u8;
+            }
+            let _ = read_nbt(&corrupted);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// src/occupancy.rs
+use crate::coords::{ChunkCoords, SectionCoords, WorldCoords, MAX_SECTION_Y, MIN_SECTION_Y};
+use crate::world::NapiWorld;
+use dashmap::DashMap;
+use napi_derive::napi;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+// Per-section occupancy masks (see `ChunkSection::occupancy`) for rays to skip empty space, built as rays reach the
+// sections and dropped as their blocks change
+#[derive(Default)]
+pub struct OccupancyCache {
+    enabled: AtomicBool,
+    sections: DashMap<SectionCoords, u64>,
+    // Bumped on every invalidation, so a mask computed from blocks that changed meanwhile isn't kept
+    generation: AtomicU64,
+}
+
+impl OccupancyCache {
+    fn invalidate(&self, sections: impl IntoIterator<Item = SectionCoords>) {
+        for section in sections {
+            self.sections.remove(&section);
+        }
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+impl NapiWorld {
+    // Occupancy of a section of a loaded column, from the cache or computed and cached. `None` while ray
+    // acceleration is off, or if the column isn't loaded or is being written.
+    pub(crate) fn section_occupancy(&self, section: SectionCoords) -> Option<u64> {
+        let cache = &self.occupancy;
+        if !cache.enabled.load(Ordering::Relaxed) { return None; }
+        if let Some(mask) = cache.sections.get(&section) {
+            return Some(*mask);
+        }
+
+        let generation = cache.generation.load(Ordering::Acquire);
+        let mask = {
+            let entry = self.columns.get(&ChunkCoords { x: section.x, z: section.z })?;
+            let column = entry.value().try_read().ok()?;
+            column.get_section(section.y).map_or(0, |s| s.occupancy())
+        };
+        cache.sections.insert(section, mask);
+        if cache.generation.load(Ordering::Acquire) != generation {
+            cache.sections.remove(&section);
+        }
+        Some(mask)
+    }
+
+    pub(crate) fn update_occupancy(&self, changed: &[WorldCoords]) {
+        if !self.occupancy.enabled.load(Ordering::Relaxed) { return; }
+        self.occupancy.invalidate(changed.iter().map(|pos| SectionCoords { x: pos.x >> 4, y: pos.y >> 4, z: pos.z >> 4 }));
+    }
+
+    pub(crate) fn raycast_acceleration(&self) -> bool {
+        self.occupancy.enabled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn forget_column_occupancy(&self, coords: ChunkCoords) {
+        if !self.occupancy.enabled.load(Ordering::Relaxed) { return; }
+        self.occupancy.invalidate((MIN_SECTION_Y..=MAX_SECTION_Y).map(|y| SectionCoords { x: coords.x, y, z: coords.z }));
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Turns empty-space skipping for raycasts on or off (off by default). When on, the world keeps a coarse
+    /// map of which 4x4x4 cells of each section hold any block, built as rays reach them, and rays step through
+    /// empty cells and sections without reading their blocks, which speeds up long rays through open air (sky to
+    /// ground, long horizontal rays). Results are the same either way; `raycastDebug` never skips. The setting and the
+    /// map are shared by worlds operating on the same columns (`fromColumns`, `fromHandle`).
+    #[napi]
+    pub fn set_raycast_acceleration(&self, enabled: bool) {
+        self.occupancy.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.occupancy.sections.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::ChunkColumn;
+    use crate::coords::{ChunkCoords, SectionCoords};
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::{ColumnStore, NapiWorld};
+    use std::sync::Arc;
+
+    #[test]
+    fn writes_through_other_worlds_drop_cached_masks() {
+        let store = ColumnStore::default();
+        let (registry, biomes) = (Arc::new(test_registry()), Arc::new(test_biomes()));
+        let world = NapiWorld::from_parts(store.clone(), Arc::clone(&registry), Arc::clone(&biomes));
+        let other = NapiWorld::from_parts(store, registry, biomes);
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, ChunkColumn::new());
+        world.set_raycast_acceleration(true);
+
+        let section = SectionCoords { x: 0, y: 4, z: 0 };
+        assert_eq!(world.section_occupancy(section), Some(0));
+        assert!(other.set_block_state_id(5, 70, 5, 1).unwrap());
+        assert_ne!(world.section_occupancy(section), Some(0));
+    }
+}
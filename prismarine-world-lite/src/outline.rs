@@ -0,0 +1,71 @@
+// src/outline.rs
+use crate::registry::{BlockRegistry, ShapeBox, FULL_CUBE};
+
+// Outline (selection) boxes: the boxes the game draws the black wireframe around and targets with the crosshair.
+// minecraft-data only ships collision shapes, so outlines are derived from them, with the vanilla outlines of the
+// common blocks whose outline differs: plants, torches, rails and the like have no collision but an outline,
+// fences and walls collide higher than they outline.
+
+// A box given in pixels (sixteenths of a block), like the game's `Block.box`
+fn pixels(min_x: f64, min_y: f64, min_z: f64, max_x: f64, max_y: f64, max_z: f64) -> ShapeBox {
+    [min_x / 16.0, min_y / 16.0, min_z / 16.0, max_x / 16.0, max_y / 16.0, max_z / 16.0]
+}
+
+const FLOWERS: &[&str] = &[
+    "dandelion", "poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip", "orange_tulip", "white_tulip",
+    "pink_tulip", "oxeye_daisy", "cornflower", "lily_of_the_valley", "wither_rose", "torchflower",
+];
+
+impl BlockRegistry {
+    // Outline boxes of a state relative to the block origin; empty for air and fluids
+    pub fn outline_shapes(&self, state_id: u32) -> Vec<ShapeBox> {
+        let Some(block) = self.block(state_id) else { return Vec::new(); };
+        let name = block.name.as_str();
+        let property = |name: &str| self.state_property(state_id, name);
+        let age = || property("age").and_then(|age| age.parse::<f64>().ok()).unwrap_or(0.0);
+
+        let outline = match name {
+            "air" | "cave_air" | "void_air" | "water" | "lava" | "bubble_column" | "light" => return Vec::new(),
+            _ if FLOWERS.contains(&name) => pixels(5.0, 0.0, 5.0, 11.0, 10.0, 11.0),
+            _ if name.ends_with("_sapling") => pixels(2.0, 0.0, 2.0, 14.0, 12.0, 14.0),
+            "grass" | "short_grass" | "fern" | "dead_bush" => pixels(2.0, 0.0, 2.0, 14.0, 13.0, 14.0),
+            "sugar_cane" => pixels(2.0, 0.0, 2.0, 14.0, 16.0, 14.0),
+            "torch" | "soul_torch" | "redstone_torch" => pixels(6.0, 0.0, 6.0, 10.0, 10.0, 10.0),
+            "wall_torch" | "soul_wall_torch" | "redstone_wall_torch" => match property("facing").as_deref() {
+                Some("south") => pixels(5.5, 3.0, 0.0, 10.5, 13.0, 5.0),
+                Some("west") => pixels(11.0, 3.0, 5.5, 16.0, 13.0, 10.5),
+                Some("east") => pixels(0.0, 3.0, 5.5, 5.0, 13.0, 10.5),
+                _ => pixels(5.5, 3.0, 11.0, 10.5, 13.0, 16.0),
+            },
+            "redstone_wire" | "fire" | "soul_fire" => pixels(0.0, 0.0, 0.0, 16.0, 1.0, 16.0),
+            "rail" | "powered_rail" | "detector_rail" | "activator_rail" => {
+                let ascending = property("shape").is_some_and(|shape| shape.starts_with("ascending"));
+                pixels(0.0, 0.0, 0.0, 16.0, if ascending { 8.0 } else { 2.0 }, 16.0)
+            }
+            "wheat" => pixels(0.0, 0.0, 0.0, 16.0, (age() + 1.0) * 2.0, 16.0),
+            "carrots" | "potatoes" => pixels(0.0, 0.0, 0.0, 16.0, age() + 2.0, 16.0),
+            "beetroots" => pixels(0.0, 0.0, 0.0, 16.0, age() * 2.0 + 2.0, 16.0),
+            "snow" => {
+                let layers = property("layers").and_then(|layers| layers.parse::<f64>().ok()).unwrap_or(1.0);
+                pixels(0.0, 0.0, 0.0, 16.0, layers * 2.0, 16.0)
+            }
+            "structure_void" => pixels(5.0, 5.0, 5.0, 11.0, 11.0, 11.0),
+            "scaffolding" => FULL_CUBE[0],
+            _ if name.ends_with("_fence_gate") && property("open").as_deref() == Some("true") => {
+                match property("facing").as_deref() {
+                    Some("east") | Some("west") => pixels(6.0, 0.0, 0.0, 10.0, 16.0, 16.0),
+                    _ => pixels(0.0, 0.0, 6.0, 16.0, 16.0, 10.0),
+                }
+            }
+            // Fences, walls and closed gates collide 1.5 blocks high but outline one block
+            _ if name.ends_with("_fence") || name.ends_with("_wall") || name.ends_with("_fence_gate") => {
+                return self.collision_shapes(state_id).iter().map(|s| [s[0], s[1], s[2], s[3], s[4].min(1.0), s[5]]).collect();
+            }
+            _ => {
+                let shapes = self.collision_shapes(state_id);
+                return if shapes.is_empty() { FULL_CUBE.to_vec() } else { shapes.to_vec() };
+            }
+        };
+        vec![outline]
+    }
+}
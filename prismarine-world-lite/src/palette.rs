@@ -1,5 +1,6 @@
 // src/palette.rs
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 
 use crate::coords::SECTION_WIDTH;
@@ -56,33 +57,27 @@ impl BitArray {
         }
     }
 
-    // --- GET METHOD WITH SPECIFIC DEBUGGING ---
+    // Like `from_data`, but returns None instead of panicking when the data doesn't fit (for untrusted input)
+    pub fn try_from_data(bits_per_value: usize, capacity: usize, data: Vec<u64>) -> Option<Self> {
+        if bits_per_value == 0 || bits_per_value > 64 { return None; }
+        let values_per_long = BITS_PER_LONG / bits_per_value;
+        if data.len() != capacity.div_ceil(values_per_long) { return None; }
+        Some(BitArray {
+            data,
+            bits_per_value,
+            values_per_long,
+            capacity,
+            value_mask: if bits_per_value == 64 { u64::MAX } else { (1u64 << bits_per_value) - 1 },
+        })
+    }
+
+    // --- GET METHOD ---
     pub fn get(&self, index: usize) -> u32 {
         assert!(index < self.capacity, "Index out of bounds: {} >= {}", index, self.capacity);
         let bit_index = index * self.bits_per_value;
         let long_index_start = bit_index / BITS_PER_LONG;
         let bit_index_start = bit_index % BITS_PER_LONG; // Index within the first long (0-63)
 
-        // --- Specific Debugging for index 3814 when bits_per_value is 5 ---
-        let is_target_for_debug = index == 3814 && self.bits_per_value == 5;
-        if is_target_for_debug {
-            eprintln!("[BitArray::get DEBUG index=3814, bits=5]");
-            eprintln!("  bit_index: {}", bit_index); // Should be 19070
-            eprintln!("  long_index_start: {}", long_index_start); // Should be 297
-            eprintln!("  bit_index_start: {}", bit_index_start); // Should be 62
-            if long_index_start < self.data.len() {
-                eprintln!("  data[{}]: {:#018x}", long_index_start, self.data[long_index_start]);
-            } else {
-                 eprintln!("  data[{}] is out of bounds!", long_index_start);
-            }
-            if long_index_start + 1 < self.data.len() {
-                 eprintln!("  data[{}]: {:#018x}", long_index_start + 1, self.data[long_index_start + 1]);
-            } else {
-                 eprintln!("  data[{}] is out of bounds!", long_index_start + 1);
-            }
-        }
-        // --- End Specific Debugging ---
-
         if long_index_start >= self.data.len() {
              eprintln!("[BitArray::get] ERROR: long_index_start out of bounds ({} >= {}). Index: {}, bits_per_value: {}", long_index_start, self.data.len(), index, self.bits_per_value);
              return 0;
@@ -91,12 +86,9 @@ impl BitArray {
         let current_long = self.data[long_index_start];
         let bits_remaining_in_long = BITS_PER_LONG - bit_index_start;
 
-        let result: u64;
-
-        if bits_remaining_in_long >= self.bits_per_value {
+        let result = if bits_remaining_in_long >= self.bits_per_value {
             // Value fits entirely within the current long
-            result = (current_long >> bit_index_start) & self.value_mask;
-             if is_target_for_debug { eprintln!("  Fits in one long. Intermediate result: {:#x}", result); }
+            (current_long >> bit_index_start) & self.value_mask
         } else {
             // Value spans across two longs
             let bits_from_first = bits_remaining_in_long;
@@ -117,24 +109,31 @@ impl BitArray {
             };
 
             // Combine the parts
-            result = first_part | (second_part << bits_from_first);
-            if is_target_for_debug {
-                eprintln!("  Spans longs.");
-                eprintln!("    bits_from_first: {}", bits_from_first); // Should be 2
-                eprintln!("    bits_from_second: {}", bits_from_second); // Should be 3
-                eprintln!("    first_part (shifted current_long): {:#x}", first_part);
-                eprintln!("    second_part (masked next_long): {:#x}", second_part);
-                eprintln!("    combined result before final mask: {:#x}", result);
-            }
-        }
+            first_part | (second_part << bits_from_first)
+        };
 
-        let final_result = (result & self.value_mask) as u32;
-        if is_target_for_debug { eprintln!("  Final masked result: {} ({:#x})", final_result, final_result); }
-
-        final_result
+        (result & self.value_mask) as u32
     }
     // --- End GET METHOD ---
 
+    // Unpacks the first `out.len()` values in index order, walking the data once instead of locating each value.
+    // Uses the same bit layout as `get`.
+    pub fn unpack_into(&self, out: &mut [u32]) {
+        let mut bit_index = 0;
+        for value in out.iter_mut().take(self.capacity) {
+            let long_index = bit_index / BITS_PER_LONG;
+            let offset = bit_index % BITS_PER_LONG;
+            let Some(&long) = self.data.get(long_index) else { break; };
+
+            let mut bits = long >> offset;
+            if offset + self.bits_per_value > BITS_PER_LONG {
+                bits |= self.data.get(long_index + 1).map_or(0, |&next| next << (BITS_PER_LONG - offset));
+            }
+            *value = (bits & self.value_mask) as u32;
+            bit_index += self.bits_per_value;
+        }
+    }
+
     // --- Set method (ensure it matches get logic if modified) ---
     pub fn set(&mut self, index: usize, value: u32) {
         assert!(index < self.capacity, "Index out of bounds: {} >= {}", index, self.capacity);
@@ -198,6 +197,47 @@ pub enum PaletteContainer {
 }
 
 impl PaletteContainer {
+    // Name of the container's storage kind, as reported by the introspection APIs
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            PaletteContainer::Single(_) => "single",
+            PaletteContainer::Indirect { .. } => "indirect",
+            PaletteContainer::Direct(_) => "direct",
+        }
+    }
+
+    // Builds the smallest container holding `values` (in index order): a single value, an indirect palette of at
+    // least `min_bits` and at most `max_indirect_bits` bits per entry, or a direct container
+    // Direct containers are at least `direct_bits` wide (on top of what the values need)
+    pub fn from_values(values: &[u32], min_bits: usize, max_indirect_bits: usize, direct_bits: usize) -> Self {
+        let mut palette: Vec<u32> = Vec::new();
+        let mut palette_indices = HashMap::new();
+        for &value in values {
+            palette_indices.entry(value).or_insert_with(|| {
+                palette.push(value);
+                palette.len() as u32 - 1
+            });
+        }
+
+        let bits = needed_bits(palette.len().saturating_sub(1)).max(min_bits);
+        if palette.len() == 1 {
+            PaletteContainer::new_single(palette[0])
+        } else if bits <= max_indirect_bits {
+            let mut data = BitArray::new(bits, values.len());
+            for (index, value) in values.iter().enumerate() {
+                data.set(index, palette_indices[value]);
+            }
+            PaletteContainer::new_indirect(palette, data)
+        } else {
+            let max_value = palette.iter().copied().max().unwrap_or(0);
+            let mut data = BitArray::new(needed_bits(max_value as usize).max(direct_bits), values.len());
+            for (index, &value) in values.iter().enumerate() {
+                data.set(index, value);
+            }
+            PaletteContainer::new_direct(data)
+        }
+    }
+
     pub fn new_single(value: u32) -> Self { PaletteContainer::Single(value) }
     pub fn new_indirect(palette: Vec<u32>, data: BitArray) -> Self { PaletteContainer::Indirect { palette, data } }
     pub fn new_direct(data: BitArray) -> Self { PaletteContainer::Direct(data) }
@@ -222,13 +262,47 @@ impl PaletteContainer {
         }
     }
 
+    // Bulk counterpart of `get`: fills `out` with the values at indices 0..out.len()
+    pub fn unpack_into(&self, out: &mut [u32]) {
+        match self {
+            PaletteContainer::Single(value) => out.fill(*value),
+            PaletteContainer::Indirect { palette, data } => {
+                data.unpack_into(out);
+                for value in out.iter_mut() {
+                    *value = palette.get(*value as usize).copied().unwrap_or(0); // Out of range indices read as air, like `get`
+                }
+            }
+            PaletteContainer::Direct(data) => data.unpack_into(out),
+        }
+    }
+
+    // Cheap membership test: Single and Indirect containers only check their palette.
+    // Note an Indirect palette can hold entries no longer referenced by the data.
+    pub fn may_contain_any(&self, values: &HashSet<u32>) -> bool {
+        self.may_contain(|value| values.contains(&value))
+    }
+
+    // Same as `may_contain_any`, for values matching a predicate
+    pub fn may_contain(&self, matches: impl Fn(u32) -> bool) -> bool {
+        match self {
+            PaletteContainer::Single(value) => matches(*value),
+            PaletteContainer::Indirect { palette, .. } => palette.iter().any(|&v| matches(v)),
+            PaletteContainer::Direct(data) => (0..data.capacity).any(|i| matches(data.get(i))),
+        }
+    }
+
     // Set method remains the same as previous version with logging
     pub fn set(&mut self, index: usize, state_id: u32) -> bool {
+        self.set_sized(index, state_id, (SECTION_WIDTH * SECTION_WIDTH * SECTION_WIDTH) as usize, 4)
+    }
+
+    // Like `set`, but for containers that aren't block sections (e.g. 64-entry biome containers).
+    // `capacity` and `min_bits` are only used when a Single container has to be upgraded.
+    pub fn set_sized(&mut self, index: usize, state_id: u32, capacity: usize, min_bits: usize) -> bool {
         match self {
             PaletteContainer::Single(current_value) => {
                 if *current_value == state_id { return false; }
-                let bits = 4.max(needed_bits(1));
-                let capacity = (SECTION_WIDTH * SECTION_WIDTH * SECTION_WIDTH) as usize;
+                let bits = min_bits.max(needed_bits(1));
                 let mut data = BitArray::new(bits, capacity);
                 let palette = vec![*current_value, state_id];
                 for i in 0..capacity { if i != index { data.set(i, 0); } }
@@ -277,6 +351,12 @@ impl PaletteContainer {
             PaletteContainer::Direct(data) => {
                 let old_state_id = data.get(index);
                 if old_state_id == state_id { return false; }
+                // Containers narrower than the global palette (e.g. from another version's data) widen first
+                if needed_bits(state_id as usize) > data.get_bits_per_value() {
+                    let mut new_data = BitArray::new(needed_bits(state_id as usize), data.capacity);
+                    for i in 0..data.capacity { new_data.set(i, data.get(i)); }
+                    *data = new_data;
+                }
                 data.set(index, state_id);
                 // eprintln!("[PaletteContainer::set Direct] Set index {} to state {}", index, state_id);
                 false
@@ -291,25 +371,80 @@ pub fn needed_bits(value: usize) -> usize {
 }
 fn registry_max_state_id() -> usize { 26000 } // Placeholder
 pub fn read_varint(cursor: &mut Cursor<&[u8]>) -> Result<i32, std::io::Error> {
-    let mut num_read = 0; let mut result = 0; let mut shift = 0;
-    loop {
+    let mut result: u32 = 0;
+    for num_read in 0..5 {
         if cursor.position() >= cursor.get_ref().len() as u64 { return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Reached EOF while reading VarInt byte")); }
-        let byte = cursor.read_u8()?; num_read += 1; let value = (byte & 0b0111_1111) as i32;
-        result |= value.checked_shl(shift).ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "VarInt shift overflow"))?;
-        shift += 7; if byte & 0b1000_0000 == 0 { break; }
-        if num_read > 5 { return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "VarInt too long")); }
-    } Ok(result)
+        let byte = cursor.read_u8()?;
+        result |= ((byte & 0b0111_1111) as u32) << (7 * num_read); // Bits past 32 in the fifth byte are dropped, like Java
+        if byte & 0b1000_0000 == 0 { return Ok(result as i32); }
+    }
+    Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "VarInt too long"))
+}
+
+// Reads a VarInt array length, rejecting negative lengths and lengths the remaining data can't hold (at
+// `min_element_size` bytes per element), so hostile lengths can't trigger huge allocations
+pub fn read_length(cursor: &mut Cursor<&[u8]>, min_element_size: usize) -> Result<usize, std::io::Error> {
+    let length = read_varint(cursor)?;
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+    if length < 0 {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Negative array length {}", length)));
+    }
+    if length as u64 * min_element_size as u64 > remaining {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, format!("Array length {} exceeds the {} remaining bytes", length, remaining)));
+    }
+    Ok(length as usize)
 }
+
 // --- Modified read_long_array ---
 pub fn read_long_array(cursor: &mut Cursor<&[u8]>, expected_len: usize) -> Result<Vec<u64>, std::io::Error> {
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+    if (expected_len as u64).saturating_mul(8) > remaining {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, format!("Expected {} longs, but only {} bytes remain", expected_len, remaining)));
+    }
     let mut longs = Vec::with_capacity(expected_len);
-    for i in 0..expected_len {
-        if cursor.position() + 8 > cursor.get_ref().len() as u64 {
-             eprintln!("[read_long_array] ERROR: Unexpected EOF. Expected long {}, but cursor is at {} and buffer len is {}", i, cursor.position(), cursor.get_ref().len());
-            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, format!("Expected long {}/{}, but reached EOF", i, expected_len)));
-        }
+    for _ in 0..expected_len {
         // --- TRY READING AS LITTLE ENDIAN ---
         longs.push(cursor.read_u64::<LittleEndian>()?);
         // --- END ---
     } Ok(longs)
-}
\ No newline at end of file
+}
+
+// Writers for the `map_chunk` format the readers above parse, for tests and the benchmark fixtures (`bench.rs`)
+#[cfg(any(test, feature = "napi"))]
+pub(crate) mod test_support {
+    use super::{BitArray, PaletteContainer};
+
+    pub fn write_varint(out: &mut Vec<u8>, value: i32) {
+        let mut value = value as u32;
+        while value & !0x7F != 0 {
+            out.push((value as u8 & 0x7F) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    // A container as `map_chunk` packets carry it, with longs in the byte order `read_long_array` reads
+    pub fn write_container(out: &mut Vec<u8>, container: &PaletteContainer) {
+        let write_longs = |out: &mut Vec<u8>, data: &BitArray| {
+            write_varint(out, data.len() as i32);
+            data.get_data().iter().for_each(|long| out.extend_from_slice(&long.to_le_bytes()));
+        };
+        match container {
+            PaletteContainer::Single(value) => {
+                out.push(0);
+                write_varint(out, *value as i32);
+                write_varint(out, 0);
+            }
+            PaletteContainer::Indirect { palette, data } => {
+                out.push(data.get_bits_per_value() as u8);
+                write_varint(out, palette.len() as i32);
+                palette.iter().for_each(|&value| write_varint(out, value as i32));
+                write_longs(out, data);
+            }
+            PaletteContainer::Direct(data) => {
+                out.push(data.get_bits_per_value() as u8);
+                write_longs(out, data);
+            }
+        }
+    }
+}
@@ -1,27 +1,56 @@
 // src/parsing.rs
-use crate::palette::{BitArray, PaletteContainer, read_long_array, read_varint};
-use crate::chunk::ChunkSection;
-use crate::coords::{SECTION_HEIGHT, SECTION_WIDTH};
+use crate::palette::{BitArray, PaletteContainer, read_length, read_long_array, read_varint};
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::coords::{BIOME_SECTION_VOLUME, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_HEIGHT, SECTION_WIDTH};
 use byteorder::{BigEndian, ReadBytesExt};
 use std::io::Cursor;
+use std::ops::Range;
 
 // Constants remain the same...
 const GLOBAL_BITS_PER_BLOCK: usize = 15;
 const MAX_BITS_PER_BLOCK: usize = 8;
 const MIN_BITS_PER_BLOCK: usize = 4;
-const BIOME_SECTION_VOLUME: usize = 4 * 4 * 4;
 const GLOBAL_BITS_PER_BIOME: usize = 6;
 const MAX_BITS_PER_BIOME: usize = 3;
 const MIN_BITS_PER_BIOME: usize = 1;
 
 
-pub fn parse_chunk_section(cursor: &mut Cursor<&[u8]>, section_y: i32) -> Result<ChunkSection, std::io::Error> {
-    let start_pos = cursor.position();
-    eprintln!("\n--- Parsing Section y={} ---", section_y);
-    eprintln!("[parse_section y={}] Start cursor: {}", section_y, start_pos);
+// Parses the sections of a `map_chunk` packet into a column. Unless `strict`, data that ends early (or mid-section)
+// leaves the remaining sections missing instead of failing.
+pub fn parse_column(data: &[u8], strict: bool) -> Result<ChunkColumn, std::io::Error> {
+    parse_column_sections(data, strict, MIN_SECTION_Y..MAX_SECTION_Y + 1)
+}
+
+// Parses a `map_chunk` packet of a dimension whose sections are `sections` (the packet holds one per section, from
+// the bottom up), like the Nether's 0..16. The range must lie within the -4..20 sections columns store.
+pub fn parse_column_sections(data: &[u8], strict: bool, sections: Range<i32>) -> Result<ChunkColumn, std::io::Error> {
+    let mut cursor = Cursor::new(data);
+    let mut column = ChunkColumn::new();
 
+    for section_y in sections {
+        if cursor.position() >= data.len() as u64 {
+            if strict {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, format!("Chunk data ended before section y={}", section_y)));
+            }
+            break;
+        }
+        match parse_chunk_section(&mut cursor, section_y) {
+            Ok(section) => column.insert_section(section_y, section),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && !strict => break,
+            Err(e) => return Err(std::io::Error::new(e.kind(), format!("Failed to parse chunk section at y={}: {}", section_y, e))),
+        }
+    }
+    Ok(column)
+}
+
+/// Parses column data the way `load_column` does by default, without going through N-API. Never panics, whatever
+/// the input: malformed data is an error. Entry point for fuzzing (see `fuzz/`).
+pub fn parse_column_bytes(data: &[u8]) -> Result<ChunkColumn, std::io::Error> {
+    parse_column(data, false)
+}
+
+pub fn parse_chunk_section(cursor: &mut Cursor<&[u8]>, section_y: i32) -> Result<ChunkSection, std::io::Error> {
     let solid_block_count = cursor.read_i16::<BigEndian>()?;
-    eprintln!("[parse_section y={}] Solid block count: {} (Cursor after: {})", section_y, solid_block_count, cursor.position());
 
     let block_states_container = parse_palette_container(
         cursor,
@@ -31,9 +60,6 @@ pub fn parse_chunk_section(cursor: &mut Cursor<&[u8]>, section_y: i32) -> Result
         GLOBAL_BITS_PER_BLOCK,
         &format!("Blocks (y={})", section_y)
     )?;
-    let cursor_after_blocks = cursor.position();
-    eprintln!("[parse_section y={}] Cursor after blocks: {}", section_y, cursor_after_blocks);
-
     let biomes_container = parse_palette_container(
         cursor,
         BIOME_SECTION_VOLUME,
@@ -42,9 +68,6 @@ pub fn parse_chunk_section(cursor: &mut Cursor<&[u8]>, section_y: i32) -> Result
         GLOBAL_BITS_PER_BIOME,
         &format!("Biomes (y={})", section_y)
     )?;
-    let cursor_after_biomes = cursor.position();
-    eprintln!("[parse_section y={}] Cursor after biomes: {}", section_y, cursor_after_biomes);
-    eprintln!("[parse_section y={}] Total bytes read for section: {}", section_y, cursor_after_biomes - start_pos);
 
     Ok(ChunkSection::new(block_states_container, biomes_container, solid_block_count))
 }
@@ -57,71 +80,179 @@ fn parse_palette_container(
     bits_direct: usize,
     context: &str,
 ) -> Result<PaletteContainer, std::io::Error> {
-    let cursor_before_bits = cursor.position();
     let bits_per_value = cursor.read_u8()? as usize;
-    eprintln!("[parse_palette {}] Cursor before bits: {}, Bits per value: {} (Cursor after: {})", context, cursor_before_bits, bits_per_value, cursor.position());
 
     if bits_per_value == 0 {
-        // Single value palette
-        let cursor_before_val = cursor.position();
+        // Single value palette, followed by an empty data array
         let value = read_varint(cursor)? as u32;
-        let cursor_after_val = cursor.position();
-        let cursor_before_len = cursor.position();
-        let data_array_len_ignored = read_varint(cursor)?;
-        eprintln!("[parse_palette {}] Type: Single. Cursor before val: {}, Value: {} (Cursor after: {}). Cursor before len: {}, Discarded DataLen: {} (Cursor after: {})",
-            context, cursor_before_val, value, cursor_after_val, cursor_before_len, data_array_len_ignored, cursor.position());
-        if data_array_len_ignored != 0 {
-             eprintln!("[parse_palette {}] WARNING: Single value palette had non-zero data array length: {}", context, data_array_len_ignored);
-        }
+        read_varint(cursor)?;
         Ok(PaletteContainer::new_single(value))
 
     } else if bits_per_value <= max_bits_indirect {
         // Indirect (section palette)
-        let cursor_before_pal_len = cursor.position();
-        let palette_len = read_varint(cursor)? as usize;
-        eprintln!("[parse_palette {}] Type: Indirect. Cursor before pal_len: {}, Palette length: {} (Cursor after: {})", context, cursor_before_pal_len, palette_len, cursor.position());
-
+        let palette_len = read_length(cursor, 1)?;
         if palette_len == 0 {
-             eprintln!("[parse_palette {}] WARNING: Indirect palette has zero length!", context);
-             let cursor_before_len = cursor.position();
-             let data_long_len = read_varint(cursor)? as usize;
-             eprintln!("[parse_palette {}] Cursor before data len: {}, Expected Data array length (VarInt): {} (Cursor after: {})", context, cursor_before_len, data_long_len, cursor.position());
-             let cursor_before_data = cursor.position();
-             let _data_longs = read_long_array(cursor, data_long_len)?;
-             eprintln!("[parse_palette {}] Cursor before data read: {}, Read {} longs (Cursor after: {})", context, cursor_before_data, data_long_len, cursor.position());
-             return Ok(PaletteContainer::new_indirect(vec![], BitArray::new(bits_per_value, capacity)));
+            let data_long_len = read_length(cursor, 8)?;
+            read_long_array(cursor, data_long_len)?;
+            return Ok(PaletteContainer::new_indirect(vec![], BitArray::new(bits_per_value, capacity)));
         }
 
-        let cursor_before_palette = cursor.position();
         let mut palette = Vec::with_capacity(palette_len);
         for _ in 0..palette_len {
             palette.push(read_varint(cursor)? as u32);
         }
-        eprintln!("[parse_palette {}] Cursor before palette: {}, Read Palette: {:?} (Cursor after: {})", context, cursor_before_palette, palette, cursor.position());
-
-        let cursor_before_len = cursor.position();
-        let data_long_len = read_varint(cursor)? as usize;
-        eprintln!("[parse_palette {}] Cursor before data len: {}, Expected Data array length (VarInt): {} (Cursor after: {})", context, cursor_before_len, data_long_len, cursor.position());
-        let cursor_before_data = cursor.position();
+        let data_long_len = read_length(cursor, 8)?;
         let data_longs = read_long_array(cursor, data_long_len)?;
-        eprintln!("[parse_palette {}] Cursor before data read: {}, Actual Data longs read: {} (Cursor after: {})", context, cursor_before_data, data_longs.len(), cursor.position());
-        // eprintln!("[parse_palette {}] Data longs (first 5): {:?}", context, data_longs.iter().take(5).map(|&x| format!("{:#x}", x)).collect::<Vec<_>>());
 
-        let bit_array = BitArray::from_data(bits_per_value, capacity, data_longs);
+        let bit_array = bit_array(bits_per_value, capacity, data_longs, context)?;
         Ok(PaletteContainer::new_indirect(palette, bit_array))
     } else {
         // Direct (global palette)
-        let cursor_before_len = cursor.position();
-        let data_long_len = read_varint(cursor)? as usize;
-        eprintln!("[parse_palette {}] Type: Direct. Cursor before data len: {}, Expected Data array length (VarInt): {} (Cursor after: {})", context, cursor_before_len, data_long_len, cursor.position());
-        let cursor_before_data = cursor.position();
+        let data_long_len = read_length(cursor, 8)?;
         let data_longs = read_long_array(cursor, data_long_len)?;
-        eprintln!("[parse_palette {}] Cursor before data read: {}, Actual Data longs read: {} (Cursor after: {})", context, cursor_before_data, data_longs.len(), cursor.position());
-        // eprintln!("[parse_palette {}] Data longs (first 5): {:?}", context, data_longs.iter().take(5).map(|&x| format!("{:#x}", x)).collect::<Vec<_>>());
 
         let effective_bits = bits_direct.max(bits_per_value);
-        eprintln!("[parse_palette {}] Effective bits for Direct: {}", context, effective_bits);
-        let bit_array = BitArray::from_data(effective_bits, capacity, data_longs);
+        let bit_array = bit_array(effective_bits, capacity, data_longs, context)?;
         Ok(PaletteContainer::new_direct(bit_array))
     }
-}
\ No newline at end of file
+}
+
+// Checks the data length against the bits instead of panicking like `BitArray::from_data`: it comes from the server
+fn bit_array(bits_per_value: usize, capacity: usize, data: Vec<u64>, context: &str) -> Result<BitArray, std::io::Error> {
+    let len = data.len();
+    BitArray::try_from_data(bits_per_value, capacity, data).ok_or_else(|| std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("{}: {} data longs don't fit {} bits per value", context, len, bits_per_value),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::SECTION_VOLUME;
+    use crate::palette::test_support::{write_container, write_varint};
+
+    // Sections cycling through single, indirect and direct block containers
+    fn sample_sections(count: usize) -> Vec<(Vec<u32>, PaletteContainer)> {
+        (0..count)
+            .map(|section| {
+                let values: Vec<u32> = match section % 3 {
+                    0 => vec![section as u32; SECTION_VOLUME],
+                    1 => (0..SECTION_VOLUME as u32).map(|i| (i * 31 + section as u32) % 12).collect(),
+                    _ => (0..SECTION_VOLUME as u32).map(|i| (i * 7919) % 20_000).collect(),
+                };
+                let container = PaletteContainer::from_values(&values, MIN_BITS_PER_BLOCK, MAX_BITS_PER_BLOCK, GLOBAL_BITS_PER_BLOCK);
+                (values, container)
+            })
+            .collect()
+    }
+
+    fn write_packet(sections: &[(Vec<u32>, PaletteContainer)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (section, (values, container)) in sections.iter().enumerate() {
+            out.extend_from_slice(&(values.iter().filter(|&&v| v != 0).count() as i16).to_be_bytes());
+            write_container(&mut out, container);
+            write_container(&mut out, &PaletteContainer::new_single(section as u32 % 4));
+        }
+        out
+    }
+
+    fn assert_sections(column: &ChunkColumn, sections: &[(Vec<u32>, PaletteContainer)], first_y: i32) {
+        assert_eq!(column.sections().count(), sections.len());
+        for (offset, (values, _)) in sections.iter().enumerate() {
+            let section_y = first_y + offset as i32;
+            let section = column.get_section(section_y).unwrap();
+            for (index, &value) in values.iter().enumerate() {
+                assert_eq!(section.block_states().get(index), value, "section {} block {}", section_y, index);
+            }
+            assert_eq!(section.biomes().get(5), offset as u32 % 4);
+        }
+    }
+
+    #[test]
+    fn packets_round_trip() {
+        let sections = sample_sections((MAX_SECTION_Y - MIN_SECTION_Y + 1) as usize);
+        let packet = write_packet(&sections);
+        assert_sections(&parse_column(&packet, true).unwrap(), &sections, MIN_SECTION_Y);
+        assert_sections(&parse_column_bytes(&packet).unwrap(), &sections, MIN_SECTION_Y);
+
+        // The Nether's 16 sections from y=0
+        let nether = sample_sections(16);
+        assert_sections(&parse_column_sections(&write_packet(&nether), true, 0..16).unwrap(), &nether, 0);
+    }
+
+    #[test]
+    fn truncated_packets_keep_whole_sections_unless_strict() {
+        let sections = sample_sections(5);
+        let packet = write_packet(&sections);
+        let two_sections = write_packet(&sections[..2]).len();
+        for len in [two_sections, two_sections + 1, two_sections + 300] {
+            assert!(parse_column(&packet[..len], true).is_err());
+            assert_sections(&parse_column(&packet[..len], false).unwrap(), &sections[..2], MIN_SECTION_Y);
+        }
+    }
+
+    #[test]
+    fn malformed_packets_are_rejected_without_panicking() {
+        let header = |bits: u8| {
+            let mut out = 100i16.to_be_bytes().to_vec();
+            out.push(bits);
+            out
+        };
+        let mut negative_palette = header(4);
+        write_varint(&mut negative_palette, -1);
+        let mut huge_data = header(4);
+        write_varint(&mut huge_data, 1);
+        write_varint(&mut huge_data, 1);
+        write_varint(&mut huge_data, i32::MAX);
+        let mut wrong_length = header(4);
+        write_varint(&mut wrong_length, 1);
+        write_varint(&mut wrong_length, 1);
+        write_varint(&mut wrong_length, 3);
+        wrong_length.extend_from_slice(&[0; 24]);
+        let mut long_varint = header(0);
+        long_varint.extend_from_slice(&[0xFF; 6]);
+        for packet in [negative_palette, huge_data, wrong_length, long_varint] {
+            assert!(parse_column(&packet, true).is_err(), "{:?}", &packet[..8]);
+        }
+
+        // Widths past what the data can hold, with enough longs for 15 bits
+        for bits in [16u8, 33, 64, 65, 255] {
+            let mut packet = header(bits);
+            write_varint(&mut packet, 1024);
+            packet.extend(std::iter::repeat_n(0xA5, 1024 * 8));
+            let _ = parse_column_bytes(&packet);
+        }
+
+        // Corrupted and random packets either fail or give columns whose blocks can all be read
+        let packet = write_packet(&sample_sections(4));
+        let mut seed = 0xbb67_ae85_84ca_a73bu64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for round in 0..300 {
+            let data = if round % 3 == 0 {
+                (0..next() % 2_000).map(|_| next() as u8).collect()
+            } else {
+                let mut corrupted = packet.clone();
+                for _ in 0..1 + next() % 4 {
+                    let index = (next() % corrupted.len() as u64) as usize;
+                    corrupted[index] = next() as u8;
+                }
+                corrupted
+            };
+            if let Ok(column) = parse_column_bytes(&data) {
+                for (_, section) in column.sections() {
+                    let mut states = [0u32; SECTION_VOLUME];
+                    section.block_states().unpack_into(&mut states);
+                    for index in 0..BIOME_SECTION_VOLUME {
+                        section.biomes().get(index);
+                    }
+                }
+            }
+        }
+    }
+}
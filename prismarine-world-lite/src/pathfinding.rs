@@ -0,0 +1,253 @@
+// src/pathfinding.rs
+use crate::coords::WorldCoords;
+use crate::movement::{PLAYER_HEIGHT, PLAYER_WIDTH};
+use crate::world::NapiWorld;
+use napi_derive::napi;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+const DEFAULT_MAX_NODES: u32 = 10_000;
+const DEFAULT_MAX_JUMP: u32 = 1;
+const DEFAULT_MAX_FALL: u32 = 3;
+const JUMP_COST: f64 = 0.5;
+const FALL_COST_PER_BLOCK: f64 = 0.1;
+
+// Movement rules for `find_path`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct PathOptions {
+    pub max_nodes: Option<u32>,      // Nodes to expand before giving up (default 10000)
+    pub allow_diagonal: Option<bool>, // Diagonal steps without corner cutting (default true)
+    pub max_jump_height: Option<u32>, // Blocks the agent can step/jump up (default 1)
+    pub max_fall_height: Option<u32>, // Blocks the agent may drop down (default 3)
+}
+
+// Movement rules with defaults applied
+#[derive(Debug, Clone, Copy)]
+pub struct MovementRules {
+    pub allow_diagonal: bool,
+    pub max_jump: i32,
+    pub max_fall: i32,
+}
+
+impl From<&PathOptions> for MovementRules {
+    fn from(options: &PathOptions) -> Self {
+        MovementRules {
+            allow_diagonal: options.allow_diagonal.unwrap_or(true),
+            max_jump: options.max_jump_height.unwrap_or(DEFAULT_MAX_JUMP) as i32,
+            max_fall: options.max_fall_height.unwrap_or(DEFAULT_MAX_FALL) as i32,
+        }
+    }
+}
+
+#[napi(object)]
+pub struct PathResult {
+    pub status: String, // "found", "partial" (node limit hit, path to the closest node) or "noPath"
+    pub path: Vec<WorldCoords>, // Feet positions from start to end, inclusive
+    pub cost: f64,
+    pub nodes_explored: u32,
+}
+
+// Open set entry, ordered so the BinaryHeap pops the lowest f-score first
+#[derive(PartialEq)]
+struct OpenNode {
+    f: f64,
+    g: f64,
+    pos: WorldCoords,
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f).then_with(|| self.g.total_cmp(&other.g))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Horizontal octile distance; ignores height so falls (cheaper than their drop) stay admissible
+fn heuristic(a: WorldCoords, b: WorldCoords) -> f64 {
+    let dx = (a.x - b.x).abs() as f64;
+    let dz = (a.z - b.z).abs() as f64;
+    dx.max(dz) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dz)
+}
+
+const DIRECTIONS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+impl NapiWorld {
+    // Whether the block has no collision boxes, i.e. an agent's body can occupy it
+    pub(crate) fn is_passable(&self, pos: WorldCoords) -> bool {
+        self.registry.collision_shapes(self.get_block_state_id(pos.x, pos.y, pos.z)).is_empty()
+    }
+
+    // Whether a player-sized agent can stand with its feet in `pos`
+    pub(crate) fn can_stand(&self, pos: WorldCoords) -> bool {
+        self.standing_height(pos, PLAYER_WIDTH, PLAYER_HEIGHT).is_some()
+    }
+
+    // Standable positions reachable from `pos` in one move, with their cost
+    pub(crate) fn movement_neighbors(&self, pos: WorldCoords, rules: &MovementRules) -> Vec<(WorldCoords, f64)> {
+        let directions = if rules.allow_diagonal { &DIRECTIONS[..] } else { &DIRECTIONS[..4] };
+        let mut neighbors = Vec::new();
+
+        for &(dx, dz) in directions {
+            let diagonal = dx != 0 && dz != 0;
+            let step_cost = if diagonal { std::f64::consts::SQRT_2 } else { 1.0 };
+            let target = WorldCoords { x: pos.x + dx, y: pos.y, z: pos.z + dz };
+
+            // Diagonals need both adjacent columns clear at body height so corners aren't cut
+            let body_clear = |p: WorldCoords| self.is_passable(p) && self.is_passable(WorldCoords { y: p.y + 1, ..p });
+            if diagonal
+                && !(body_clear(WorldCoords { x: pos.x + dx, ..pos }) && body_clear(WorldCoords { z: pos.z + dz, ..pos }))
+            {
+                continue;
+            }
+
+            if self.can_stand(target) {
+                neighbors.push((target, step_cost));
+                continue;
+            }
+
+            // Jump up: needs headroom above the current position for every block climbed
+            if !self.is_passable(target) {
+                for height in 1..=rules.max_jump {
+                    if !self.is_passable(WorldCoords { y: pos.y + 1 + height, ..pos }) { break; }
+                    let up = WorldCoords { y: pos.y + height, ..target };
+                    if self.can_stand(up) {
+                        neighbors.push((up, step_cost + JUMP_COST * height as f64));
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            // Fall: walk off the edge and drop onto the first floor below
+            if body_clear(target) {
+                for depth in 1..=rules.max_fall {
+                    let down = WorldCoords { y: pos.y - depth, ..target };
+                    if !self.is_passable(down) { break; }
+                    if self.can_stand(down) {
+                        neighbors.push((down, step_cost + FALL_COST_PER_BLOCK * depth as f64));
+                        break;
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Finds a walking path between two feet positions with A* over the loaded blocks.
+    ///
+    /// The agent is 1 block wide and 2 tall, can step or jump up `maxJumpHeight` blocks, drop `maxFallHeight`
+    /// blocks and, unless disabled, move diagonally when both adjacent columns are clear.
+    /// If the node budget runs out, the path to the node closest to the goal is returned with status `"partial"`.
+    #[napi]
+    pub fn find_path(&self, start: WorldCoords, goal: WorldCoords, options: Option<PathOptions>) -> PathResult {
+        let options = options.unwrap_or_default();
+        let rules = MovementRules::from(&options);
+        let max_nodes = options.max_nodes.unwrap_or(DEFAULT_MAX_NODES);
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<WorldCoords, WorldCoords> = HashMap::new();
+        let mut g_score: HashMap<WorldCoords, f64> = HashMap::new();
+
+        g_score.insert(start, 0.0);
+        open.push(OpenNode { f: heuristic(start, goal), g: 0.0, pos: start });
+
+        let mut closest = (heuristic(start, goal) + (start.y - goal.y).abs() as f64, start);
+        let mut explored = 0u32;
+
+        while let Some(OpenNode { g, pos, .. }) = open.pop() {
+            if g > g_score.get(&pos).copied().unwrap_or(f64::INFINITY) { continue; } // Stale entry
+
+            if pos == goal {
+                return PathResult {
+                    status: "found".to_string(),
+                    path: reconstruct_path(&came_from, pos),
+                    cost: g,
+                    nodes_explored: explored,
+                };
+            }
+
+            explored += 1;
+            if explored > max_nodes {
+                break;
+            }
+
+            let remaining = heuristic(pos, goal) + (pos.y - goal.y).abs() as f64;
+            if remaining < closest.0 {
+                closest = (remaining, pos);
+            }
+
+            for (next, cost) in self.movement_neighbors(pos, &rules) {
+                let tentative = g + cost;
+                if tentative < g_score.get(&next).copied().unwrap_or(f64::INFINITY) {
+                    came_from.insert(next, pos);
+                    g_score.insert(next, tentative);
+                    open.push(OpenNode { f: tentative + heuristic(next, goal), g: tentative, pos: next });
+                }
+            }
+        }
+
+        let exhausted = explored <= max_nodes;
+        if exhausted {
+            return PathResult { status: "noPath".to_string(), path: Vec::new(), cost: 0.0, nodes_explored: explored };
+        }
+        let end = closest.1;
+        PathResult {
+            status: "partial".to_string(),
+            path: reconstruct_path(&came_from, end),
+            cost: g_score.get(&end).copied().unwrap_or(0.0),
+            nodes_explored: explored,
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Lists every feet position a walking agent can reach from `start`, using the same movement rules as `find_path`
+    /// (`maxNodes` in the rules is ignored in favour of the explicit limit).
+    ///
+    /// Positions are returned in breadth-first order, so when `max_nodes` cuts the search short the closest ones are kept.
+    #[napi]
+    pub fn flood_fill_reachable(&self, start: WorldCoords, max_nodes: u32, movement_rules: Option<PathOptions>) -> Vec<WorldCoords> {
+        let rules = MovementRules::from(&movement_rules.unwrap_or_default());
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        let mut reachable = Vec::new();
+
+        if !self.can_stand(start) { return reachable; }
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            if reachable.len() >= max_nodes as usize { break; }
+            reachable.push(pos);
+            for (next, _) in self.movement_neighbors(pos, &rules) {
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+fn reconstruct_path(came_from: &HashMap<WorldCoords, WorldCoords>, end: WorldCoords) -> Vec<WorldCoords> {
+    let mut path = vec![end];
+    let mut current = end;
+    while let Some(&previous) = came_from.get(&current) {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    path
+}
@@ -0,0 +1,103 @@
+// src/perf.rs
+use crate::world::NapiWorld;
+use napi_derive::napi;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+// Running totals of the native work done by a world, shared by all handles to it. Relaxed atomics: the counters
+// are statistics, not synchronization.
+#[derive(Debug, Default)]
+pub struct PerfCounters {
+    chunk_parses: AtomicU64,
+    chunk_parse_nanos: AtomicU64,
+    raycasts: AtomicU64,
+    raycast_nanos: AtomicU64,
+    block_gets: AtomicU64,
+    block_sets: AtomicU64,
+    lock_contentions: AtomicU64,
+    column_cache_hits: AtomicU64,   // Column lookups that found the column loaded
+    column_cache_misses: AtomicU64, // Lookups that had to go to region files (or found nothing)
+}
+
+impl PerfCounters {
+    pub fn chunk_parsed(&self, time: Duration) {
+        self.chunk_parses.fetch_add(1, Ordering::Relaxed);
+        self.chunk_parse_nanos.fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn raycast(&self, time: Duration) {
+        self.raycasts.fetch_add(1, Ordering::Relaxed);
+        self.raycast_nanos.fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn block_get(&self) {
+        self.block_gets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn block_set(&self) {
+        self.block_sets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn lock_contended(&self) {
+        self.lock_contentions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn column_lookup(&self, hit: bool) {
+        let counter = if hit { &self.column_cache_hits } else { &self.column_cache_misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for counter in [
+            &self.chunk_parses, &self.chunk_parse_nanos, &self.raycasts, &self.raycast_nanos, &self.block_gets,
+            &self.block_sets, &self.lock_contentions, &self.column_cache_hits, &self.column_cache_misses,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Counts and timings of a world's native work since it was created or `reset_perf_counters` was called.
+#[napi(object)]
+pub struct PerfReport {
+    pub chunk_parses: i64,
+    pub chunk_parse_ms: f64,
+    pub raycasts: i64,          // Block raycasts, including those made by batch, entity and visibility queries
+    pub raycast_ms: f64,
+    pub block_gets: i64,        // Single block reads, including those raycasts make per visited block
+    pub block_sets: i64,        // Block writes through `set_block_state_id`/`try_set_block_state_id`
+    pub lock_contentions: i64,  // Column locks that couldn't be taken right away
+    pub column_cache_hits: i64,
+    pub column_cache_misses: i64,
+    pub column_cache_hit_rate: f64, // Hits over all column lookups, 0 without lookups
+}
+
+#[napi]
+impl NapiWorld {
+    /// Gets counts and timings of the world's native work: chunk parses, raycasts, block reads and writes, lock
+    /// contention and how often columns were found loaded. Counting is always on and costs an atomic add per event.
+    #[napi]
+    pub fn get_perf_counters(&self) -> PerfReport {
+        let perf = &self.perf;
+        let read = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let (hits, misses) = (read(&perf.column_cache_hits), read(&perf.column_cache_misses));
+        PerfReport {
+            chunk_parses: read(&perf.chunk_parses) as i64,
+            chunk_parse_ms: read(&perf.chunk_parse_nanos) as f64 / 1e6,
+            raycasts: read(&perf.raycasts) as i64,
+            raycast_ms: read(&perf.raycast_nanos) as f64 / 1e6,
+            block_gets: read(&perf.block_gets) as i64,
+            block_sets: read(&perf.block_sets) as i64,
+            lock_contentions: read(&perf.lock_contentions) as i64,
+            column_cache_hits: hits as i64,
+            column_cache_misses: misses as i64,
+            column_cache_hit_rate: if hits + misses == 0 { 0.0 } else { hits as f64 / (hits + misses) as f64 },
+        }
+    }
+
+    /// Sets every performance counter back to zero.
+    #[napi]
+    pub fn reset_perf_counters(&self) {
+        self.perf.reset();
+    }
+}
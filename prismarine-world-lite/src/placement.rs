@@ -0,0 +1,234 @@
+// src/placement.rs
+use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::movement::boxes_overlap;
+use crate::raycast::{AabbArg, BlockFace, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+
+// Faces of the neighbors a block can be placed against that get clicked; each neighbor sits on the opposite side
+// of the target. The block below comes first, as it's the most natural to build on.
+pub const PLACEMENT_NEIGHBORS: [BlockFace; 6] =
+    [BlockFace::Top, BlockFace::South, BlockFace::North, BlockFace::East, BlockFace::West, BlockFace::Bottom];
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct PlacementOptions {
+    pub require_support: Option<bool>, // Default true: a neighboring block has to be there to click on
+    pub entities: Option<Vec<AabbArg>>, // Entity boxes the placed block must not intersect
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct PlacementCheck {
+    pub can_place: bool,
+    // Why not: "unloaded", "outOfWorld", "occupied", "noSupport" or "entity"
+    pub reason: Option<String>,
+    pub against: Option<WorldCoords>, // Neighboring block to click on, if any
+    pub face: Option<u32>,            // BlockFace of `against` to click
+}
+
+// Blocks that need soil (grass, dirt and the like) below them
+const SOIL_PLANTS: &[&str] = &[
+    "grass", "short_grass", "tall_grass", "fern", "large_fern", "dandelion", "poppy", "blue_orchid", "allium",
+    "azure_bluet", "red_tulip", "orange_tulip", "white_tulip", "pink_tulip", "oxeye_daisy", "cornflower",
+    "lily_of_the_valley", "wither_rose", "sunflower", "lilac", "rose_bush", "peony", "sweet_berry_bush",
+    "torchflower", "pink_petals",
+];
+const SOIL: &[&str] = &[
+    "grass_block", "dirt", "coarse_dirt", "podzol", "rooted_dirt", "farmland", "moss_block", "mud", "mycelium",
+];
+const CROPS: &[&str] = &[
+    "wheat", "carrots", "potatoes", "beetroots", "melon_stem", "pumpkin_stem", "attached_melon_stem",
+    "attached_pumpkin_stem", "torchflower_crop", "pitcher_crop",
+];
+
+// Offset of a direction property value
+fn direction_offset(direction: &str) -> Option<(i32, i32, i32)> {
+    match direction {
+        "north" => Some((0, 0, -1)),
+        "south" => Some((0, 0, 1)),
+        "west" => Some((-1, 0, 0)),
+        "east" => Some((1, 0, 0)),
+        "up" => Some((0, 1, 0)),
+        "down" => Some((0, -1, 0)),
+        _ => None,
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ScaffoldStep {
+    pub position: WorldCoords, // Where to place a block
+    pub against: WorldCoords,  // Block to click on
+    pub face: u32,             // BlockFace of `against` to click
+}
+
+impl PlacementCheck {
+    fn rejected(reason: &str) -> Self {
+        PlacementCheck { can_place: false, reason: Some(reason.to_string()), against: None, face: None }
+    }
+}
+
+impl NapiWorld {
+    // The neighbor of `target` to place against and the face to click, if there is one
+    pub(crate) fn placement_support(&self, target: WorldCoords) -> Option<(WorldCoords, BlockFace)> {
+        PLACEMENT_NEIGHBORS.iter().find_map(|&face| {
+            let neighbor = face.opposite().offset_position(target, 1);
+            let state_id = self.get_block_state_id(neighbor.x, neighbor.y, neighbor.z);
+            (!self.registry.is_replaceable(state_id)).then_some((neighbor, face))
+        })
+    }
+
+    // Whether a block state at `pos` has what it needs to stay in place. Blocks without attachment rules are
+    // always supported. A full collision cube stands in for vanilla's "sturdy face".
+    pub(crate) fn block_supported(&self, pos: WorldCoords, state_id: u32) -> bool {
+        let Some(block) = self.registry.block(state_id) else { return true; };
+        let name = block.name.as_str();
+        let at = |(dx, dy, dz): (i32, i32, i32)| self.get_block_state_id(pos.x + dx, pos.y + dy, pos.z + dz);
+        let name_at = |offset| self.registry.block(at(offset)).map_or("air", |b| b.name.as_str());
+        let sturdy = |offset| self.registry.is_full_cube(at(offset));
+        let property = |property| self.registry.state_property(state_id, property);
+        // The block this one hangs on, opposite the way it faces
+        let behind = || property("facing").as_deref().and_then(direction_offset).map(|(dx, dy, dz)| (-dx, -dy, -dz));
+        let below = (0, -1, 0);
+
+        // Upper halves of doors and two-tall plants rest on their lower half
+        if property("half").as_deref() == Some("upper") { return name_at(below) == name; }
+
+        match name {
+            _ if name.ends_with("wall_torch") || name.ends_with("wall_sign") || name.ends_with("wall_banner")
+                || name.ends_with("wall_head") || name.ends_with("wall_skull") || name == "ladder" => {
+                behind().is_some_and(sturdy)
+            }
+            _ if name.ends_with("_button") || name == "lever" => match property("face").as_deref() {
+                Some("floor") => sturdy(below),
+                Some("ceiling") => sturdy((0, 1, 0)),
+                _ => behind().is_some_and(sturdy),
+            },
+            "torch" | "soul_torch" | "redstone_torch" => {
+                // Fences and walls hold torches too, through the middle of their top
+                let below_state = at(below);
+                self.registry.collision_shapes(below_state).iter().any(|shape| {
+                    shape[4] >= 1.0 && shape[0] <= 0.5 && shape[3] >= 0.5 && shape[2] <= 0.5 && shape[5] >= 0.5
+                })
+            }
+            "lantern" | "soul_lantern" => {
+                let offset = if property("hanging").as_deref() == Some("true") { (0, 1, 0) } else { below };
+                !self.registry.is_air(at(offset))
+            }
+            "nether_wart" => name_at(below) == "soul_sand",
+            "sugar_cane" => name_at(below) == "sugar_cane" || SOIL.contains(&name_at(below)) || name_at(below).ends_with("sand"),
+            "cactus" => {
+                let clear_sides = [(1, 0, 0), (-1, 0, 0), (0, 0, 1), (0, 0, -1)]
+                    .into_iter()
+                    .all(|offset| self.registry.collision_shapes(at(offset)).is_empty());
+                clear_sides && matches!(name_at(below), "cactus" | "sand" | "red_sand")
+            }
+            "dead_bush" => SOIL.contains(&name_at(below)) || name_at(below).ends_with("sand") || name_at(below).ends_with("terracotta"),
+            _ if CROPS.contains(&name) => name_at(below) == "farmland",
+            _ if SOIL_PLANTS.contains(&name) || name.ends_with("_sapling") => {
+                SOIL.contains(&name_at(below))
+            }
+            _ if name.ends_with("rail") || name.ends_with("_pressure_plate") || name.ends_with("_door")
+                || matches!(name, "redstone_wire" | "repeater" | "comparator" | "snow") => sturdy(below),
+            _ if name.ends_with("_carpet") => !self.registry.is_air(at(below)),
+            _ => true,
+        }
+    }
+
+    // The checks behind `can_place_block`
+    pub(crate) fn check_placement(&self, target: WorldCoords, state_id: u32, require_support: bool, entities: &[(DVec3, DVec3)]) -> PlacementCheck {
+        if target.y < MIN_CHUNK_Y || target.y >= MAX_CHUNK_Y { return PlacementCheck::rejected("outOfWorld"); }
+        if !self.ensure_column(ChunkCoords { x: target.x >> 4, z: target.z >> 4 }) {
+            return PlacementCheck::rejected("unloaded");
+        }
+        if !self.registry.is_replaceable(self.get_block_state_id(target.x, target.y, target.z)) {
+            return PlacementCheck::rejected("occupied");
+        }
+
+        let support = self.placement_support(target);
+        if support.is_none() && require_support { return PlacementCheck::rejected("noSupport"); }
+
+        let origin = DVec3::new(target.x as f64, target.y as f64, target.z as f64);
+        let blocked = self.registry.collision_shapes(state_id).iter().any(|shape| {
+            let (min, max) = (origin + DVec3::new(shape[0], shape[1], shape[2]), origin + DVec3::new(shape[3], shape[4], shape[5]));
+            entities.iter().any(|&(entity_min, entity_max)| boxes_overlap(min, max, entity_min, entity_max))
+        });
+        if blocked { return PlacementCheck::rejected("entity"); }
+
+        PlacementCheck {
+            can_place: true,
+            reason: None,
+            against: support.map(|(neighbor, _)| neighbor),
+            face: support.map(|(_, face)| face as u32),
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Checks whether a block state could be placed at `target` the way the server validates it: the position is
+    /// loaded and inside the world, the block there is replaceable (air, liquids, grass...), a neighboring block
+    /// exists to click on (unless `requireSupport` is false), and the new block's collision boxes don't intersect
+    /// any of the given entity boxes. On success, `against`/`face` tell which neighbor face to click.
+    #[napi]
+    pub fn can_place_block(&self, target: WorldCoords, state_id: u32, options: Option<PlacementOptions>) -> Result<PlacementCheck> {
+        if self.registry.block(state_id).is_none() {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown block state ID: {}", state_id),
+            ));
+        }
+        let options = options.unwrap_or_default();
+        let entities: Vec<(DVec3, DVec3)> = options
+            .entities
+            .unwrap_or_default()
+            .into_iter()
+            .map(|aabb| (DVec3::from(aabb.min), DVec3::from(aabb.max)))
+            .collect();
+        Ok(self.check_placement(target, state_id, options.require_support.unwrap_or(true), &entities))
+    }
+
+    /// Checks whether the block at the position has what it needs to stay in place, by its attachment rules:
+    /// torches, ladders, buttons and signs need the block they hang on, crops need farmland, plants need soil,
+    /// rails and redstone need a solid block below, upper halves of doors and tall plants need their lower half.
+    /// A full collision cube counts as solid. Blocks without such rules are always supported.
+    #[napi]
+    pub fn is_block_supported(&self, x: i32, y: i32, z: i32) -> bool {
+        self.block_supported(WorldCoords { x, y, z }, self.get_block_state_id(x, y, z))
+    }
+
+    /// Plans the block placements needed to bridge from one standing position to another: the blocks under the feet
+    /// along the line between them (face-connected, so each placed block can hold the next) that aren't there yet,
+    /// in placement order, each with the block and face to click, which may be a block placed earlier in the plan.
+    /// Returns an empty list if the floor is already complete, or `null` if a block can't be placed (unloaded,
+    /// outside the world, or nothing to place it against).
+    #[napi]
+    pub fn plan_scaffold(&self, from: WorldCoords, to: WorldCoords) -> Option<Vec<ScaffoldStep>> {
+        let center = |pos: WorldCoords| Vec3Arg { x: pos.x as f64 + 0.5, y: pos.y as f64 - 0.5, z: pos.z as f64 + 0.5 };
+        let floor = self.blocks_on_line(center(from), center(to));
+
+        let mut placed: HashSet<WorldCoords> = HashSet::new();
+        let mut steps = Vec::new();
+        for pos in floor {
+            if pos.y < MIN_CHUNK_Y || pos.y >= MAX_CHUNK_Y || !self.ensure_column(ChunkCoords { x: pos.x >> 4, z: pos.z >> 4 }) {
+                return None;
+            }
+            // Existing floor (anything not replaceable) is walked over as is
+            if !self.registry.is_replaceable(self.get_block_state_id(pos.x, pos.y, pos.z)) { continue; }
+
+            let (against, face) = PLACEMENT_NEIGHBORS.iter().find_map(|&face| {
+                let neighbor = face.opposite().offset_position(pos, 1);
+                let solid = placed.contains(&neighbor)
+                    || !self.registry.is_replaceable(self.get_block_state_id(neighbor.x, neighbor.y, neighbor.z));
+                solid.then_some((neighbor, face))
+            })?;
+            placed.insert(pos);
+            steps.push(ScaffoldStep { position: pos, against, face: face as u32 });
+        }
+        Some(steps)
+    }
+}
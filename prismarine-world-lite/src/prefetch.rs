@@ -0,0 +1,156 @@
+// src/prefetch.rs
+use crate::coords::ChunkCoords;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+use std::collections::HashSet;
+use std::sync::{Arc, Condvar, Mutex};
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkPosition {
+    pub chunk_x: i32,
+    pub chunk_z: i32,
+}
+
+#[derive(Default)]
+struct PrefetchControl {
+    target: Option<(ChunkCoords, i32)>, // Center column and radius in chunks
+    generation: u64,                    // Bumped on every change, so the worker can drop outdated work
+    stopped: bool,
+    listener: Option<ThreadsafeFunction<Vec<ChunkPosition>, ErrorStrategy::Fatal>>,
+}
+
+// Shared between the JS thread and the prefetch worker
+pub struct Prefetcher {
+    control: Arc<(Mutex<PrefetchControl>, Condvar)>,
+}
+
+impl Prefetcher {
+    fn update(&self, f: impl FnOnce(&mut PrefetchControl)) {
+        let (lock, changed) = &*self.control;
+        let mut control = lock.lock().unwrap();
+        f(&mut control);
+        control.generation += 1;
+        changed.notify_one();
+    }
+}
+
+// Dropped by `stop_prefetch` or along with the last handle to the world
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        self.update(|control| control.stopped = true);
+    }
+}
+
+// Columns within `radius` of the center (a square, like the vanilla view distance), nearest first
+fn wanted_columns(center: ChunkCoords, radius: i32) -> Vec<ChunkCoords> {
+    let mut columns: Vec<ChunkCoords> = (-radius..=radius)
+        .flat_map(|dx| (-radius..=radius).map(move |dz| ChunkCoords { x: center.x + dx, z: center.z + dz }))
+        .collect();
+    columns.sort_by_key(|c| (c.x - center.x).pow(2) + (c.z - center.z).pow(2));
+    columns
+}
+
+// Worker loop: waits for a target, unloads what it loaded earlier that is now out of range, then loads the
+// missing columns nearest-first, starting over whenever the target changes
+fn run_prefetcher(world: NapiWorld, control: Arc<(Mutex<PrefetchControl>, Condvar)>) {
+    let (lock, changed) = &*control;
+    let mut prefetched: HashSet<ChunkCoords> = HashSet::new();
+    let mut seen_generation = 0;
+
+    loop {
+        let (target, generation) = {
+            let mut state = lock.lock().unwrap();
+            while !state.stopped && state.generation == seen_generation {
+                state = changed.wait(state).unwrap();
+            }
+            if state.stopped { return; }
+            (state.target, state.generation)
+        };
+        seen_generation = generation;
+        let Some((center, radius)) = target else { continue; };
+
+        // One extra column of slack, so walking back and forth over a border doesn't reload columns
+        let outside = |c: &ChunkCoords| (c.x - center.x).abs() > radius + 1 || (c.z - center.z).abs() > radius + 1;
+        for coords in prefetched.iter().filter(|c| outside(c)) {
+            world.remove_column(*coords);
+        }
+        prefetched.retain(|c| !outside(c));
+
+        for coords in wanted_columns(center, radius) {
+            let state = lock.lock().unwrap();
+            if state.stopped || state.generation != seen_generation { break; }
+            let listener = state.listener.clone();
+            drop(state);
+
+            if world.columns.contains_key(&coords) || !world.ensure_column(coords) { continue; }
+            prefetched.insert(coords);
+            if let Some(listener) = listener {
+                listener.call(vec![ChunkPosition { chunk_x: coords.x, chunk_z: coords.z }], ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+}
+
+impl NapiWorld {
+    // Changes the prefetcher's settings, starting its worker thread first if needed
+    fn update_prefetcher(&self, f: impl FnOnce(&mut PrefetchControl)) {
+        let mut prefetcher = self.prefetcher.lock().unwrap();
+        let prefetcher = prefetcher.get_or_insert_with(|| {
+            let control = Arc::new((Mutex::new(PrefetchControl::default()), Condvar::new()));
+            // The worker's handle gets a prefetcher slot of its own, so it doesn't keep this one (and with it
+            // itself) alive once every other handle to the world is gone
+            let mut world = self.same_world();
+            world.prefetcher = Arc::new(Mutex::new(None));
+            let worker_control = Arc::clone(&control);
+            std::thread::spawn(move || run_prefetcher(world, worker_control));
+            Prefetcher { control }
+        });
+        prefetcher.update(f);
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Keeps the columns within `radius` chunks (at most 64) of a block position loaded from the attached region
+    /// directory, like a view distance. Columns are read nearest-first on a background thread; columns it loaded earlier
+    /// that are now out of range are unloaded again (columns loaded any other way are left alone). Call it again as the
+    /// position moves; outdated work is dropped. The thread runs until `stop_prefetch` or until the world is
+    /// garbage-collected.
+    #[napi]
+    pub fn set_prefetch_center(&self, x: f64, z: f64, radius: u32) -> Result<()> {
+        if self.region_dir.read().unwrap().is_none() {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "No region directory attached".to_string(),
+            ));
+        }
+        let center = ChunkCoords { x: (x.floor() as i32) >> 4, z: (z.floor() as i32) >> 4 };
+        let radius = radius.min(64) as i32;
+
+        self.update_prefetcher(|control| control.target = Some((center, radius)));
+        Ok(())
+    }
+
+    /// Calls `callback` with the positions of columns as the prefetcher loads them. Replaces any earlier callback.
+    #[napi(ts_args_type = "callback: (columns: ChunkPosition[]) => void")]
+    pub fn on_prefetch_load(&self, env: Env, callback: JsFunction) -> Result<()> {
+        let mut listener: ThreadsafeFunction<Vec<ChunkPosition>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Vec<ChunkPosition>>| Ok(vec![ctx.value]))?;
+        // Listeners shouldn't keep the process alive on their own
+        listener.unref(&env)?;
+
+        self.update_prefetcher(|control| control.listener = Some(listener));
+        Ok(())
+    }
+
+    /// Stops the prefetch thread. Columns it loaded stay loaded.
+    #[napi]
+    pub fn stop_prefetch(&self) {
+        // Dropping it stops the worker
+        self.prefetcher.lock().unwrap().take();
+    }
+}
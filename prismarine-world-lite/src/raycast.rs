@@ -1,10 +1,16 @@
 // src/raycast.rs
-use crate::coords::WorldCoords;
+use crate::coords::{SectionCoords, WorldCoords, MAX_CHUNK_Y, MIN_CHUNK_Y};
+use crate::matcher::BlockMatcher;
+use crate::registry::{BlockRegistry, ShapeBox, FULL_CUBE};
+use minecraft_data_rs::models::block::Block;
 use glam::DVec3; // Use DVec3 for f64 precision
+#[cfg(feature = "napi")]
 use napi_derive::napi;
+use std::collections::HashSet;
 
 // Enum to represent block faces (matches prismarine-world convention)
-#[napi]
+#[cfg_attr(feature = "napi", napi)]
+#[cfg_attr(not(feature = "napi"), derive(Clone, Copy))] // #[napi] enums derive these themselves
 #[derive(Debug, PartialEq)]
 pub enum BlockFace {
     Bottom = 0, // -Y
@@ -15,17 +21,296 @@ pub enum BlockFace {
     East = 5,   // +X
 }
 
+impl BlockFace {
+    pub const ALL: [BlockFace; 6] =
+        [BlockFace::Bottom, BlockFace::Top, BlockFace::North, BlockFace::South, BlockFace::West, BlockFace::East];
+
+    // Unit offset to the neighbor on this side
+    pub fn offset(self) -> WorldCoords {
+        match self {
+            BlockFace::Bottom => WorldCoords { x: 0, y: -1, z: 0 },
+            BlockFace::Top => WorldCoords { x: 0, y: 1, z: 0 },
+            BlockFace::North => WorldCoords { x: 0, y: 0, z: -1 },
+            BlockFace::South => WorldCoords { x: 0, y: 0, z: 1 },
+            BlockFace::West => WorldCoords { x: -1, y: 0, z: 0 },
+            BlockFace::East => WorldCoords { x: 1, y: 0, z: 0 },
+        }
+    }
+
+    // `pos` moved `distance` blocks out through this face, stopping at the ends of the i32 range
+    pub fn offset_position(self, pos: WorldCoords, distance: i32) -> WorldCoords {
+        let offset = self.offset();
+        let step = |v: i32, o: i32| v.saturating_add(o.saturating_mul(distance));
+        WorldCoords { x: step(pos.x, offset.x), y: step(pos.y, offset.y), z: step(pos.z, offset.z) }
+    }
+
+    pub fn normal(self) -> DVec3 {
+        let offset = self.offset();
+        DVec3::new(offset.x as f64, offset.y as f64, offset.z as f64)
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            BlockFace::Bottom => BlockFace::Top,
+            BlockFace::Top => BlockFace::Bottom,
+            BlockFace::North => BlockFace::South,
+            BlockFace::South => BlockFace::North,
+            BlockFace::West => BlockFace::East,
+            BlockFace::East => BlockFace::West,
+        }
+    }
+
+    // The face whose normal is closest to `vector`; ties go to X, then Y
+    pub fn from_vector(vector: DVec3) -> Self {
+        let abs = vector.abs();
+        if abs.x >= abs.y && abs.x >= abs.z {
+            if vector.x < 0.0 { BlockFace::West } else { BlockFace::East }
+        } else if abs.y >= abs.z {
+            if vector.y < 0.0 { BlockFace::Bottom } else { BlockFace::Top }
+        } else if vector.z < 0.0 {
+            BlockFace::North
+        } else {
+            BlockFace::South
+        }
+    }
+}
+
 // Struct to represent the result returned to JavaScript
-#[napi(object)]
+#[cfg_attr(feature = "napi", napi(object))]
 #[derive(Debug)]
 pub struct RaycastResult {
     pub position: WorldCoords, // Position of the intersected block
     pub face: u32,             // Numeric value of the BlockFace enum
     pub intersect_point: Vec3Arg, // Exact point of intersection
+    pub exited_loaded_terrain: bool, // True if the ray crossed a chunk that isn't loaded
+    pub distance: f64,         // Euclidean distance from the origin to `intersect_point`
+    pub entered_inside: bool,  // True if the origin was inside the hit box (the hit is at the origin)
+    pub shape: Option<HitShape>, // The collision box that was hit, if the block has collision shapes
+}
+
+// One of the hit block's collision boxes, relative to the block origin
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone)]
+pub struct HitShape {
+    pub index: u32, // Index into the block state's collision shapes
+    pub min: Vec3Arg,
+    pub max: Vec3Arg,
+}
+
+impl From<RaycastHit> for RaycastResult {
+    fn from(hit: RaycastHit) -> Self {
+        RaycastResult {
+            position: hit.position,
+            face: hit.face as u32,
+            intersect_point: hit.point.into(),
+            exited_loaded_terrain: hit.exited_loaded_terrain,
+            distance: hit.t,
+            entered_inside: hit.entered_inside,
+            shape: hit.shape.map(|(index, shape)| HitShape {
+                index,
+                min: DVec3::new(shape[0], shape[1], shape[2]).into(),
+                max: DVec3::new(shape[3], shape[4], shape[5]).into(),
+            }),
+        }
+    }
+}
+
+// A box tested against the ray while tracing, in world space
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone)]
+pub struct TraceShape {
+    pub min: Vec3Arg,
+    pub max: Vec3Arg,
+    pub hit_t: Option<f64>, // Entry distance of the slab test, if the ray's line crosses the box
+}
+
+// A voxel visited while tracing, in visiting order
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone)]
+pub struct TraceVoxel {
+    pub position: WorldCoords,
+    pub t: f64, // Distance along the ray where it entered the voxel
+    pub state_id: u32,
+    pub loaded: bool,
+    pub shapes: Vec<TraceShape>,
+}
+
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug)]
+pub struct RaycastTrace {
+    pub hit: Option<RaycastResult>,
+    pub exited_loaded_terrain: bool, // True if the ray crossed a chunk that isn't loaded, hit or not
+    pub voxels: Vec<TraceVoxel>,
+}
+
+// A block raycast's result whether or not it hit anything
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug)]
+pub struct RaycastOutcome {
+    pub hit: Option<RaycastResult>,
+    pub exited_loaded_terrain: bool, // True if the ray crossed a chunk that isn't loaded, hit or not
+}
+
+// Options for a single block raycast
+#[derive(Debug, Clone)]
+pub struct RaycastSettings {
+    pub max_distance: f64,
+    pub non_solid_exceptions: HashSet<String>, // Non-solid block names or `#` tags that should still be hit
+    pub unloaded_mode: UnloadedChunkMode,
+    pub min_y: Option<i32>,
+    pub max_y: Option<i32>,
+    pub matcher: Option<BlockMatcher>, // When set, exactly the matching blocks are hit instead of solid ones
+    pub fluid_mode: FluidMode,
+    pub shape_source: ShapeSource,
+    pub return_all: bool, // Report every block hit along the ray, not just the first
+}
+
+impl RaycastSettings {
+    // Plain raycast against solid blocks, passing through unloaded chunks
+    pub fn new(max_distance: f64) -> Self {
+        RaycastSettings {
+            max_distance,
+            non_solid_exceptions: HashSet::new(),
+            unloaded_mode: UnloadedChunkMode::Air,
+            min_y: None,
+            max_y: None,
+            matcher: None,
+            fluid_mode: FluidMode::None,
+            shape_source: ShapeSource::Collision,
+            return_all: false,
+        }
+    }
+
+    // Settings from the positional arguments of the JS raycasting methods
+    #[cfg(feature = "napi")]
+    pub fn from_args(
+        max_distance: f64,
+        non_solid_exceptions: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> napi::Result<Self> {
+        Ok(RaycastSettings {
+            non_solid_exceptions: non_solid_exceptions.unwrap_or_default().into_iter().collect(),
+            unloaded_mode: UnloadedChunkMode::parse(unloaded_chunks.as_deref())?,
+            min_y,
+            max_y,
+            ..RaycastSettings::new(max_distance)
+        })
+    }
+
+    // Whether a block is a target of the ray: matched by the matcher if there is one, else solid or an exception
+    pub fn is_target(&self, registry: &BlockRegistry, state_id: u32, block: &Block) -> bool {
+        match &self.matcher {
+            Some(matcher) => matcher.matches(state_id),
+            None => registry.is_solid(state_id) || self.is_exception(registry, block),
+        }
+    }
+
+    // The fluid surface box the ray hits in a block, per the fluid mode. Waterlogged blocks count as water sources.
+    pub fn fluid_box(&self, registry: &BlockRegistry, state_id: u32, block: &Block) -> Option<ShapeBox> {
+        if self.fluid_mode == FluidMode::None { return None; }
+        let level: u32 = match block.name.as_str() {
+            "water" | "lava" => registry.state_property(state_id, "level")?.parse().ok()?,
+            _ if registry.state_property(state_id, "waterlogged").as_deref() == Some("true") => 0,
+            _ => return None,
+        };
+        if self.fluid_mode == FluidMode::Source && level != 0 { return None; }
+        // Levels 1-7 are flowing fluid getting lower, 8 and up are falling fluid filling the block
+        let height = if level >= 8 { 1.0 } else { (8 - level) as f64 / 9.0 };
+        Some([0.0, 0.0, 0.0, 1.0, height, 1.0])
+    }
+
+    // Whether a non-solid block is listed in the exceptions, by name or by one of its tags
+    pub fn is_exception(&self, registry: &BlockRegistry, block: &Block) -> bool {
+        self.non_solid_exceptions.contains(&block.name)
+            || self.non_solid_exceptions.iter().any(|entry| entry.starts_with('#') && registry.block_has_tag(block.id, entry))
+    }
+}
+
+// Closest hit found by a block raycast
+#[derive(Debug, Clone, Copy)]
+pub struct RaycastHit {
+    pub position: WorldCoords,
+    pub face: BlockFace,
+    pub t: f64,      // Distance along the normalized ray
+    pub point: DVec3,
+    pub exited_loaded_terrain: bool,
+    pub entered_inside: bool,
+    pub shape: Option<(u32, ShapeBox)>, // Index and bounds of the collision box that was hit
+}
+
+// How the raycaster treats blocks inside chunks that aren't loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnloadedChunkMode {
+    Air,   // Pass through unloaded chunks as if they were empty
+    Solid, // Treat every unloaded block as a full cube
+    Stop,  // End the ray at the first unloaded block
+}
+
+impl UnloadedChunkMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "air" => Some(UnloadedChunkMode::Air),
+            "solid" => Some(UnloadedChunkMode::Solid),
+            "stop" => Some(UnloadedChunkMode::Stop),
+            _ => None,
+        }
+    }
+
+    // The mode named by an optional JS argument, `air` if none is given
+    #[cfg(feature = "napi")]
+    pub fn parse(name: Option<&str>) -> napi::Result<Self> {
+        match name {
+            None => Ok(UnloadedChunkMode::Air),
+            Some(name) => UnloadedChunkMode::from_name(name).ok_or_else(|| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Unknown unloaded chunk mode: {}", name)
+            )),
+        }
+    }
+}
+
+// Which fluid blocks the raycaster hits, at their surface height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluidMode {
+    None,   // Fluids are passed through unless matched like any other block
+    Source, // Source blocks (and waterlogged blocks) are hit
+    Any,    // Source and flowing fluid are hit
+}
+
+impl FluidMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(FluidMode::None),
+            "source" => Some(FluidMode::Source),
+            "any" => Some(FluidMode::Any),
+            _ => None,
+        }
+    }
+}
+
+// Which boxes the raycaster tests for the blocks it hits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeSource {
+    Collision, // Collision boxes; blocks without any are hit as full cubes
+    Outline,   // Outline (selection) boxes, what the game's crosshair targets
+    Full,      // Every block is a full cube
+}
+
+impl ShapeSource {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "collision" => Some(ShapeSource::Collision),
+            "outline" => Some(ShapeSource::Outline),
+            "full" => Some(ShapeSource::Full),
+            _ => None,
+        }
+    }
 }
 
 // Struct to receive Vec3 arguments from JavaScript
-#[napi(object)]
+#[cfg_attr(feature = "napi", napi(object))]
 #[derive(Debug, Clone, Copy)]
 pub struct Vec3Arg {
     pub x: f64,
@@ -33,6 +318,14 @@ pub struct Vec3Arg {
     pub z: f64,
 }
 
+// Struct to receive axis-aligned bounding boxes from JavaScript
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone, Copy)]
+pub struct AabbArg {
+    pub min: Vec3Arg,
+    pub max: Vec3Arg,
+}
+
 impl From<Vec3Arg> for DVec3 {
     fn from(arg: Vec3Arg) -> Self {
         DVec3::new(arg.x, arg.y, arg.z)
@@ -45,12 +338,256 @@ impl From<DVec3> for Vec3Arg {
     }
 }
 
+// Minecraft look angles are in degrees: yaw 0 faces +Z (south) and turns towards -X (west at 90), positive pitch
+// looks down. Unit view direction for a yaw and pitch, as the game's `Entity.calculateViewVector`.
+pub fn direction_from_look(yaw: f64, pitch: f64) -> DVec3 {
+    let (yaw, pitch) = (yaw.to_radians(), pitch.to_radians());
+    DVec3::new(-yaw.sin() * pitch.cos(), -pitch.sin(), yaw.cos() * pitch.cos())
+}
+
+// Yaw (wrapped to -180..180) and pitch (-90..90) looking along `direction`, as the game's `Entity.lookAt`
+pub fn look_angles(direction: DVec3) -> (f64, f64) {
+    let horizontal = direction.x.hypot(direction.z);
+    let yaw = (direction.z.atan2(direction.x).to_degrees() - 90.0).rem_euclid(360.0);
+    let yaw = if yaw >= 180.0 { yaw - 360.0 } else { yaw };
+    let pitch = 0.0 - direction.y.atan2(horizontal).to_degrees();
+    (yaw, pitch)
+}
+
+/// Where a block raycast reads blocks from.
+pub trait BlockSource {
+    /// The state at a position, or `None` if its column isn't loaded.
+    fn block_state_id(&self, pos: WorldCoords) -> Option<u32>;
+
+    /// Which 4x4x4 cells of a section hold non-air blocks (see `ChunkSection::occupancy`), if the source keeps
+    /// track. Rays skip the empty cells without reading their blocks; `None`, the default, skips nothing.
+    fn section_occupancy(&self, _section: SectionCoords) -> Option<u64> {
+        None
+    }
+
+    /// Inclusive block Y range of the dimension, which rays don't search past. Defaults to the range columns store.
+    fn y_range(&self) -> (i32, i32) {
+        (MIN_CHUNK_Y, MAX_CHUNK_Y - 1)
+    }
+}
+
+// (distance, face, block, whether the origin is inside the hit box, collision box that was hit)
+type CandidateHit = (f64, BlockFace, WorldCoords, bool, Option<(u32, ShapeBox)>);
+
+// Turns a slab test result into a hit. A negative entry distance means the origin is inside the box:
+// that's a hit at distance 0, reported on the face the ray would have entered through.
+pub fn inside_hit(t: f64, face: BlockFace, direction: DVec3) -> (f64, BlockFace, bool) {
+    if t < 0.0 { (0.0, entry_face(direction), true) } else { (t, face, false) }
+}
+
+// Adds a tested box to the voxel being traced, if tracing
+fn record_shape(trace: &mut Option<&mut Vec<TraceVoxel>>, min: DVec3, max: DVec3, hit: Option<(f64, BlockFace)>) {
+    if let Some(voxel) = trace.as_deref_mut().and_then(|voxels| voxels.last_mut()) {
+        voxel.shapes.push(TraceShape { min: min.into(), max: max.into(), hit_t: hit.map(|(t, _)| t) });
+    }
+}
+
+/// Block raycast core, shared by every front end: the closest hit, or with `return_all` the closest hit in every
+/// block along the ray, nearest first. `trace` collects the visited voxels, for debugging.
+pub fn find_ray_hits(
+    source: &impl BlockSource,
+    registry: &BlockRegistry,
+    origin: DVec3,
+    direction: DVec3,
+    settings: &RaycastSettings,
+    trace: Option<&mut Vec<TraceVoxel>>,
+) -> Vec<RaycastHit> {
+    find_ray_outcome(source, registry, origin, direction, settings, trace).hits
+}
+
+/// The hits of a block raycast, and whether the ray crossed unloaded chunks (which misses report too)
+#[derive(Debug, Clone, Default)]
+pub struct RayOutcome {
+    pub hits: Vec<RaycastHit>,
+    pub exited_loaded_terrain: bool,
+}
+
+/// `find_ray_hits`, also telling whether the ray crossed unloaded chunks when it hit nothing.
+pub fn find_ray_outcome(
+    source: &impl BlockSource,
+    registry: &BlockRegistry,
+    origin: DVec3,
+    direction: DVec3,
+    settings: &RaycastSettings,
+    mut trace: Option<&mut Vec<TraceVoxel>>,
+) -> RayOutcome {
+    let direction = direction.normalize_or_zero();
+    if direction == DVec3::ZERO { return RayOutcome::default(); }
+
+    let inv_dir = DVec3::new(1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z);
+
+    let mut iterator = RaycastIterator::new(origin, direction, settings.max_distance)
+        .with_y_bounds(settings.min_y, settings.max_y, source.y_range());
+
+    let mut closest_hit: Option<CandidateHit> = None;
+    let mut all_hits: Vec<CandidateHit> = Vec::new();
+    let mut exited_loaded_terrain = false;
+    let mut occupancy: Option<(SectionCoords, Option<u64>)> = None; // Of the section the ray was last in
+
+    while let Some((block_pos, entered_face)) = iterator.next() {
+        // Nothing in blocks past the closest hit can beat it, and unloaded chunks there weren't crossed to reach it
+        if closest_hit.is_some_and(|closest| closest.0 < iterator.current_t) { break; }
+        let block = source.block_state_id(block_pos);
+        let (loaded, state_id) = (block.is_some(), block.unwrap_or(0));
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.push(TraceVoxel { position: block_pos, t: iterator.current_t, state_id, loaded, shapes: Vec::new() });
+        }
+
+        if !loaded {
+            exited_loaded_terrain = true;
+            let boundary_hit = match settings.unloaded_mode {
+                UnloadedChunkMode::Air => continue,
+                UnloadedChunkMode::Solid => {
+                    let block_world_pos = DVec3::new(block_pos.x as f64, block_pos.y as f64, block_pos.z as f64);
+                    let hit = intersect_aabb(block_world_pos, block_world_pos + DVec3::ONE, origin, inv_dir);
+                    record_shape(&mut trace, block_world_pos, block_world_pos + DVec3::ONE, hit);
+                    hit.filter(|(t, _)| *t <= settings.max_distance).map(|(t, face)| {
+                        let (t, face, inside) = inside_hit(t, face, direction);
+                        (t, face, block_pos, inside, None)
+                    })
+                }
+                // Only reported if nothing loaded was hit before the boundary
+                UnloadedChunkMode::Stop => Some((iterator.current_t, entered_face, block_pos, false, None)),
+            };
+            if let Some(hit) = boundary_hit {
+                if settings.return_all {
+                    all_hits.push(hit);
+                } else if closest_hit.is_none_or(|closest| hit.0 < closest.0) {
+                    closest_hit = Some(hit);
+                }
+            }
+            break;
+        }
+
+        if state_id == 0 {
+            // Skip the rest of an empty cell, or of a whole empty section, without reading its blocks. Not done
+            // while tracing, which reports every voxel.
+            if trace.is_none() {
+                let section = SectionCoords { x: block_pos.x >> 4, y: block_pos.y >> 4, z: block_pos.z >> 4 };
+                if occupancy.is_none_or(|(coords, _)| coords != section) {
+                    occupancy = Some((section, source.section_occupancy(section)));
+                }
+                if let Some((_, Some(mask))) = occupancy {
+                    let cell = |v: i32| v.rem_euclid(16) >> 2;
+                    let bit = (cell(block_pos.y) * 4 + cell(block_pos.z)) * 4 + cell(block_pos.x);
+                    if mask == 0 {
+                        let min = WorldCoords { x: section.x << 4, y: section.y << 4, z: section.z << 4 };
+                        iterator.skip_box(min, WorldCoords { x: min.x + 15, y: min.y + 15, z: min.z + 15 });
+                    } else if mask & (1 << bit) == 0 {
+                        let min = WorldCoords { x: block_pos.x & !3, y: block_pos.y & !3, z: block_pos.z & !3 };
+                        iterator.skip_box(min, WorldCoords { x: min.x + 3, y: min.y + 3, z: min.z + 3 });
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(block_data) = registry.block(state_id) {
+            let is_solid = registry.is_solid(state_id);
+            let is_target = settings.is_target(registry, state_id, block_data);
+            let fluid_box = settings.fluid_box(registry, state_id, block_data);
+
+            if is_target || fluid_box.is_some() {
+                let block_world_pos = DVec3::new(block_pos.x as f64, block_pos.y as f64, block_pos.z as f64);
+                let mut block_hit: Option<CandidateHit> = None;
+
+                // Target blocks without collision boxes (or with `full` shapes) are hit as full cubes;
+                // fluid surfaces are tested along with them
+                let mut shapes: Vec<ShapeBox> = Vec::new();
+                if is_target {
+                    match settings.shape_source {
+                        ShapeSource::Collision => shapes.extend_from_slice(registry.collision_shapes(state_id)),
+                        ShapeSource::Outline => shapes.extend(registry.outline_shapes(state_id)),
+                        ShapeSource::Full => {}
+                    }
+                    if shapes.is_empty() && !is_solid { shapes.extend_from_slice(&FULL_CUBE); }
+                }
+                shapes.extend(fluid_box);
+
+                // Shapes are tested four at a time; unused lanes hold an empty box and are ignored
+                for (batch_index, batch) in shapes.chunks(4).enumerate() {
+                    // Shape coords are relative 0-1, convert to world AABBs
+                    let mut aabb_mins = [block_world_pos; 4];
+                    let mut aabb_maxs = [block_world_pos; 4];
+                    for (lane, shape) in batch.iter().enumerate() {
+                        aabb_mins[lane] = block_world_pos + DVec3::new(shape[0], shape[1], shape[2]);
+                        aabb_maxs[lane] = block_world_pos + DVec3::new(shape[3], shape[4], shape[5]);
+                    }
+                    let hits = intersect_aabb4(aabb_mins, aabb_maxs, origin, inv_dir);
+
+                    for (lane, shape) in batch.iter().enumerate() {
+                        let hit = hits[lane];
+                        record_shape(&mut trace, aabb_mins[lane], aabb_maxs[lane], hit);
+                        if let Some((t, face)) = hit {
+                            // Boxes containing the origin (e.g. standing in a snow layer) are hit right away
+                            let (t, face, inside) = inside_hit(t, face, direction);
+                            // `direction` is normalized, so `t` is the exact distance
+                            if t <= settings.max_distance && block_hit.is_none_or(|best| t < best.0) {
+                                let index = (batch_index * 4 + lane) as u32;
+                                block_hit = Some((t, face, block_pos, inside, Some((index, *shape))));
+                            }
+                        }
+                    }
+                }
+
+                // Fallback for solid blocks if no specific shapes were found or intersected
+                if block_hit.is_none() && is_solid && is_target {
+                     let aabb_min = block_world_pos;
+                     let aabb_max = block_world_pos + DVec3::ONE;
+                     let hit = intersect_aabb(aabb_min, aabb_max, origin, inv_dir);
+                     record_shape(&mut trace, aabb_min, aabb_max, hit);
+                     if let Some((t, face)) = hit {
+                         let (t, face, inside) = inside_hit(t, face, direction);
+                         if t <= settings.max_distance {
+                             block_hit = Some((t, face, block_pos, inside, None));
+                         }
+                     }
+                }
+
+                if let Some(hit) = block_hit {
+                    if settings.return_all {
+                        all_hits.push(hit);
+                    } else if closest_hit.is_none_or(|closest| hit.0 < closest.0) {
+                        closest_hit = Some(hit);
+                        // Optimization: If the closest hit found is closer than the current ray position, stop.
+                        if hit.0 < iterator.current_t { break; }
+                    }
+                }
+            }
+        }
+    }
+
+    if settings.return_all {
+        all_hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+    } else {
+        all_hits.extend(closest_hit);
+    }
+    let hits = all_hits
+        .into_iter()
+        .map(|(t, face, position, entered_inside, shape)| RaycastHit {
+            position,
+            face,
+            t,
+            point: origin + direction * t,
+            exited_loaded_terrain,
+            entered_inside,
+            shape,
+        })
+        .collect();
+    RayOutcome { hits, exited_loaded_terrain }
+}
+
 // --- Raycasting Algorithm (Amanatides & Woo variant) ---
 
 pub struct RaycastIterator {
     pub origin: DVec3,
     pub direction: DVec3,
-    pub max_distance_sq: f64,
+    pub max_t: f64, // Largest `t` within the max distance (`t` is in units of `direction`)
 
     pub current_pos: WorldCoords,
     pub step: WorldCoords,
@@ -60,6 +597,10 @@ pub struct RaycastIterator {
     pub current_t: f64,
     pub just_started: bool,
     pub current_face: BlockFace,
+
+    // Inclusive block Y range the ray is allowed to search
+    pub min_y: i32,
+    pub max_y: i32,
 }
 
 impl RaycastIterator {
@@ -101,7 +642,7 @@ impl RaycastIterator {
         RaycastIterator {
             origin,
             direction,
-            max_distance_sq: max_distance * max_distance,
+            max_t: max_distance / direction.length(),
             current_pos,
             step,
             t_max,
@@ -109,14 +650,34 @@ impl RaycastIterator {
             current_t: 0.0,
             just_started: true,
             current_face: BlockFace::Bottom, // Initial arbitrary face
+            min_y: MIN_CHUNK_Y,
+            max_y: MAX_CHUNK_Y - 1,
         }
     }
 
+    /// Narrows the searched Y range to `min_y..=max_y` within the inclusive `world` range, which is the default for
+    /// bounds not given. Bounds outside the world range are clamped to it.
+    pub fn with_y_bounds(mut self, min_y: Option<i32>, max_y: Option<i32>, world: (i32, i32)) -> Self {
+        self.min_y = min_y.unwrap_or(world.0).max(world.0);
+        self.max_y = max_y.unwrap_or(world.1).min(world.1);
+        self
+    }
+
+    // True once the ray is outside the Y range and heading further away from it
+    fn left_y_bounds(&self) -> bool {
+        (self.current_pos.y > self.max_y && self.direction.y >= 0.0)
+            || (self.current_pos.y < self.min_y && self.direction.y <= 0.0)
+    }
+
     pub fn next(&mut self) -> Option<(WorldCoords, BlockFace)> {
+        if self.min_y > self.max_y || self.left_y_bounds() {
+            return None; // Nothing left to hit in this direction
+        }
+
         if self.just_started {
             self.just_started = false;
             // Check if the starting block itself is the target
-            if self.current_t * self.current_t * self.direction.length_squared() <= self.max_distance_sq {
+            if self.current_t <= self.max_t {
                  // The initial face doesn't make much sense, maybe return a special value or calculate based on entry?
                  // For simplicity, let's just return the current block coords and an arbitrary face for the start.
                  // A more robust implementation might calculate the entry face if starting inside.
@@ -126,6 +687,18 @@ impl RaycastIterator {
             }
         }
 
+        let face = self.step();
+        self.current_face = face;
+
+        if self.current_t > self.max_t || self.left_y_bounds() {
+            None
+        } else {
+            Some((self.current_pos, face.clone()))
+        }
+    }
+
+    // Moves to the next voxel along the ray, returning the face it was entered through
+    fn step(&mut self) -> BlockFace {
         let face: BlockFace;
         if self.t_max.x < self.t_max.y {
             if self.t_max.x < self.t_max.z {
@@ -152,13 +725,33 @@ impl RaycastIterator {
                 face = if self.step.z > 0 { BlockFace::North } else { BlockFace::South };
             }
         }
-        self.current_face = face;
+        face
+    }
 
-        // Check distance using squared length to avoid sqrt
-        if self.current_t * self.current_t * self.direction.length_squared() > self.max_distance_sq {
-            None
+    // The axis (0 = x, 1 = y, 2 = z) `step` crosses next
+    fn next_axis(&self) -> usize {
+        if self.t_max.x < self.t_max.y {
+            if self.t_max.x < self.t_max.z { 0 } else { 2 }
+        } else if self.t_max.y < self.t_max.z {
+            1
         } else {
-            Some((self.current_pos, face.clone()))
+            2
+        }
+    }
+
+    /// Advances through the voxels of the inclusive box `min..=max` the ray is in, without returning them, so the
+    /// next call to `next` returns the first voxel after the box.
+    pub fn skip_box(&mut self, min: WorldCoords, max: WorldCoords) {
+        if self.just_started { return; }
+        loop {
+            let axis = self.next_axis();
+            let (position, step, lo, hi, t) = match axis {
+                0 => (self.current_pos.x, self.step.x, min.x, max.x, self.t_max.x),
+                1 => (self.current_pos.y, self.step.y, min.y, max.y, self.t_max.y),
+                _ => (self.current_pos.z, self.step.z, min.z, max.z, self.t_max.z),
+            };
+            if !(lo..=hi).contains(&(position + step)) || t > self.max_t { return; }
+            self.current_face = self.step();
         }
     }
 
@@ -168,40 +761,279 @@ impl RaycastIterator {
     }
 }
 
+// The face a ray moving in `direction` enters boxes through along its dominant axis
+pub fn entry_face(direction: DVec3) -> BlockFace {
+    BlockFace::from_vector(-direction)
+}
+
 // --- AABB Intersection Test (Slab Method) ---
+// Returns the entry distance and the face the ray enters through. The face comes from the axis whose slab
+// was entered last, tracked while testing rather than recovered from float comparisons afterwards; on exact
+// ties (edges and corners) the first axis in X, Y, Z order wins. The distance is negative when the origin is
+// inside the box. Axes the ray is parallel to (infinite `ray_inv_dir`) are handled without producing NaNs.
 pub fn intersect_aabb(aabb_min: DVec3, aabb_max: DVec3, ray_origin: DVec3, ray_inv_dir: DVec3) -> Option<(f64, BlockFace)> {
-    let tx1 = (aabb_min.x - ray_origin.x) * ray_inv_dir.x;
-    let tx2 = (aabb_max.x - ray_origin.x) * ray_inv_dir.x;
+    let axes = [
+        (ray_origin.x, aabb_min.x, aabb_max.x, ray_inv_dir.x, BlockFace::West, BlockFace::East),
+        (ray_origin.y, aabb_min.y, aabb_max.y, ray_inv_dir.y, BlockFace::Bottom, BlockFace::Top),
+        (ray_origin.z, aabb_min.z, aabb_max.z, ray_inv_dir.z, BlockFace::North, BlockFace::South),
+    ];
 
-    let mut tmin = tx1.min(tx2);
-    let mut tmax = tx1.max(tx2);
+    let mut t_enter = f64::NEG_INFINITY;
+    let mut t_exit = f64::INFINITY;
+    let mut face = None;
 
-    let ty1 = (aabb_min.y - ray_origin.y) * ray_inv_dir.y;
-    let ty2 = (aabb_max.y - ray_origin.y) * ray_inv_dir.y;
+    for (origin, min, max, inv_dir, min_face, max_face) in axes {
+        if inv_dir.is_infinite() || inv_dir.is_nan() {
+            // Parallel to this slab: either always inside it or never
+            if origin < min || origin > max { return None; }
+            continue;
+        }
 
-    tmin = tmin.max(ty1.min(ty2));
-    tmax = tmax.min(ty1.max(ty2));
+        let (t_near, t_far, near_face) = if inv_dir > 0.0 {
+            ((min - origin) * inv_dir, (max - origin) * inv_dir, min_face)
+        } else {
+            ((max - origin) * inv_dir, (min - origin) * inv_dir, max_face)
+        };
+        if t_near > t_enter {
+            t_enter = t_near;
+            face = Some(near_face);
+        }
+        t_exit = t_exit.min(t_far);
+    }
 
-    let tz1 = (aabb_min.z - ray_origin.z) * ray_inv_dir.z;
-    let tz2 = (aabb_max.z - ray_origin.z) * ray_inv_dir.z;
+    // No face means the ray doesn't move at all
+    let face = face?;
+    if t_enter > t_exit || t_exit < 0.0 { return None; }
+    Some((t_enter, face))
+}
 
-    tmin = tmin.max(tz1.min(tz2));
-    tmax = tmax.min(tz1.max(tz2));
+// The same slab test against four boxes at once. Lanes are laid out side by side and the ray's per-axis
+// branches are shared by all of them, so the inner loops compile to SIMD without `std::simd` (nightly only).
+// Each lane gives exactly what `intersect_aabb` gives for that box.
+pub fn intersect_aabb4(aabb_mins: [DVec3; 4], aabb_maxs: [DVec3; 4], ray_origin: DVec3, ray_inv_dir: DVec3) -> [Option<(f64, BlockFace)>; 4] {
+    const AXIS_FACES: [(BlockFace, BlockFace); 3] = [
+        (BlockFace::West, BlockFace::East),
+        (BlockFace::Bottom, BlockFace::Top),
+        (BlockFace::North, BlockFace::South),
+    ];
 
-    if tmax >= tmin && tmax >= 0.0 { // Ensure intersection is not behind the origin and tmin <= tmax
-        // Determine the face of intersection based on which slab boundary was hit at tmin
-        let mut face = BlockFace::Bottom; // Default, should be overwritten
-        let epsilon = 1e-6; // Tolerance for float comparisons
+    let mut t_enter = [f64::NEG_INFINITY; 4];
+    let mut t_exit = [f64::INFINITY; 4];
+    let mut face: [Option<BlockFace>; 4] = [None; 4];
+    let mut missed = [false; 4];
 
-        if (tmin - tx1).abs() < epsilon { face = BlockFace::West; }
-        else if (tmin - tx2).abs() < epsilon { face = BlockFace::East; }
-        else if (tmin - ty1).abs() < epsilon { face = BlockFace::Bottom; }
-        else if (tmin - ty2).abs() < epsilon { face = BlockFace::Top; }
-        else if (tmin - tz1).abs() < epsilon { face = BlockFace::North; }
-        else if (tmin - tz2).abs() < epsilon { face = BlockFace::South; }
+    for (axis, (min_face, max_face)) in AXIS_FACES.into_iter().enumerate() {
+        let origin = ray_origin[axis];
+        let inv_dir = ray_inv_dir[axis];
+        let min = aabb_mins.map(|v| v[axis]);
+        let max = aabb_maxs.map(|v| v[axis]);
 
-        Some((tmin, face))
-    } else {
-        None
+        if inv_dir.is_infinite() || inv_dir.is_nan() {
+            for lane in 0..4 {
+                missed[lane] |= origin < min[lane] || origin > max[lane];
+            }
+            continue;
+        }
+
+        let (near, far, near_face) = if inv_dir > 0.0 { (min, max, min_face) } else { (max, min, max_face) };
+        for lane in 0..4 {
+            let t_near = (near[lane] - origin) * inv_dir;
+            let t_far = (far[lane] - origin) * inv_dir;
+            if t_near > t_enter[lane] {
+                t_enter[lane] = t_near;
+                face[lane] = Some(near_face);
+            }
+            t_exit[lane] = t_exit[lane].min(t_far);
+        }
     }
-}
\ No newline at end of file
+
+    std::array::from_fn(|lane| {
+        let face = face[lane]?;
+        if missed[lane] || t_enter[lane] > t_exit[lane] || t_exit[lane] < 0.0 { return None; }
+        Some((t_enter[lane], face))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::test_registry;
+
+    // Air, except for a stone wall at x = 8; columns at x >= 16 aren't loaded
+    struct Terrain;
+
+    impl BlockSource for Terrain {
+        fn block_state_id(&self, pos: WorldCoords) -> Option<u32> {
+            match pos.x {
+                16.. => None,
+                8 => Some(1),
+                _ => Some(0),
+            }
+        }
+    }
+
+    #[test]
+    fn offset_position_saturates() {
+        let pos = WorldCoords { x: i32::MAX - 1, y: 0, z: i32::MIN + 1 };
+        assert_eq!(BlockFace::East.offset_position(pos, 5).x, i32::MAX);
+        assert_eq!(BlockFace::North.offset_position(pos, 5).z, i32::MIN);
+        assert_eq!(BlockFace::Top.offset_position(pos, i32::MIN), WorldCoords { y: i32::MIN, ..pos });
+        assert_eq!(BlockFace::Bottom.offset_position(pos, i32::MIN), WorldCoords { y: i32::MAX, ..pos });
+        assert_eq!(BlockFace::South.offset_position(pos, -2), WorldCoords { z: i32::MIN, ..pos });
+    }
+
+    // Open air in a dimension spanning y = 0..256, like the Nether
+    struct Nether;
+
+    impl BlockSource for Nether {
+        fn block_state_id(&self, _pos: WorldCoords) -> Option<u32> {
+            Some(0)
+        }
+
+        fn y_range(&self) -> (i32, i32) {
+            (0, 255)
+        }
+    }
+
+    // Lowest and highest Y a ray from y = 10.5 visits heading along `direction`
+    fn traced_y_span(source: &impl BlockSource, direction: DVec3) -> (i32, i32) {
+        let mut trace = Vec::new();
+        let settings = RaycastSettings::new(500.0);
+        find_ray_outcome(source, &test_registry(), DVec3::new(0.5, 10.5, 0.5), direction, &settings, Some(&mut trace));
+        let ys = trace.iter().map(|voxel| voxel.position.y);
+        (ys.clone().min().unwrap(), ys.max().unwrap())
+    }
+
+    #[test]
+    fn rays_stop_at_the_dimension_bounds() {
+        assert_eq!(traced_y_span(&Nether, DVec3::NEG_Y), (0, 10));
+        assert_eq!(traced_y_span(&Nether, DVec3::Y), (10, 255));
+        assert_eq!(traced_y_span(&Terrain, DVec3::NEG_Y), (MIN_CHUNK_Y, 10));
+
+        // Explicit bounds can only narrow the dimension's range
+        let iterator = RaycastIterator::new(DVec3::ZERO, DVec3::Y, 10.0).with_y_bounds(Some(-30), Some(400), Nether.y_range());
+        assert_eq!((iterator.min_y, iterator.max_y), (0, 255));
+    }
+
+    #[test]
+    fn misses_report_crossing_unloaded_chunks() {
+        let registry = test_registry();
+        let settings = RaycastSettings::new(40.0);
+        let along_wall = find_ray_outcome(&Terrain, &registry, DVec3::new(0.5, 0.5, 0.5), DVec3::Z, &settings, None);
+        assert!(along_wall.hits.is_empty() && !along_wall.exited_loaded_terrain);
+
+        // Passes over the wall and into the unloaded columns beyond it
+        let over = find_ray_outcome(&Terrain, &registry, DVec3::new(9.5, 0.5, 0.5), DVec3::X, &settings, None);
+        assert!(over.hits.is_empty() && over.exited_loaded_terrain);
+
+        let hit = find_ray_outcome(&Terrain, &registry, DVec3::new(0.5, 0.5, 0.5), DVec3::X, &settings, None);
+        assert_eq!(hit.hits[0].position, WorldCoords { x: 8, y: 0, z: 0 });
+        assert!(!hit.exited_loaded_terrain);
+    }
+
+    // xorshift64*, so the randomized cases below are the same on every run
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> f64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn range(&mut self, lo: f64, hi: f64) -> f64 {
+            lo + (hi - lo) * self.next()
+        }
+
+        fn vec(&mut self, lo: f64, hi: f64) -> DVec3 {
+            DVec3::new(self.range(lo, hi), self.range(lo, hi), self.range(lo, hi))
+        }
+    }
+
+    fn random_box(rng: &mut Rng) -> (DVec3, DVec3) {
+        let min = rng.vec(-10.0, 10.0);
+        (min, min + rng.vec(0.05, 3.0))
+    }
+
+    // A hit's point has to lie on the reported face, inside the box, with the ray entering through the face
+    fn assert_valid_hit(min: DVec3, max: DVec3, origin: DVec3, direction: DVec3, t: f64, face: BlockFace) {
+        let point = origin + direction * t;
+        let normal = face.normal();
+        let axis = normal.abs().max_position();
+        let plane = if normal[axis] > 0.0 { max[axis] } else { min[axis] };
+        assert!(t.is_finite() && t >= 0.0, "t = {}", t);
+        assert!((point[axis] - plane).abs() < 1e-9, "{:?} isn't on face {:?} of {:?}..{:?}", point, face, min, max);
+        assert!(point.cmpge(min - 1e-9).all() && point.cmple(max + 1e-9).all(), "{:?} is outside {:?}..{:?}", point, min, max);
+        assert!(normal.dot(direction) < 0.0, "ray {:?} leaves through face {:?}", direction, face);
+    }
+
+    #[test]
+    fn rays_into_boxes_hit_the_face_they_enter() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for case in 0..20_000 {
+            let (min, max) = random_box(&mut rng);
+            let origin = rng.vec(-20.0, 20.0);
+            if origin.cmpge(min).all() && origin.cmple(max).all() { continue; }
+            // Aimed at the interior, or just inside a corner for grazing hits along edges (exactly at a corner,
+            // rounding may go either way)
+            let corner = DVec3::select(rng.vec(0.0, 1.0).cmplt(DVec3::splat(0.5)), DVec3::splat(1e-6), DVec3::splat(1.0 - 1e-6));
+            let target = min + (max - min) * if case % 4 == 0 { corner } else { rng.vec(0.01, 0.99) };
+            let direction = target - origin;
+            let hit = intersect_aabb(min, max, origin, direction.recip());
+            let (t, face) = hit.unwrap_or_else(|| panic!("ray from {:?} to {:?} missed {:?}..{:?}", origin, target, min, max));
+            assert_valid_hit(min, max, origin, direction, t, face);
+        }
+    }
+
+    #[test]
+    fn axis_aligned_rays_never_produce_nan() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..5_000 {
+            let (min, max) = random_box(&mut rng);
+            for face in BlockFace::ALL {
+                // Starts inside the box's slabs on the other axes, a few blocks out along the face normal
+                let normal = face.normal();
+                let inside = min + (max - min) * rng.vec(0.0, 1.0);
+                let origin = DVec3::select(normal.cmpeq(DVec3::ZERO), inside, if normal.max_element() > 0.0 { max + 2.0 } else { min - 2.0 });
+                let direction = -normal * rng.range(0.1, 5.0);
+                let (t, hit_face) = intersect_aabb(min, max, origin, direction.recip()).expect("axis-aligned ray missed");
+                assert_eq!(hit_face, face);
+                assert_valid_hit(min, max, origin, direction, t, hit_face);
+
+                // Pointing away misses
+                assert!(intersect_aabb(min, max, origin, (-direction).recip()).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn rays_past_boxes_miss() {
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        for _ in 0..20_000 {
+            let (min, max) = random_box(&mut rng);
+            // Parallel to a face plane and just outside it
+            let origin = DVec3::new(rng.range(-20.0, 20.0), max.y + rng.range(0.001, 5.0), rng.range(-20.0, 20.0));
+            let direction = DVec3::new(rng.range(-1.0, 1.0), 0.0, rng.range(-1.0, 1.0));
+            assert!(intersect_aabb(min, max, origin, direction.recip()).is_none());
+        }
+    }
+
+    #[test]
+    fn four_lane_test_matches_single_box_test() {
+        let mut rng = Rng(0x0bad_5eed_0bad_5eed);
+        for _ in 0..5_000 {
+            let boxes: [(DVec3, DVec3); 4] = std::array::from_fn(|_| random_box(&mut rng));
+            let origin = rng.vec(-20.0, 20.0);
+            let mut direction = rng.vec(-1.0, 1.0);
+            // Some rays are parallel to an axis plane
+            if rng.next() < 0.3 { direction.y = 0.0; }
+            let inv_dir = direction.recip();
+            let lanes = intersect_aabb4(boxes.map(|b| b.0), boxes.map(|b| b.1), origin, inv_dir);
+            for (lane, (min, max)) in boxes.into_iter().enumerate() {
+                let single = intersect_aabb(min, max, origin, inv_dir);
+                assert_eq!(lanes[lane].map(|(t, face)| (t.to_bits(), face)), single.map(|(t, face)| (t.to_bits(), face)));
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// src/raycast_batch.rs
+use crate::raycast::{RaycastHit, RaycastSettings, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi::{Env, Task};
+use napi_derive::napi;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+// Numbers per ray in the packed input: origin x, y, z, direction x, y, z
+const RAY_STRIDE: usize = 6;
+// Numbers per ray in the packed output: block x, y, z, face, intersection point x, y, z, distance
+const RESULT_STRIDE: usize = 8;
+
+// Packs one result; misses have face -1 and NaN everywhere else
+fn pack_hit(hit: Option<RaycastHit>) -> [f64; RESULT_STRIDE] {
+    match hit {
+        Some(hit) => [
+            hit.position.x as f64,
+            hit.position.y as f64,
+            hit.position.z as f64,
+            hit.face as u32 as f64,
+            hit.point.x,
+            hit.point.y,
+            hit.point.z,
+            hit.t,
+        ],
+        None => [f64::NAN, f64::NAN, f64::NAN, -1.0, f64::NAN, f64::NAN, f64::NAN, f64::NAN],
+    }
+}
+
+pub struct BatchRaycast {
+    // A view of the world's columns and registries; events and caches aren't touched by raycasts
+    world: NapiWorld,
+    rays: Vec<f64>,
+    settings: RaycastSettings,
+}
+
+impl Task for BatchRaycast {
+    type Output = Vec<f64>;
+    type JsValue = Float64Array;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.rays
+            .par_chunks_exact(RAY_STRIDE)
+            .flat_map_iter(|ray| {
+                let origin = DVec3::new(ray[0], ray[1], ray[2]);
+                let direction = DVec3::new(ray[3], ray[4], ray[5]);
+                pack_hit(self.world.cast_ray(origin, direction, &self.settings))
+            })
+            .collect())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(Float64Array::new(output))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Casts many rays at once on a thread pool, without blocking the event loop.
+    ///
+    /// `rays` holds 6 numbers per ray: origin x, y, z and direction x, y, z. The other arguments are the same as
+    /// for `raycast` and apply to every ray. Resolves to 8 numbers per ray, in the same order: block x, y, z, face,
+    /// intersection point x, y, z and distance. Rays that hit nothing have face -1 and NaN for everything else.
+    /// Blocks written while the rays are being cast may or may not be seen by them.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "rays: Float64Array, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
+    pub fn raycast_batch(
+        &self,
+        rays: Float64Array,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<AsyncTask<BatchRaycast>> {
+        if !rays.len().is_multiple_of(RAY_STRIDE) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Ray buffer length must be a multiple of {}, got {}", RAY_STRIDE, rays.len()),
+            ));
+        }
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        Ok(AsyncTask::new(BatchRaycast {
+            world: NapiWorld::from_parts(self.column_store(), Arc::clone(&self.registry), Arc::clone(&self.biomes)),
+            rays: rays.to_vec(),
+            settings,
+        }))
+    }
+
+    /// Casts a ray like `raycast` but writes the result into `out` instead of allocating an object, for callers
+    /// casting many rays per tick. The 8 numbers of the result (same layout as `raycastBatch`) go at index
+    /// `offset * 8` (`offset` defaults to 0), so one array can pool the results of several rays. Returns whether
+    /// the ray hit a block.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, out: Float64Array, offset?: number | null, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
+    pub fn raycast_into(
+        &self,
+        origin: Vec3Arg,
+        direction: Vec3Arg,
+        max_distance: f64,
+        mut out: Float64Array,
+        offset: Option<u32>,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<bool> {
+        let start = offset.unwrap_or(0) as usize * RESULT_STRIDE;
+        let Some(slot) = out.get_mut(start..start + RESULT_STRIDE) else {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Output array of length {} has no room for a result at index {}", out.len(), start),
+            ));
+        };
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        let hit = self.cast_ray(DVec3::from(origin), DVec3::from(direction), &settings);
+        let hit_something = hit.is_some();
+        slot.copy_from_slice(&pack_hit(hit));
+        Ok(hit_something)
+    }
+
+    /// Synchronous counterpart of `raycastBatch` writing into a caller-provided array: `out` must hold 8 numbers
+    /// per ray of `rays`, and is filled in the layout `raycastBatch` resolves to. Returns the number of hits.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "rays: Float64Array, out: Float64Array, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
+    pub fn raycast_batch_into(
+        &self,
+        rays: Float64Array,
+        mut out: Float64Array,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<u32> {
+        if !rays.len().is_multiple_of(RAY_STRIDE) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Ray buffer length must be a multiple of {}, got {}", RAY_STRIDE, rays.len()),
+            ));
+        }
+        let ray_count = rays.len() / RAY_STRIDE;
+        if out.len() < ray_count * RESULT_STRIDE {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Output array must hold {} numbers for {} rays, got {}", ray_count * RESULT_STRIDE, ray_count, out.len()),
+            ));
+        }
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        let mut hits = 0;
+        for (ray, slot) in rays.chunks_exact(RAY_STRIDE).zip(out.chunks_exact_mut(RESULT_STRIDE)) {
+            let hit = self.cast_ray(DVec3::new(ray[0], ray[1], ray[2]), DVec3::new(ray[3], ray[4], ray[5]), &settings);
+            hits += hit.is_some() as u32;
+            slot.copy_from_slice(&pack_hit(hit));
+        }
+        Ok(hits)
+    }
+}
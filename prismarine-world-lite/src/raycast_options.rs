@@ -0,0 +1,120 @@
+// src/raycast_options.rs
+use crate::coords::WorldCoords;
+use crate::entities::EntityHitbox;
+use crate::raycast::{FluidMode, HitShape, RaycastResult, RaycastSettings, ShapeSource, UnloadedChunkMode, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Options for `raycast_ex`. Everything but `max_distance` is optional.
+#[napi(object)]
+pub struct RaycastOptions {
+    pub max_distance: f64,
+    #[napi(ts_type = "Array<number | string>")]
+    pub matcher: Option<Vec<Either<u32, String>>>, // State IDs, block names or `#` tags to hit instead of solid blocks
+    #[napi(ts_type = "'none' | 'source' | 'any'")]
+    pub fluid_mode: Option<String>,          // Fluids to hit at their surface (default `'none'`)
+    #[napi(ts_type = "'collision' | 'outline' | 'full'")]
+    pub shape_source: Option<String>,        // Boxes to test for hit blocks (default `'collision'`)
+    pub include_entities: Option<Vec<EntityHitbox>>, // Entity hitboxes to test along with the blocks
+    #[napi(ts_type = "'air' | 'solid' | 'stop'")]
+    pub unloaded_chunks: Option<String>,     // As for `raycast` (default `'air'`)
+    pub return_all: Option<bool>,            // Report every hit along the ray, nearest first, not just the first
+    pub min_y: Option<i32>,
+    pub max_y: Option<i32>,
+}
+
+/// A block or entity hit by `raycast_ex`.
+#[napi(object)]
+pub struct RaycastExHit {
+    pub position: Option<WorldCoords>, // Block hits only
+    pub state_id: Option<u32>,         // Block hits only
+    pub entity_id: Option<i32>,        // Entity hits only
+    pub face: u32,
+    pub intersect_point: Vec3Arg,
+    pub distance: f64,
+    pub entered_inside: bool,
+    pub exited_loaded_terrain: bool,   // The ray crossed an unloaded chunk
+    pub shape: Option<HitShape>,
+}
+
+fn parse_option<T>(value: Option<&str>, kind: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Option<T>> {
+    value.map(|name| parse(name).ok_or_else(|| napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Unknown {}: {}", kind, name),
+    ))).transpose()
+}
+
+impl NapiWorld {
+    // Block raycast settings for a set of options
+    pub(crate) fn settings_from_options(&self, options: &RaycastOptions) -> Result<RaycastSettings> {
+        let defaults = RaycastSettings::new(options.max_distance);
+        Ok(RaycastSettings {
+            unloaded_mode: UnloadedChunkMode::parse(options.unloaded_chunks.as_deref())?,
+            min_y: options.min_y,
+            max_y: options.max_y,
+            matcher: options.matcher.clone().map(|blocks| self.block_matcher(blocks)).transpose()?,
+            fluid_mode: parse_option(options.fluid_mode.as_deref(), "fluid mode", FluidMode::from_name)?.unwrap_or(defaults.fluid_mode),
+            shape_source: parse_option(options.shape_source.as_deref(), "shape source", ShapeSource::from_name)?.unwrap_or(defaults.shape_source),
+            return_all: options.return_all.unwrap_or(false),
+            ..defaults
+        })
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Casts a ray configured by an options object: which blocks to hit (`matcher`), whether fluids are hit
+    /// (`fluid_mode`), which boxes hit blocks have (`shape_source`), entities to test as well, how unloaded chunks
+    /// are treated, and whether to return every hit instead of the first. Returns the hits nearest first: at most
+    /// one unless `return_all` is set, with blocks and entities hidden behind the first hit left out.
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, options: RaycastOptions")]
+    pub fn raycast_ex(&self, origin: Vec3Arg, direction: Vec3Arg, options: RaycastOptions) -> Result<Vec<RaycastExHit>> {
+        let settings = self.settings_from_options(&options)?;
+        let origin = DVec3::from(origin);
+        let direction = DVec3::from(direction).normalize_or_zero();
+        if direction == DVec3::ZERO { return Ok(Vec::new()); }
+
+        let block_hits = self.cast_ray_hits(origin, direction, &settings, None);
+        let exited_loaded_terrain = block_hits.first().is_some_and(|hit| hit.exited_loaded_terrain);
+        let mut hits: Vec<RaycastExHit> = block_hits
+            .into_iter()
+            .map(|hit| {
+                let result = RaycastResult::from(hit);
+                RaycastExHit {
+                    position: Some(result.position),
+                    state_id: Some(self.get_block_state_id(result.position.x, result.position.y, result.position.z)),
+                    entity_id: None,
+                    face: result.face,
+                    intersect_point: result.intersect_point,
+                    distance: result.distance,
+                    entered_inside: result.entered_inside,
+                    exited_loaded_terrain: result.exited_loaded_terrain,
+                    shape: result.shape,
+                }
+            })
+            .collect();
+
+        if let Some(entities) = options.include_entities.as_deref() {
+            // Without `return_all`, entities behind the block that was hit can't be seen
+            let reach = if settings.return_all { settings.max_distance } else { hits.first().map_or(settings.max_distance, |hit| hit.distance) };
+            for (t, face, inside, id) in self.entity_hits(origin, direction, reach, entities)? {
+                hits.push(RaycastExHit {
+                    position: None,
+                    state_id: None,
+                    entity_id: Some(id),
+                    face: face as u32,
+                    intersect_point: (origin + direction * t).into(),
+                    distance: t,
+                    entered_inside: inside,
+                    exited_loaded_terrain,
+                    shape: None,
+                });
+            }
+            hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+            if !settings.return_all { hits.truncate(1); }
+        }
+        Ok(hits)
+    }
+}
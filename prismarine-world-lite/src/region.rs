@@ -0,0 +1,189 @@
+// src/region.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+
+/// A cuboid block selection defined by two inclusive corners.
+#[napi]
+#[derive(Debug, Clone, Copy)]
+pub struct Region {
+    min: WorldCoords,
+    max: WorldCoords,
+}
+
+#[napi]
+impl Region {
+    /// Creates a region spanning both corners, in any order.
+    #[napi(constructor)]
+    pub fn new(a: WorldCoords, b: WorldCoords) -> Self {
+        Region {
+            min: WorldCoords { x: a.x.min(b.x), y: a.y.min(b.y), z: a.z.min(b.z) },
+            max: WorldCoords { x: a.x.max(b.x), y: a.y.max(b.y), z: a.z.max(b.z) },
+        }
+    }
+
+    #[napi(getter)]
+    pub fn min(&self) -> WorldCoords {
+        self.min
+    }
+
+    #[napi(getter)]
+    pub fn max(&self) -> WorldCoords {
+        self.max
+    }
+
+    /// Number of blocks in the region.
+    #[napi(getter)]
+    pub fn volume(&self) -> f64 {
+        let size = |lo: i32, hi: i32| (hi as i64 - lo as i64 + 1) as f64;
+        size(self.min.x, self.max.x) * size(self.min.y, self.max.y) * size(self.min.z, self.max.z)
+    }
+
+    #[napi]
+    pub fn contains(&self, pos: WorldCoords) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x)
+            && (self.min.y..=self.max.y).contains(&pos.y)
+            && (self.min.z..=self.max.z).contains(&pos.z)
+    }
+
+    /// Returns a copy grown by the given amount on every side (per axis when `dy`/`dz` are given).
+    /// Negative amounts shrink the region, down to a single block.
+    #[napi]
+    pub fn expand(&self, dx: i32, dy: Option<i32>, dz: Option<i32>) -> Region {
+        let (dy, dz) = (dy.unwrap_or(dx), dz.unwrap_or(dx));
+        let grow = |lo: i32, hi: i32, d: i32| {
+            let (lo, hi) = (lo.saturating_sub(d), hi.saturating_add(d));
+            if lo > hi { let mid = ((lo as i64 + hi as i64) / 2) as i32; (mid, mid) } else { (lo, hi) }
+        };
+        let (min_x, max_x) = grow(self.min.x, self.max.x, dx);
+        let (min_y, max_y) = grow(self.min.y, self.max.y, dy);
+        let (min_z, max_z) = grow(self.min.z, self.max.z, dz);
+        Region {
+            min: WorldCoords { x: min_x, y: min_y, z: min_z },
+            max: WorldCoords { x: max_x, y: max_y, z: max_z },
+        }
+    }
+
+    /// Returns the overlap of both regions, or `null` if they don't overlap.
+    #[napi]
+    pub fn intersect(&self, other: &Region) -> Option<Region> {
+        let min = WorldCoords { x: self.min.x.max(other.min.x), y: self.min.y.max(other.min.y), z: self.min.z.max(other.min.z) };
+        let max = WorldCoords { x: self.max.x.min(other.max.x), y: self.max.y.min(other.max.y), z: self.max.z.min(other.max.z) };
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Region { min, max })
+    }
+
+    /// Lists every position in the region as a flat `Int32Array` of `[x, y, z]` triples (Y outermost, X fastest).
+    #[napi]
+    pub fn iter_blocks(&self) -> Int32Array {
+        Int32Array::new(self.positions().flat_map(|p| [p.x, p.y, p.z]).collect())
+    }
+}
+
+impl Region {
+    pub fn positions(&self) -> impl Iterator<Item = WorldCoords> {
+        let (lo, hi) = (self.min, self.max);
+        (lo.y..=hi.y).flat_map(move |y| (lo.z..=hi.z).flat_map(move |z| (lo.x..=hi.x).map(move |x| WorldCoords { x, y, z })))
+    }
+
+    // Coordinates of the chunk columns the region overlaps
+    pub fn chunks(&self) -> impl Iterator<Item = ChunkCoords> {
+        let (lo, hi) = (self.min.to_chunk_coords(), self.max.to_chunk_coords());
+        (lo.x..=hi.x).flat_map(move |x| (lo.z..=hi.z).map(move |z| ChunkCoords { x, z }))
+    }
+
+    // The part of the region between two block Ys (inclusive), if any
+    pub fn clip_to_y(&self, min_y: i32, max_y: i32) -> Option<Region> {
+        let (lo, hi) = (self.min.y.max(min_y), self.max.y.min(max_y));
+        (lo <= hi).then_some(Region { min: WorldCoords { y: lo, ..self.min }, max: WorldCoords { y: hi, ..self.max } })
+    }
+
+    // The part of the region inside the given chunk column
+    pub fn clip_to_chunk(&self, chunk: ChunkCoords) -> Region {
+        Region {
+            min: WorldCoords { x: self.min.x.max(chunk.x << 4), y: self.min.y, z: self.min.z.max(chunk.z << 4) },
+            max: WorldCoords { x: self.max.x.min((chunk.x << 4) + 15), y: self.max.y, z: self.max.z.min((chunk.z << 4) + 15) },
+        }
+    }
+}
+
+impl NapiWorld {
+    // Runs `f` on every loaded column overlapping the region under a single write lock each,
+    // passing the part of the region inside that column. Unloaded columns are skipped.
+    pub(crate) fn with_region_columns_mut(&self, region: &Region, mut f: impl FnMut(&mut ChunkColumn, Region)) -> Result<()> {
+        for chunk in region.chunks() {
+            self.ensure_column(chunk);
+            self.unshare_column(chunk);
+            let Some(entry) = self.columns.get(&chunk) else { continue; };
+            let mut column = entry.value().try_write().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire write lock for chunk".to_string(),
+            ))?;
+            f(&mut column, region.clip_to_chunk(chunk));
+        }
+        Ok(())
+    }
+
+    // Read-only counterpart of `with_region_columns_mut`; contended columns are skipped
+    pub(crate) fn with_region_columns(&self, region: &Region, mut f: impl FnMut(&ChunkColumn, Region)) {
+        for chunk in region.chunks() {
+            self.ensure_column(chunk);
+            let Some(entry) = self.columns.get(&chunk) else { continue; };
+            let Ok(column) = entry.value().try_read() else { continue; };
+            f(&column, region.clip_to_chunk(chunk));
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Sets every loaded block in the region to `state_id`. Returns the number of blocks that changed; positions
+    /// outside the world aren't written.
+    #[napi]
+    pub fn fill_region(&self, region: &Region, state_id: u32) -> Result<u32> {
+        let mut changed = Vec::new();
+        self.with_region_columns_mut(region, |column, part| {
+            let Some(part) = part.clip_to_y(self.config.min_y, self.config.min_y + self.config.height - 1) else { return; };
+            for pos in part.positions() {
+                if column.get_block_state_id(pos) != state_id {
+                    column.set_block_state_id(pos, state_id);
+                    changed.push(pos);
+                }
+            }
+        })?;
+        self.blocks_changed(&changed);
+        Ok(changed.len() as u32)
+    }
+
+    /// Replaces every loaded block in the region whose state is one of `from_state_ids` with `to_state_id`.
+    /// Returns the number of blocks replaced; positions outside the world aren't written.
+    #[napi]
+    pub fn replace_in_region(&self, region: &Region, from_state_ids: Vec<u32>, to_state_id: u32) -> Result<u32> {
+        let from: HashSet<u32> = from_state_ids.into_iter().filter(|&id| id != to_state_id).collect();
+        let mut changed = Vec::new();
+        self.with_region_columns_mut(region, |column, part| {
+            let Some(part) = part.clip_to_y(self.config.min_y, self.config.min_y + self.config.height - 1) else { return; };
+            for pos in part.positions() {
+                if from.contains(&column.get_block_state_id(pos)) {
+                    column.set_block_state_id(pos, to_state_id);
+                    changed.push(pos);
+                }
+            }
+        })?;
+        self.blocks_changed(&changed);
+        Ok(changed.len() as u32)
+    }
+
+    /// Counts the loaded blocks in the region whose state is one of `state_ids`.
+    #[napi]
+    pub fn count_in_region(&self, region: &Region, state_ids: Vec<u32>) -> u32 {
+        let wanted: HashSet<u32> = state_ids.into_iter().collect();
+        let mut count = 0;
+        self.with_region_columns(region, |column, part| {
+            count += part.positions().filter(|&pos| wanted.contains(&column.get_block_state_id(pos))).count() as u32;
+        });
+        count
+    }
+}
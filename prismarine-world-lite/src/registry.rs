@@ -0,0 +1,405 @@
+// src/registry.rs
+use minecraft_data_rs::api::Api;
+use minecraft_data_rs::models::biome::Biome;
+use minecraft_data_rs::models::block::{Block, BoundingBox, StateType};
+use minecraft_data_rs::models::block_collision_shapes::{BlockCollisionShapes, CollisionShapeIds};
+use minecraft_data_rs::DataResult;
+use crate::palette::needed_bits;
+use crate::tags::{block_tags, normalize_tag};
+use std::collections::{HashMap, HashSet};
+
+// A collision box relative to the block origin: [min_x, min_y, min_z, max_x, max_y, max_z]
+pub type ShapeBox = [f64; 6];
+
+pub static FULL_CUBE: [ShapeBox; 1] = [[0.0, 0.0, 0.0, 1.0, 1.0, 1.0]];
+
+/// Block data from minecraft-data, loaded once per world and indexed by state ID.
+/// Looking blocks up through `Api` re-parses the JSON on every call, which is far too slow for raycasting.
+pub struct BlockRegistry {
+    blocks: Vec<Block>,
+    // state ID -> index into `blocks`
+    block_index_by_state: Vec<Option<u32>>,
+    // state ID -> collision boxes for that exact state
+    shapes_by_state: Vec<Vec<ShapeBox>>,
+    // Item IDs, for resolving tool names against `harvest_tools`
+    item_ids_by_name: HashMap<String, u32>,
+    // Entity name -> (width, height) of its hitbox
+    entity_sizes_by_name: HashMap<String, (f64, f64)>,
+    // Block ID -> block tags it belongs to (unprefixed, e.g. "logs", "mineable/axe")
+    tags_by_block: HashMap<u32, Vec<String>>,
+    // Block tag -> IDs of the blocks in it
+    blocks_by_tag: HashMap<String, HashSet<u32>>,
+    // Minecraft version the data is for, e.g. "1.20.4"
+    pub minecraft_version: String,
+}
+
+impl BlockRegistry {
+    pub fn load(api: &Api) -> DataResult<Self> {
+        let blocks = api.blocks.blocks_array()?;
+        let collision_shapes = api.blocks.block_collision_shapes()?;
+        let item_ids_by_name = api.items.items_array()?.into_iter().map(|item| (item.name, item.id)).collect();
+        let entity_sizes_by_name = api
+            .entities
+            .entities_array()?
+            .into_iter()
+            .filter_map(|entity| Some((entity.name, (entity.width? as f64, entity.height? as f64))))
+            .collect();
+        let minecraft_version = api.version.minecraft_version.clone();
+        Ok(BlockRegistry::from_data(blocks, &collision_shapes, item_ids_by_name, entity_sizes_by_name, minecraft_version))
+    }
+
+    pub(crate) fn from_data(
+        blocks: Vec<Block>,
+        collision_shapes: &BlockCollisionShapes,
+        item_ids_by_name: HashMap<String, u32>,
+        entity_sizes_by_name: HashMap<String, (f64, f64)>,
+        minecraft_version: String,
+    ) -> Self {
+        let max_state_id = blocks
+            .iter()
+            .map(|b| b.max_state_id.unwrap_or(b.id << 4 | 15))
+            .max()
+            .unwrap_or(0) as usize;
+        let mut block_index_by_state = vec![None; max_state_id + 1];
+        let mut shapes_by_state = vec![Vec::new(); max_state_id + 1];
+
+        for (block_index, block) in blocks.iter().enumerate() {
+            let min_state_id = block.min_state_id.unwrap_or(block.id << 4);
+            let max_state_id = block.max_state_id.unwrap_or(min_state_id + 15);
+            let shape_ids = collision_shapes.blocks.get(&block.name);
+
+            for state_id in min_state_id..=max_state_id {
+                block_index_by_state[state_id as usize] = Some(block_index as u32);
+
+                // Array entries are per state, offset from the block's first state
+                let shape_id = match shape_ids {
+                    Some(CollisionShapeIds::Value(id)) => Some(*id),
+                    Some(CollisionShapeIds::Array(ids)) => ids
+                        .get((state_id - min_state_id) as usize)
+                        .or_else(|| ids.first())
+                        .copied(),
+                    None => None,
+                };
+                if let Some(boxes) = shape_id.and_then(|id| collision_shapes.shapes.get(&id)) {
+                    shapes_by_state[state_id as usize] = boxes
+                        .iter()
+                        .filter(|b| b.len() == 6)
+                        .map(|b| [b[0] as f64, b[1] as f64, b[2] as f64, b[3] as f64, b[4] as f64, b[5] as f64])
+                        .collect();
+                }
+            }
+        }
+
+        let tags_by_block: HashMap<u32, Vec<String>> = blocks.iter().map(|block| (block.id, block_tags(block))).collect();
+        let mut blocks_by_tag: HashMap<String, HashSet<u32>> = HashMap::new();
+        for (block_id, tags) in &tags_by_block {
+            for tag in tags {
+                blocks_by_tag.entry(tag.clone()).or_default().insert(*block_id);
+            }
+        }
+
+        BlockRegistry {
+            blocks,
+            block_index_by_state,
+            shapes_by_state,
+            item_ids_by_name,
+            entity_sizes_by_name,
+            tags_by_block,
+            blocks_by_tag,
+            minecraft_version,
+        }
+    }
+
+    pub fn block(&self, state_id: u32) -> Option<&Block> {
+        self.block_index_by_state
+            .get(state_id as usize)
+            .copied()
+            .flatten()
+            .map(|idx| &self.blocks[idx as usize])
+    }
+
+    pub fn block_by_name(&self, name: &str) -> Option<&Block> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        self.blocks.iter().find(|b| b.name == name)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.blocks_by_tag.contains_key(normalize_tag(tag))
+    }
+
+    pub fn tags_of(&self, block_id: u32) -> Vec<String> {
+        self.tags_by_block.get(&block_id).cloned().unwrap_or_default()
+    }
+
+    // Tags may be given as `#minecraft:logs`, `#logs` or `logs`
+    pub fn block_has_tag(&self, block_id: u32, tag: &str) -> bool {
+        self.blocks_by_tag.get(normalize_tag(tag)).is_some_and(|ids| ids.contains(&block_id))
+    }
+
+    pub fn blocks_in_tag(&self, tag: &str) -> Vec<&Block> {
+        let Some(ids) = self.blocks_by_tag.get(normalize_tag(tag)) else { return Vec::new(); };
+        self.blocks.iter().filter(|block| ids.contains(&block.id)).collect()
+    }
+
+    pub fn item_id(&self, name: &str) -> Option<u32> {
+        self.item_ids_by_name.get(name.strip_prefix("minecraft:").unwrap_or(name)).copied()
+    }
+
+    // Hitbox (width, height) of an entity type
+    pub fn entity_size(&self, name: &str) -> Option<(f64, f64)> {
+        self.entity_sizes_by_name.get(name.strip_prefix("minecraft:").unwrap_or(name)).copied()
+    }
+
+    // Bits per entry of the global palette, the width of Direct block containers holding any state of this version
+    pub fn global_bits(&self) -> usize {
+        needed_bits(self.block_index_by_state.len().saturating_sub(1))
+    }
+
+    // Air, cave air and void air; unknown states count as air
+    pub fn is_air(&self, state_id: u32) -> bool {
+        self.block(state_id).is_none_or(|b| matches!(b.name.as_str(), "air" | "cave_air" | "void_air"))
+    }
+
+    // Blocks a placed block replaces instead of needing to be broken first: air, liquids, grass, vines and the like.
+    // Only a single snow layer is replaceable.
+    pub fn is_replaceable(&self, state_id: u32) -> bool {
+        let Some(block) = self.block(state_id) else { return true; };
+        match block.name.as_str() {
+            "air" | "cave_air" | "void_air" | "water" | "lava" | "grass" | "short_grass" | "tall_grass" | "fern"
+            | "large_fern" | "dead_bush" | "vine" | "glow_lichen" | "seagrass" | "tall_seagrass" | "fire"
+            | "soul_fire" | "structure_void" | "light" | "crimson_roots" | "warped_roots" | "nether_sprouts"
+            | "hanging_roots" => true,
+            "snow" => self.state_property(state_id, "layers").as_deref() == Some("1"),
+            _ => false,
+        }
+    }
+
+    pub fn is_solid(&self, state_id: u32) -> bool {
+        self.block(state_id)
+            .map(|b| matches!(b.bounding_box, BoundingBox::Block))
+            .unwrap_or(false)
+    }
+
+    // Whether the collision shape covers the whole 0..1 block space, fully occluding its neighbors' faces
+    pub fn is_full_cube(&self, state_id: u32) -> bool {
+        self.collision_shapes(state_id).iter().any(|shape| {
+            shape[..3].iter().all(|&v| v <= 0.0) && shape[3..].iter().all(|&v| v >= 1.0)
+        })
+    }
+
+    // Unknown states are treated like air, letting light through
+    pub fn is_transparent(&self, state_id: u32) -> bool {
+        self.block(state_id).map(|b| b.transparent).unwrap_or(true)
+    }
+
+    // How much light is lost passing through the block, 0 (air, glass) to 15 (fully opaque)
+    pub fn light_filter(&self, state_id: u32) -> u8 {
+        self.block(state_id).map(|b| b.filter_light.min(15)).unwrap_or(0)
+    }
+
+    // Light level the block state gives off. Blocks with a `lit` property (furnaces, redstone lamps, candles...)
+    // only emit while lit; other state-dependent levels use the block's maximum.
+    pub fn light_emission(&self, state_id: u32) -> u8 {
+        let Some(block) = self.block(state_id) else { return 0; };
+        if block.emit_light == 0 || self.state_property(state_id, "lit").as_deref() == Some("false") { return 0; }
+        block.emit_light.min(15)
+    }
+
+    // Decodes the block state properties of a state ID, in the order minecraft-data lists them.
+    // The last property varies fastest between consecutive state IDs.
+    pub fn state_properties(&self, state_id: u32) -> Vec<(String, String)> {
+        let Some(block) = self.block(state_id) else { return Vec::new(); };
+        let Some(states) = block.states.as_ref() else { return Vec::new(); };
+
+        let mut data = state_id - block.min_state_id.unwrap_or(block.id << 4);
+        let mut properties = Vec::with_capacity(states.len());
+        for state in states.iter().rev() {
+            let num_values = state.num_values.max(1);
+            let index = (data % num_values) as usize;
+            data /= num_values;
+
+            let value = match (&state.values, &state.state_type) {
+                (Some(values), _) if index < values.len() => values[index].clone(),
+                (_, StateType::Bool) => (index == 0).to_string(), // minecraft-data lists `true` first
+                _ => index.to_string(),
+            };
+            properties.push((state.name.clone(), value));
+        }
+        properties.reverse();
+        properties
+    }
+
+    // Encodes a block name and property values back into a state ID. Properties that aren't given take the value
+    // of the block's default state; unknown blocks or values give None.
+    pub fn state_id_from_properties(&self, name: &str, properties: &[(String, String)]) -> Option<u32> {
+        let block = self.block_by_name(name)?;
+        let min_state_id = block.min_state_id.unwrap_or(block.id << 4);
+        let Some(states) = block.states.as_ref() else { return Some(min_state_id); };
+        let defaults = self.state_properties(block.default_state.unwrap_or(min_state_id));
+
+        let mut data = 0;
+        for state in states {
+            let value = properties.iter()
+                .chain(defaults.iter())
+                .find(|(property, _)| *property == state.name)
+                .map(|(_, value)| value.as_str())?;
+            let index = match (&state.values, &state.state_type) {
+                (Some(values), _) => values.iter().position(|v| v == value)? as u32,
+                (_, StateType::Bool) => if value == "true" { 0 } else { 1 },
+                _ => value.parse().ok()?,
+            };
+            data = data * state.num_values.max(1) + index;
+        }
+        Some(min_state_id + data)
+    }
+
+    pub fn state_property(&self, state_id: u32, name: &str) -> Option<String> {
+        self.state_properties(state_id)
+            .into_iter()
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn collision_shapes(&self, state_id: u32) -> &[ShapeBox] {
+        self.shapes_by_state
+            .get(state_id as usize)
+            .map(|shapes| shapes.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // The boxes the raycaster tests for a state: its collision shapes, or a full cube for solid blocks without any
+    pub fn resolved_shapes(&self, state_id: u32) -> &[ShapeBox] {
+        let shapes = self.collision_shapes(state_id);
+        if shapes.is_empty() && self.is_solid(state_id) { &FULL_CUBE } else { shapes }
+    }
+}
+
+/// Biome data from minecraft-data, indexed by biome ID.
+pub struct BiomeRegistry {
+    biomes_by_id: HashMap<u32, Biome>,
+}
+
+impl BiomeRegistry {
+    pub fn load(api: &Api) -> DataResult<Self> {
+        Ok(BiomeRegistry {
+            biomes_by_id: api.biomes.biomes()?,
+        })
+    }
+
+    pub fn biome(&self, biome_id: u32) -> Option<&Biome> {
+        self.biomes_by_id.get(&biome_id)
+    }
+
+    pub fn biome_id_by_name(&self, name: &str) -> Option<u32> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        self.biomes_by_id.values().find(|b| b.name == name).map(|b| b.id)
+    }
+}
+
+// A small hand-built registry for unit tests, since the minecraft-data JSON isn't available to them
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use minecraft_data_rs::models::block::{BoundingBox, State};
+
+    // (name, first state, last state, solid)
+    const BLOCKS: &[(&str, u32, u32, bool)] = &[
+        ("air", 0, 0, false),
+        ("stone", 1, 1, true),
+        ("dirt", 2, 2, true),
+        ("cave_air", 3, 3, false),
+        ("oak_log", 4, 6, true),
+        ("crimson_stem", 7, 9, true),
+        ("melon_stem", 10, 17, false),
+        ("pumpkin_stem", 18, 25, false),
+        ("water", 26, 41, false),
+        ("note_block", 42, 1041, true),
+        ("oak_stairs", 1042, 1081, true),
+        ("rail", 1082, 1091, false),
+        ("oak_sign", 1092, 1107, false),
+        ("oak_fence", 1108, 1123, true),
+    ];
+
+    // Block properties as (name, values), the last varying fastest; an empty list of values is a boolean
+    const PROPERTIES: &[(&str, &[(&str, &[&str])])] = &[
+        ("oak_log", &[("axis", &["x", "y", "z"])]),
+        ("oak_stairs", &[
+            ("facing", &["north", "south", "west", "east"]),
+            ("half", &["top", "bottom"]),
+            ("shape", &["straight", "inner_left", "inner_right", "outer_left", "outer_right"]),
+        ]),
+        ("rail", &[(
+            "shape",
+            &["north_south", "east_west", "ascending_east", "ascending_west", "ascending_north", "ascending_south",
+              "south_east", "south_west", "north_west", "north_east"],
+        )]),
+        ("oak_sign", &[("rotation", &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15"])]),
+        ("oak_fence", &[("east", &[]), ("north", &[]), ("south", &[]), ("west", &[])]),
+    ];
+
+    fn states(name: &str) -> Option<Vec<State>> {
+        let (_, properties) = PROPERTIES.iter().find(|(block, _)| *block == name)?;
+        Some(properties
+            .iter()
+            .map(|&(name, values)| State {
+                name: name.to_string(),
+                state_type: if values.is_empty() { StateType::Bool } else { StateType::Enum },
+                values: (!values.is_empty()).then(|| values.iter().map(|v| v.to_string()).collect()),
+                num_values: values.len().max(2) as u32,
+            })
+            .collect())
+    }
+
+    pub(crate) fn test_registry() -> BlockRegistry {
+        let blocks = BLOCKS
+            .iter()
+            .enumerate()
+            .map(|(id, &(name, min_state_id, max_state_id, solid))| Block {
+                id: id as u32,
+                display_name: name.to_string(),
+                name: name.to_string(),
+                hardness: Some(if solid { 1.5 } else { 0.0 }),
+                stack_size: 64,
+                diggable: true,
+                bounding_box: if solid { BoundingBox::Block } else { BoundingBox::Empty },
+                material: None,
+                harvest_tools: None,
+                variations: None,
+                drops: Vec::new(),
+                transparent: !solid,
+                emit_light: 0,
+                filter_light: if solid { 15 } else { 0 },
+                min_state_id: Some(min_state_id),
+                max_state_id: Some(max_state_id),
+                states: states(name),
+                default_state: Some(min_state_id),
+                blast_resistance: None,
+            })
+            .collect();
+        let collision_shapes = BlockCollisionShapes { blocks: HashMap::new(), shapes: HashMap::new() };
+        BlockRegistry::from_data(blocks, &collision_shapes, HashMap::new(), HashMap::new(), "test".to_string())
+    }
+
+    pub(crate) fn test_biomes() -> BiomeRegistry {
+        let biomes_by_id = ["plains", "desert", "forest"]
+            .iter()
+            .enumerate()
+            .map(|(id, &name)| {
+                let biome = Biome {
+                    id: id as u32,
+                    name: name.to_string(),
+                    category: "none".to_string(),
+                    temperature: 0.5,
+                    precipitation: None,
+                    depth: None,
+                    dimension: "overworld".to_string(),
+                    display_name: name.to_string(),
+                    color: 0,
+                    rainfall: None,
+                };
+                (id as u32, biome)
+            })
+            .collect();
+        BiomeRegistry { biomes_by_id }
+    }
+}
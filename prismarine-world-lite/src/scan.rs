@@ -0,0 +1,120 @@
+// src/scan.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords, SECTION_VOLUME};
+use crate::matcher::BlockMatcher;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi::{Env, Task};
+use napi_derive::napi;
+use rayon::prelude::*;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+// What a world scan collects from each column
+pub enum ScanKind {
+    Positions { max_results: Option<usize> },
+    Count,
+}
+
+// Output of a scan, before it's turned into a JS value
+pub enum ScanOutput {
+    Positions(Vec<WorldCoords>),
+    Count(u64),
+}
+
+// A scan over a snapshot of the loaded columns, taken on the JS thread. Columns loaded or unloaded while it runs
+// aren't seen; blocks written to the snapshotted columns may or may not be.
+pub struct WorldScan {
+    columns: Vec<(ChunkCoords, Arc<RwLock<ChunkColumn>>)>,
+    matcher: BlockMatcher,
+    kind: ScanKind,
+}
+
+// Calls `found` with the matching positions in one column, sections bottom to top, each in index order (y, then z,
+// then x)
+fn scan_column(coords: ChunkCoords, column: &ChunkColumn, matcher: &BlockMatcher, mut found: impl FnMut(WorldCoords)) {
+    let mut states = vec![0u32; SECTION_VOLUME];
+    for (section_y, section) in column.sections() {
+        if !section.block_states().may_contain(|state_id| matcher.matches(state_id)) { continue; }
+        section.block_states().unpack_into(&mut states);
+        for (index, &state_id) in states.iter().enumerate() {
+            if !matcher.matches(state_id) { continue; }
+            found(WorldCoords {
+                x: (coords.x << 4) + (index & 15) as i32,
+                y: (section_y << 4) + (index >> 8) as i32,
+                z: (coords.z << 4) + ((index >> 4) & 15) as i32,
+            });
+        }
+    }
+}
+
+fn read_column(column: &RwLock<ChunkColumn>) -> Result<RwLockReadGuard<'_, ChunkColumn>> {
+    column.read().map_err(|_| napi::Error::new(
+        napi::Status::GenericFailure,
+        "Failed to acquire read lock for chunk".to_string(),
+    ))
+}
+
+impl Task for WorldScan {
+    type Output = ScanOutput;
+    type JsValue = Either<Vec<WorldCoords>, i64>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let matcher = &self.matcher;
+        match self.kind {
+            ScanKind::Count => {
+                let counts = self.columns.par_iter().map(|(coords, column)| {
+                    let mut count = 0u64;
+                    scan_column(*coords, &*read_column(column)?, matcher, |_| count += 1);
+                    Ok(count)
+                });
+                Ok(ScanOutput::Count(counts.sum::<Result<u64>>()?))
+            }
+            ScanKind::Positions { max_results } => {
+                let found: Vec<Vec<WorldCoords>> = self.columns.par_iter().map(|(coords, column)| {
+                    let mut found = Vec::new();
+                    scan_column(*coords, &*read_column(column)?, matcher, |pos| found.push(pos));
+                    Ok(found)
+                }).collect::<Result<_>>()?;
+                let mut positions: Vec<WorldCoords> = found.into_iter().flatten().collect();
+                if let Some(max_results) = max_results {
+                    positions.truncate(max_results);
+                }
+                Ok(ScanOutput::Positions(positions))
+            }
+        }
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(match output {
+            ScanOutput::Positions(positions) => Either::A(positions),
+            ScanOutput::Count(count) => Either::B(count as i64),
+        })
+    }
+}
+
+impl NapiWorld {
+    fn world_scan(&self, blocks: Vec<Either<u32, String>>, kind: ScanKind) -> Result<AsyncTask<WorldScan>> {
+        let matcher = self.block_matcher(blocks)?;
+        // Sorted so results come out in the same order from one call to the next
+        let columns = self.all_columns();
+        Ok(AsyncTask::new(WorldScan { columns, matcher, kind }))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Finds every block in the loaded columns matching `blocks` (state IDs, block names or `#` tags), on a
+    /// thread pool without blocking the event loop. Columns are scanned in parallel and sections that can't hold a
+    /// match are skipped from their palette alone. Resolves to positions ordered by column (x, then z), then by y,
+    /// z and x, cut to `maxResults` if given.
+    #[napi(ts_return_type = "Promise<Array<WorldCoords>>")]
+    pub fn scan_world(&self, blocks: Vec<Either<u32, String>>, max_results: Option<u32>) -> Result<AsyncTask<WorldScan>> {
+        self.world_scan(blocks, ScanKind::Positions { max_results: max_results.map(|n| n as usize) })
+    }
+
+    /// Counts the blocks in the loaded columns matching `blocks`, like `scanWorld` without collecting positions.
+    #[napi(ts_return_type = "Promise<number>")]
+    pub fn count_in_world(&self, blocks: Vec<Either<u32, String>>) -> Result<AsyncTask<WorldScan>> {
+        self.world_scan(blocks, ScanKind::Count)
+    }
+}
@@ -0,0 +1,274 @@
+// src/schematic.rs
+use crate::anvil::{decompress_detected, state_from_nbt};
+use crate::coords::WorldCoords;
+use crate::nbt::{read_nbt, Tag};
+use crate::raycast::Vec3Arg;
+use crate::region::Region;
+use crate::registry::BlockRegistry;
+use crate::transform::{transform_state, MirrorAxis, StateTransform, TransformOptions};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+
+// State of schematic positions that don't matter (structure voids, unloaded blocks when copying)
+pub const SCHEMATIC_VOID: u32 = u32::MAX;
+// Largest structure template read, in blocks
+const MAX_STRUCTURE_VOLUME: i64 = 1 << 24;
+
+/// A block template: a cuboid of state IDs stored Y outermost, then Z, with X fastest (like chunk sections).
+/// Positions holding `0xFFFFFFFF` are left alone when pasting and ignored when diffing.
+#[napi]
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub(crate) size: WorldCoords,
+    pub(crate) states: Vec<u32>,
+    pub(crate) entities: Vec<SchematicEntity>,
+}
+
+/// An entity stored in a structure template. Entities aren't spawned by pasting; they're listed for the caller.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SchematicEntity {
+    pub name: String,      // Entity type, e.g. "minecraft:villager"
+    pub position: Vec3Arg, // Relative to the schematic's minimum corner
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SchematicDiffEntry {
+    pub position: WorldCoords,
+    pub expected: u32, // State ID the schematic wants
+    pub actual: u32,   // State ID in the world
+}
+
+#[napi(object)]
+#[derive(Debug, Default)]
+pub struct SchematicDiff {
+    pub missing: Vec<SchematicDiffEntry>, // Air in the world where the schematic has a block
+    pub wrong: Vec<SchematicDiffEntry>,   // A different block than the schematic's
+    pub extra: Vec<SchematicDiffEntry>,   // A block in the world where the schematic has air
+}
+
+impl Schematic {
+    pub fn index(&self, x: i32, y: i32, z: i32) -> usize {
+        ((y * self.size.z + z) * self.size.x + x) as usize
+    }
+
+    // Reads a vanilla structure template (`.nbt`, as saved by structure blocks). Positions without a block entry
+    // are void; with several palettes (shipwrecks and the like), the first is used. Unknown blocks become air.
+    pub fn from_structure_nbt(root: &Tag, registry: &BlockRegistry) -> std::io::Result<Self> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+        let ints = |tag: Option<&Tag>| -> Option<Vec<i64>> { tag?.as_list()?.iter().map(Tag::as_i64).collect() };
+
+        let size = ints(root.get("size"))
+            .filter(|size| size.len() == 3 && size.iter().all(|&v| v > 0 && v <= 4096) && size.iter().product::<i64>() <= MAX_STRUCTURE_VOLUME)
+            .ok_or_else(|| invalid("Structure has no valid size"))?;
+        let palette_tag = root.get("palette")
+            .or_else(|| root.get("palettes").and_then(Tag::as_list).and_then(|palettes| palettes.first()))
+            .and_then(Tag::as_list)
+            .ok_or_else(|| invalid("Structure has no palette"))?;
+        let palette: Vec<u32> = palette_tag.iter().map(|entry| state_from_nbt(entry, registry).unwrap_or(0)).collect();
+
+        let mut schematic = Schematic {
+            size: WorldCoords { x: size[0] as i32, y: size[1] as i32, z: size[2] as i32 },
+            states: vec![SCHEMATIC_VOID; (size[0] * size[1] * size[2]) as usize],
+            entities: Vec::new(),
+        };
+        for block in root.get("blocks").and_then(Tag::as_list).unwrap_or(&[]) {
+            let Some(pos) = ints(block.get("pos")).filter(|pos| pos.len() == 3) else { continue; };
+            let Some(&state_id) = block.get("state").and_then(Tag::as_i64).and_then(|i| palette.get(i as usize)) else { continue; };
+            if (0..3).all(|axis| (0..size[axis]).contains(&pos[axis])) {
+                let index = schematic.index(pos[0] as i32, pos[1] as i32, pos[2] as i32);
+                schematic.states[index] = state_id;
+            }
+        }
+
+        for entity in root.get("entities").and_then(Tag::as_list).unwrap_or(&[]) {
+            let position: Option<Vec<f64>> = entity.get("pos").and_then(Tag::as_list)
+                .and_then(|pos| pos.iter().map(|v| match v { Tag::Double(v) => Some(*v), _ => None }).collect());
+            let name = entity.get("nbt").and_then(|nbt| nbt.get("id")).and_then(Tag::as_str);
+            if let (Some([x, y, z]), Some(name)) = (position.as_deref(), name) {
+                schematic.entities.push(SchematicEntity { name: name.to_string(), position: Vec3Arg { x: *x, y: *y, z: *z } });
+            }
+        }
+        Ok(schematic)
+    }
+
+    // The schematic mirrored and rotated as placed in the world, with the block states turned to match
+    pub fn transformed(&self, registry: &BlockRegistry, transform: StateTransform) -> Cow<'_, Schematic> {
+        if transform.is_identity() { return Cow::Borrowed(self); }
+        let quarter_turns = transform.quarter_turns;
+        let size = if quarter_turns % 2 == 1 { WorldCoords { x: self.size.z, y: self.size.y, z: self.size.x } } else { self.size };
+
+        // Where a horizontal position ends up; `extent` is the source size (one less for block positions)
+        let place = |mut x: f64, mut z: f64, extent_x: f64, extent_z: f64| {
+            match transform.mirror {
+                Some(MirrorAxis::X) => x = extent_x - x,
+                Some(MirrorAxis::Z) => z = extent_z - z,
+                None => {}
+            }
+            let (mut extent_x, mut extent_z) = (extent_x, extent_z);
+            for _ in 0..quarter_turns {
+                (x, z) = (extent_z - z, x);
+                (extent_x, extent_z) = (extent_z, extent_x);
+            }
+            (x, z)
+        };
+
+        let mut states = vec![SCHEMATIC_VOID; self.states.len()];
+        let mut transformed_states: HashMap<u32, u32> = HashMap::new();
+        let (last_x, last_z) = ((self.size.x - 1) as f64, (self.size.z - 1) as f64);
+        for y in 0..self.size.y {
+            for z in 0..self.size.z {
+                for x in 0..self.size.x {
+                    let state_id = self.states[self.index(x, y, z)];
+                    if state_id == SCHEMATIC_VOID { continue; }
+                    let (new_x, new_z) = place(x as f64, z as f64, last_x, last_z);
+                    let index = ((y * size.z + new_z as i32) * size.x + new_x as i32) as usize;
+                    states[index] = *transformed_states
+                        .entry(state_id)
+                        .or_insert_with(|| transform_state(registry, state_id, transform));
+                }
+            }
+        }
+
+        let entities = self.entities.iter().map(|entity| {
+            let (x, z) = place(entity.position.x, entity.position.z, self.size.x as f64, self.size.z as f64);
+            SchematicEntity { name: entity.name.clone(), position: Vec3Arg { x, y: entity.position.y, z } }
+        }).collect();
+        Cow::Owned(Schematic { size, states, entities })
+    }
+
+    // The world region the schematic covers when placed with its minimum corner at `origin`
+    pub fn region_at(&self, origin: WorldCoords) -> Region {
+        Region::new(
+            origin,
+            WorldCoords { x: origin.x + self.size.x - 1, y: origin.y + self.size.y - 1, z: origin.z + self.size.z - 1 },
+        )
+    }
+}
+
+#[napi]
+impl Schematic {
+    /// Creates a schematic of the given size (`x` wide, `y` tall, `z` long) from its state IDs.
+    #[napi(constructor)]
+    pub fn new(size: WorldCoords, states: Uint32Array) -> Result<Self> {
+        let volume = size.x as i64 * size.y as i64 * size.z as i64;
+        if size.x <= 0 || size.y <= 0 || size.z <= 0 || volume != states.len() as i64 {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Expected {} state IDs for a {}x{}x{} schematic, got {}", volume.max(0), size.x, size.y, size.z, states.len()),
+            ));
+        }
+        Ok(Schematic { size, states: states.to_vec(), entities: Vec::new() })
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> WorldCoords {
+        self.size
+    }
+
+    /// State ID at a position relative to the schematic's minimum corner, or `null` if it's outside or void.
+    #[napi]
+    pub fn get_state_id(&self, x: i32, y: i32, z: i32) -> Option<u32> {
+        if !(0..self.size.x).contains(&x) || !(0..self.size.y).contains(&y) || !(0..self.size.z).contains(&z) { return None; }
+        Some(self.states[self.index(x, y, z)]).filter(|&state_id| state_id != SCHEMATIC_VOID)
+    }
+
+    /// All state IDs, in storage order.
+    #[napi]
+    pub fn states(&self) -> Uint32Array {
+        Uint32Array::new(self.states.clone())
+    }
+
+    /// Entities stored with the schematic (only structure templates have them).
+    #[napi]
+    pub fn entities(&self) -> Vec<SchematicEntity> {
+        self.entities.clone()
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Copies the blocks of a region into a schematic. Blocks in unloaded columns become void.
+    #[napi]
+    pub fn copy_schematic(&self, region: &Region) -> Schematic {
+        let (min, max) = (region.min(), region.max());
+        let mut schematic = Schematic {
+            size: WorldCoords { x: max.x - min.x + 1, y: max.y - min.y + 1, z: max.z - min.z + 1 },
+            states: vec![SCHEMATIC_VOID; region.volume() as usize],
+            entities: Vec::new(),
+        };
+        self.with_region_columns(region, |column, part| {
+            for pos in part.positions() {
+                let index = schematic.index(pos.x - min.x, pos.y - min.y, pos.z - min.z);
+                schematic.states[index] = column.get_block_state_id(pos);
+            }
+        });
+        schematic
+    }
+
+    /// Compares the world to a schematic placed with its minimum corner at `origin`, listing the blocks that are
+    /// missing (air where the schematic has a block), wrong (another block) or extra (a block where the schematic
+    /// has air). Void positions and unloaded columns are skipped. Lists are in Y, Z, X order per column.
+    /// With `transform`, the schematic is compared as mirrored and rotated, its minimum corner still at `origin`.
+    #[napi]
+    pub fn diff_schematic(&self, schematic: &Schematic, origin: WorldCoords, transform: Option<TransformOptions>) -> Result<SchematicDiff> {
+        let schematic = schematic.transformed(&self.registry, StateTransform::from_options(transform)?);
+        let mut diff = SchematicDiff::default();
+        self.with_region_columns(&schematic.region_at(origin), |column, part| {
+            for pos in part.positions() {
+                let expected = schematic.states[schematic.index(pos.x - origin.x, pos.y - origin.y, pos.z - origin.z)];
+                if expected == SCHEMATIC_VOID { continue; }
+                let actual = column.get_block_state_id(pos);
+                if actual == expected { continue; }
+
+                let entry = SchematicDiffEntry { position: pos, expected, actual };
+                match (self.registry.is_air(expected), self.registry.is_air(actual)) {
+                    (true, true) => {} // Air and cave air are the same to a builder
+                    (false, true) => diff.missing.push(entry),
+                    (true, false) => diff.extra.push(entry),
+                    (false, false) => diff.wrong.push(entry),
+                }
+            }
+        });
+        Ok(diff)
+    }
+
+    /// Reads a vanilla structure template (`.nbt` as saved by structure blocks, gzip compressed or not) into a
+    /// schematic. Positions the template doesn't list (structure voids) are void. With several palettes, the
+    /// first is used. Unknown blocks become air.
+    #[napi]
+    pub fn load_structure(&self, buffer: Buffer) -> Result<Schematic> {
+        decompress_detected(buffer.as_ref())
+            .and_then(|nbt| read_nbt(&nbt))
+            .and_then(|root| Schematic::from_structure_nbt(&root, &self.registry))
+            .map_err(|e| napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Failed to read structure: {}", e),
+            ))
+    }
+
+    /// Writes a schematic into the world with its minimum corner at `origin`. Void positions and unloaded columns
+    /// are left alone. Returns the number of blocks that changed. With `transform`, the schematic is mirrored and
+    /// rotated (stairs, logs, rails... turned to match), its minimum corner still at `origin`.
+    #[napi]
+    pub fn paste_schematic(&self, schematic: &Schematic, origin: WorldCoords, transform: Option<TransformOptions>) -> Result<u32> {
+        let schematic = schematic.transformed(&self.registry, StateTransform::from_options(transform)?);
+        let mut changed = Vec::new();
+        self.with_region_columns_mut(&schematic.region_at(origin), |column, part| {
+            for pos in part.positions() {
+                let state_id = schematic.states[schematic.index(pos.x - origin.x, pos.y - origin.y, pos.z - origin.z)];
+                if state_id != SCHEMATIC_VOID && column.get_block_state_id(pos) != state_id {
+                    column.set_block_state_id(pos, state_id);
+                    changed.push(pos);
+                }
+            }
+        })?;
+        self.blocks_changed(&changed);
+        Ok(changed.len() as u32)
+    }
+}
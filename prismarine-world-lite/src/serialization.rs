@@ -0,0 +1,264 @@
+// src/serialization.rs
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::chunk_keys::column_coords;
+use crate::coords::{ChunkCoords, BIOME_SECTION_VOLUME, MAX_SECTION_Y, MIN_SECTION_Y, SECTION_VOLUME};
+use crate::palette::{BitArray, PaletteContainer};
+use crate::world::NapiWorld;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+// Compact column format, only meant to be read back by this library (e.g. in another worker):
+//   "PWLC" | u8 format version | u8 section count
+//   per section: i8 section Y | i16 solid block count | block states container | biomes container
+//   container: u8 kind (0 single, 1 indirect, 2 direct), then
+//     single: u32 value
+//     indirect: u8 bits | u16 palette length | u32 palette entries | u64 data longs
+//     direct: u8 bits | u64 data longs
+// All integers are little endian; the number of longs follows from the bits and container size.
+const MAGIC: &[u8; 4] = b"PWLC";
+const FORMAT_VERSION: u8 = 1;
+
+const KIND_SINGLE: u8 = 0;
+const KIND_INDIRECT: u8 = 1;
+const KIND_DIRECT: u8 = 2;
+
+pub fn serialize_column(column: &ChunkColumn) -> Vec<u8> {
+    let sections: Vec<_> = column.sections().collect();
+    let mut out = Vec::with_capacity(6 + sections.len() * 64);
+    out.extend_from_slice(MAGIC);
+    out.push(FORMAT_VERSION);
+    out.push(sections.len() as u8);
+
+    for (section_y, section) in sections {
+        out.push(section_y as i8 as u8);
+        out.extend_from_slice(&section.solid_block_count().to_le_bytes());
+        write_container(&mut out, section.block_states());
+        write_container(&mut out, section.biomes());
+    }
+    out
+}
+
+fn write_container(out: &mut Vec<u8>, container: &PaletteContainer) {
+    match container {
+        PaletteContainer::Single(value) => {
+            out.push(KIND_SINGLE);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        PaletteContainer::Indirect { palette, data } => {
+            out.push(KIND_INDIRECT);
+            out.push(data.get_bits_per_value() as u8);
+            out.extend_from_slice(&(palette.len() as u16).to_le_bytes());
+            for entry in palette {
+                out.extend_from_slice(&entry.to_le_bytes());
+            }
+            write_longs(out, data);
+        }
+        PaletteContainer::Direct(data) => {
+            out.push(KIND_DIRECT);
+            out.push(data.get_bits_per_value() as u8);
+            write_longs(out, data);
+        }
+    }
+}
+
+fn write_longs(out: &mut Vec<u8>, data: &BitArray) {
+    for long in data.get_data() {
+        out.write_u64::<LittleEndian>(*long).unwrap();
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+pub fn deserialize_column(data: &[u8]) -> std::io::Result<ChunkColumn> {
+    let mut cursor = Cursor::new(data);
+    let mut magic = [0u8; 4];
+    cursor.read_exact(&mut magic)?;
+    if &magic != MAGIC { return Err(invalid("Not a serialized column")); }
+    let version = cursor.read_u8()?;
+    if version != FORMAT_VERSION {
+        return Err(invalid(&format!("Unsupported column format version {}", version)));
+    }
+
+    let mut column = ChunkColumn::new();
+    for _ in 0..cursor.read_u8()? {
+        let section_y = cursor.read_i8()? as i32;
+        if !(MIN_SECTION_Y..=MAX_SECTION_Y).contains(&section_y) {
+            return Err(invalid(&format!("Section y={} is outside the world", section_y)));
+        }
+        let solid_block_count = cursor.read_i16::<LittleEndian>()?;
+        let block_states = read_container(&mut cursor, SECTION_VOLUME)?;
+        let biomes = read_container(&mut cursor, BIOME_SECTION_VOLUME)?;
+        column.insert_section(section_y, ChunkSection::new(block_states, biomes, solid_block_count));
+    }
+    Ok(column)
+}
+
+fn read_container(cursor: &mut Cursor<&[u8]>, capacity: usize) -> std::io::Result<PaletteContainer> {
+    match cursor.read_u8()? {
+        KIND_SINGLE => Ok(PaletteContainer::new_single(cursor.read_u32::<LittleEndian>()?)),
+        KIND_INDIRECT => {
+            let bits = cursor.read_u8()? as usize;
+            let palette_len = cursor.read_u16::<LittleEndian>()? as usize;
+            let palette = (0..palette_len)
+                .map(|_| cursor.read_u32::<LittleEndian>())
+                .collect::<std::io::Result<Vec<u32>>>()?;
+            if palette.is_empty() { return Err(invalid("Empty palette")); }
+            Ok(PaletteContainer::new_indirect(palette, read_bit_array(cursor, bits, capacity)?))
+        }
+        KIND_DIRECT => {
+            let bits = cursor.read_u8()? as usize;
+            Ok(PaletteContainer::new_direct(read_bit_array(cursor, bits, capacity)?))
+        }
+        kind => Err(invalid(&format!("Unknown palette kind {}", kind))),
+    }
+}
+
+fn read_bit_array(cursor: &mut Cursor<&[u8]>, bits: usize, capacity: usize) -> std::io::Result<BitArray> {
+    if bits == 0 || bits > 32 { return Err(invalid(&format!("Invalid bits per value {}", bits))); }
+    let longs = (0..capacity.div_ceil(64 / bits))
+        .map(|_| cursor.read_u64::<LittleEndian>())
+        .collect::<std::io::Result<Vec<u64>>>()?;
+    BitArray::try_from_data(bits, capacity, longs).ok_or_else(|| invalid("Data length doesn't match bits per value"))
+}
+
+#[napi]
+impl NapiWorld {
+    /// Serializes a loaded column (by coordinates or `chunkKey`) into a compact buffer for `deserialize_column`,
+    /// e.g. to hand it to a worker thread. The format is internal to this library and may change between versions.
+    #[napi]
+    pub fn serialize_column(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<Buffer> {
+        let chunk_coords = column_coords(chunk_x, chunk_z)?;
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords).ok_or_else(|| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Chunk at {}, {} not loaded", chunk_coords.x, chunk_coords.z),
+        ))?;
+        let column = entry.value().read().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire read lock for chunk".to_string(),
+        ))?;
+        Ok(serialize_column(&column).into())
+    }
+
+    /// Loads a column produced by `serialize_column`, replacing any column already loaded at these coordinates.
+    #[napi]
+    pub fn deserialize_column(&self, chunk_x: i32, chunk_z: i32, data: Buffer) -> Result<()> {
+        let column = deserialize_column(&data).map_err(|e| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Failed to deserialize column: {}", e),
+        ))?;
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, column);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::test_registry;
+
+    // One section of each container kind, with mixed biomes in the indirect one
+    fn sample_column() -> ChunkColumn {
+        let registry = test_registry();
+        let mut column = ChunkColumn::new();
+        column.insert_section(MIN_SECTION_Y, ChunkSection::from_block_states(&vec![1; SECTION_VOLUME], &registry));
+
+        let mut indirect = ChunkSection::from_block_states(&(0..SECTION_VOLUME as u32).map(|i| i % 7).collect::<Vec<_>>(), &registry);
+        indirect.set_biomes(&(0..BIOME_SECTION_VOLUME as u32).map(|i| i % 3).collect::<Vec<_>>());
+        column.insert_section(0, indirect);
+
+        let direct = ChunkSection::from_block_states(&(0..SECTION_VOLUME as u32).map(|i| (i * 3) % 1042).collect::<Vec<_>>(), &registry);
+        column.insert_section(MAX_SECTION_Y, direct);
+        column
+    }
+
+    fn assert_same_sections(expected: &ChunkColumn, actual: &ChunkColumn) {
+        let expected: Vec<_> = expected.sections().collect();
+        let actual: Vec<_> = actual.sections().collect();
+        assert_eq!(expected.len(), actual.len());
+        for ((expected_y, expected), (actual_y, actual)) in expected.into_iter().zip(actual) {
+            assert_eq!(expected_y, actual_y);
+            assert_eq!(expected.solid_block_count(), actual.solid_block_count());
+            for index in 0..SECTION_VOLUME {
+                assert_eq!(expected.block_states().get(index), actual.block_states().get(index), "section {} block {}", actual_y, index);
+            }
+            for index in 0..BIOME_SECTION_VOLUME {
+                assert_eq!(expected.biomes().get(index), actual.biomes().get(index), "section {} biome {}", actual_y, index);
+            }
+        }
+    }
+
+    #[test]
+    fn columns_round_trip() {
+        let column = sample_column();
+        let data = serialize_column(&column);
+        assert_same_sections(&column, &deserialize_column(&data).unwrap());
+        assert_eq!(serialize_column(&deserialize_column(&data).unwrap()), data);
+
+        let empty = serialize_column(&ChunkColumn::new());
+        assert_eq!(deserialize_column(&empty).unwrap().sections().count(), 0);
+    }
+
+    #[test]
+    fn malformed_data_is_rejected_without_panicking() {
+        let data = serialize_column(&sample_column());
+        for len in 0..data.len() {
+            assert!(deserialize_column(&data[..len]).is_err(), "prefix of {} bytes", len);
+        }
+
+        let mut bad_magic = data.clone();
+        bad_magic[0] = b'X';
+        assert!(deserialize_column(&bad_magic).is_err());
+        let mut bad_version = data.clone();
+        bad_version[4] = FORMAT_VERSION + 1;
+        assert!(deserialize_column(&bad_version).is_err());
+
+        // A single section whose header is followed by the given block states container
+        let section_with = |container: &[u8]| {
+            let mut out = MAGIC.to_vec();
+            out.extend_from_slice(&[FORMAT_VERSION, 1, 0, 0, 0]);
+            out.extend_from_slice(container);
+            out.extend_from_slice(&[KIND_SINGLE, 0, 0, 0, 0]);
+            out
+        };
+        assert!(deserialize_column(&section_with(&[KIND_SINGLE, 1, 0, 0, 0])).is_ok());
+        assert!(deserialize_column(&section_with(&[3, 1, 0, 0, 0])).is_err(), "unknown kind");
+        assert!(deserialize_column(&section_with(&[KIND_INDIRECT, 4, 0, 0])).is_err(), "empty palette");
+        for bits in [0, 33, 255] {
+            assert!(deserialize_column(&section_with(&[KIND_DIRECT, bits])).is_err(), "{} bits", bits);
+        }
+        let mut out_of_world = section_with(&[KIND_SINGLE, 1, 0, 0, 0]);
+        out_of_world[6] = (MAX_SECTION_Y + 1) as i8 as u8;
+        assert!(deserialize_column(&out_of_world).is_err());
+
+        // Random corruptions must either fail or give a column that can be read everywhere
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..500 {
+            let mut corrupted = data.clone();
+            for _ in 0..1 + next() % 4 {
+                let index = (next() % corrupted.len() as u64) as usize;
+                corrupted[index] = next() as u8;
+            }
+            if let Ok(column) = deserialize_column(&corrupted) {
+                for (_, section) in column.sections() {
+                    for index in (0..SECTION_VOLUME).step_by(97) {
+                        section.block_states().get(index);
+                    }
+                    for index in 0..BIOME_SECTION_VOLUME {
+                        section.biomes().get(index);
+                    }
+                }
+            }
+        }
+    }
+}
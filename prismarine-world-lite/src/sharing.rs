@@ -0,0 +1,104 @@
+// src/sharing.rs
+use crate::anvil::RegionDir;
+use crate::config::WorldConfig;
+use crate::coords::ChunkCoords;
+use crate::navmesh::ChunkNavmesh;
+use crate::registry::{BiomeRegistry, BlockRegistry};
+use crate::world::{ColumnStore, NapiWorld};
+use dashmap::DashMap;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Identifies native world state shared with `share`. Plain data, so it can be posted to worker threads.
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct ExternalHandle {
+    pub id: u32,
+}
+
+// The thread-safe part of a world, reachable from every worker of the process
+struct SharedWorld {
+    store: ColumnStore,
+    registry: Arc<BlockRegistry>,
+    biomes: Arc<BiomeRegistry>,
+    navmeshes: Arc<DashMap<ChunkCoords, ChunkNavmesh>>,
+    bedrock_runtime_ids: Option<Arc<Vec<u32>>>,
+    region_dir: Arc<RwLock<Option<Arc<RegionDir>>>>,
+    config: Arc<WorldConfig>,
+}
+
+static SHARED_WORLDS: OnceLock<DashMap<u32, SharedWorld>> = OnceLock::new();
+static NEXT_HANDLE_ID: AtomicU32 = AtomicU32::new(1);
+
+fn shared_worlds() -> &'static DashMap<u32, SharedWorld> {
+    SHARED_WORLDS.get_or_init(DashMap::new)
+}
+
+#[napi]
+impl NapiWorld {
+    /// Shares this world's native state (columns, block/biome data, options such as the dimension's Y range, the cold
+    /// column policy, navmesh and block caches) with other worker threads. Post the returned handle to a worker and
+    /// open it there with `World.fromHandle`: both sides then operate on the same loaded chunks without copying them.
+    /// Watchers, subscriptions and transactions remain per world.
+    #[napi]
+    pub fn share(&self) -> ExternalHandle {
+        let id = NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed);
+        shared_worlds().insert(id, SharedWorld {
+            store: self.column_store(),
+            registry: Arc::clone(&self.registry),
+            biomes: Arc::clone(&self.biomes),
+            navmeshes: Arc::clone(&self.navmeshes),
+            bedrock_runtime_ids: self.bedrock_runtime_ids.clone(),
+            region_dir: Arc::clone(&self.region_dir),
+            config: Arc::clone(&self.config),
+        });
+        ExternalHandle { id }
+    }
+
+    /// Opens a world shared with `share`, from any thread of the process.
+    #[napi(factory)]
+    pub fn from_handle(handle: ExternalHandle) -> Result<Self> {
+        let shared = shared_worlds().get(&handle.id).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("No shared world with handle {}", handle.id),
+        ))?;
+        let mut world = NapiWorld::from_parts(shared.store.clone(), Arc::clone(&shared.registry), Arc::clone(&shared.biomes));
+        world.navmeshes = Arc::clone(&shared.navmeshes);
+        world.bedrock_runtime_ids = shared.bedrock_runtime_ids.clone();
+        world.region_dir = Arc::clone(&shared.region_dir);
+        world.config = Arc::clone(&shared.config);
+        Ok(world)
+    }
+
+    /// Forgets a shared handle so it can't be opened anymore. Worlds already opened from it keep working.
+    /// Returns false if the handle was unknown.
+    #[napi]
+    pub fn release_handle(handle: ExternalHandle) -> bool {
+        shared_worlds().remove(&handle.id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::{test_biomes, test_registry};
+
+    #[test]
+    fn opened_worlds_keep_the_options_and_columns() {
+        let mut world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(test_registry()), Arc::new(test_biomes()));
+        let mut config = WorldConfig::new("test");
+        config.set_dimension(0, 256).unwrap();
+        config.strict_parsing = true;
+        world.config = Arc::new(config);
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, Default::default());
+
+        let handle = world.share();
+        let opened = NapiWorld::from_handle(handle).unwrap();
+        assert!(NapiWorld::release_handle(handle));
+        assert_eq!((opened.config.min_y, opened.config.height, opened.config.strict_parsing), (0, 256, true));
+        assert!(Arc::ptr_eq(&opened.columns, &world.columns));
+        assert!(Arc::ptr_eq(&opened.cold, &world.cold));
+    }
+}
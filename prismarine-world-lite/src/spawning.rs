@@ -0,0 +1,44 @@
+// src/spawning.rs
+use crate::coords::WorldCoords;
+use crate::world::NapiWorld;
+use napi_derive::napi;
+
+// Blocks mobs can never spawn inside, even though they have no collision
+const NO_SPAWN_INSIDE: &[&str] = &["water", "lava", "powder_snow", "sweet_berry_bush", "wither_rose", "fire", "soul_fire", "cobweb"];
+
+#[napi]
+impl NapiWorld {
+    /// Checks whether a hostile mob could naturally spawn with its feet at the given position.
+    ///
+    /// The block below must be a solid, non-transparent block and the feet and head blocks must be free of collision boxes and fluids.
+    #[napi]
+    pub fn is_spawnable(&self, x: i32, y: i32, z: i32) -> bool {
+        let floor = self.get_block_state_id(x, y - 1, z);
+        if !self.registry.is_solid(floor) || self.registry.block(floor).is_none_or(|b| b.transparent) {
+            return false;
+        }
+
+        // TODO: Require block light 0 once light data is parsed (get_block_light is a placeholder)
+        (y..=y + 1).all(|body_y| {
+            let state_id = self.get_block_state_id(x, body_y, z);
+            self.registry.collision_shapes(state_id).is_empty()
+                && self.registry.block(state_id).is_none_or(|b| !NO_SPAWN_INSIDE.contains(&b.name.as_str()))
+        })
+    }
+
+    /// Finds every spawnable position inside the region (inclusive corners), see `is_spawnable`.
+    #[napi]
+    pub fn find_spawnable_positions(&self, min: WorldCoords, max: WorldCoords) -> Vec<WorldCoords> {
+        let mut positions = Vec::new();
+        for y in min.y.min(max.y)..=min.y.max(max.y) {
+            for z in min.z.min(max.z)..=min.z.max(max.z) {
+                for x in min.x.min(max.x)..=min.x.max(max.x) {
+                    if self.is_spawnable(x, y, z) {
+                        positions.push(WorldCoords { x, y, z });
+                    }
+                }
+            }
+        }
+        positions
+    }
+}
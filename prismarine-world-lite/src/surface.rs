@@ -0,0 +1,190 @@
+// src/surface.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords, MAX_CHUNK_Y, MAX_SECTION_Y, MIN_CHUNK_Y, MIN_SECTION_Y};
+use crate::region::Region;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceBlock {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub state_id: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceHit {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub state_id: u32,
+    // Top of the block's collision boxes when looking down, bottom when looking up
+    pub surface_y: f64,
+}
+
+// Topmost block of the column at (x, z) within min_y..=max_y whose state matches, as (y, state ID).
+// Sections whose palette has no matching state are skipped without reading their blocks.
+pub(crate) fn highest_matching(column: &ChunkColumn, x: i32, z: i32, min_y: i32, max_y: i32, matches: impl Fn(u32) -> bool) -> Option<(i32, u32)> {
+    let (min_y, max_y) = (min_y.max(MIN_CHUNK_Y), max_y.min(MAX_CHUNK_Y - 1));
+    if min_y > max_y { return None; }
+
+    for section_y in (min_y >> 4..=max_y >> 4).rev() {
+        let Some(section) = column.get_section(section_y) else { continue; };
+        if !section.block_states().may_contain(&matches) { continue; }
+
+        let bottom = (section_y << 4).max(min_y);
+        let top = ((section_y << 4) + 15).min(max_y);
+        for y in (bottom..=top).rev() {
+            let state_id = column.get_block_state_id(WorldCoords { x, y, z });
+            if matches(state_id) { return Some((y, state_id)); }
+        }
+    }
+    None
+}
+
+// Bottommost block of the column at (x, z) within min_y..=max_y whose state matches, like `highest_matching`
+fn lowest_matching(column: &ChunkColumn, x: i32, z: i32, min_y: i32, max_y: i32, matches: impl Fn(u32) -> bool) -> Option<(i32, u32)> {
+    let (min_y, max_y) = (min_y.max(MIN_CHUNK_Y), max_y.min(MAX_CHUNK_Y - 1));
+    if min_y > max_y { return None; }
+
+    for section_y in min_y >> 4..=max_y >> 4 {
+        let Some(section) = column.get_section(section_y) else { continue; };
+        if !section.block_states().may_contain(&matches) { continue; }
+
+        let bottom = (section_y << 4).max(min_y);
+        let top = ((section_y << 4) + 15).min(max_y);
+        for y in bottom..=top {
+            let state_id = column.get_block_state_id(WorldCoords { x, y, z });
+            if matches(state_id) { return Some((y, state_id)); }
+        }
+    }
+    None
+}
+
+impl NapiWorld {
+    // Sky light coming straight down the column at (x, z) until the first opaque block, losing each
+    // translucent block's light filter on the way. Sections without light-filtering blocks are skipped.
+    fn direct_sky_light(&self, column: &ChunkColumn, x: i32, z: i32) -> u8 {
+        let mut level = 15u8;
+        for section_y in (MIN_SECTION_Y..=MAX_SECTION_Y).rev() {
+            let Some(section) = column.get_section(section_y) else { continue; };
+            if !section.block_states().may_contain(|state_id| self.registry.light_filter(state_id) > 0) { continue; }
+
+            for y in ((section_y << 4)..(section_y << 4) + 16).rev() {
+                let filter = self.registry.light_filter(column.get_block_state_id(WorldCoords { x, y, z }));
+                if filter >= 15 { return level; }
+                level = level.saturating_sub(filter);
+            }
+        }
+        level
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Finds the topmost block at (x, z) whose state is one of `state_ids`, or the topmost non-air block if none
+    /// are given. Returns `null` if the chunk isn't loaded or nothing matches.
+    #[napi]
+    pub fn get_highest_block(&self, x: i32, z: i32, state_ids: Option<Vec<u32>>) -> Option<SurfaceBlock> {
+        let chunk_coords = WorldCoords { x, y: 0, z }.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().read().ok()?;
+
+        let wanted: Option<HashSet<u32>> = state_ids.map(|ids| ids.into_iter().collect());
+        let matches = |state_id: u32| match &wanted {
+            Some(wanted) => wanted.contains(&state_id),
+            None => !self.registry.is_air(state_id),
+        };
+        highest_matching(&column, x, z, MIN_CHUNK_Y, MAX_CHUNK_Y - 1, matches)
+            .map(|(y, state_id)| SurfaceBlock { x, y, z, state_id })
+    }
+
+    /// Finds the topmost solid block of every (x, z) column in the region, looking only within the region's Y range.
+    /// Columns without a solid block there, and unloaded chunks, are left out.
+    #[napi]
+    pub fn get_surface_blocks(&self, region: &Region) -> Vec<SurfaceBlock> {
+        let mut surface = Vec::new();
+        self.with_region_columns(region, |column, part| {
+            let (min, max) = (part.min(), part.max());
+            for x in min.x..=max.x {
+                for z in min.z..=max.z {
+                    let top = highest_matching(column, x, z, min.y, max.y, |state_id| self.registry.is_solid(state_id));
+                    if let Some((y, state_id)) = top {
+                        surface.push(SurfaceBlock { x, y, z, state_id });
+                    }
+                }
+            }
+        });
+        surface
+    }
+
+    /// Gets how open to the sky each block column of a chunk is: the sky light level right above its topmost
+    /// opaque block, after passing through the translucent blocks above it (leaves, water, glass...).
+    /// 15 means nothing filters the sky there. Only direct light is considered, not light spreading sideways.
+    /// Returns 256 values indexed by `z * 16 + x` (chunk-relative).
+    #[napi]
+    pub fn get_sky_exposure(&self, chunk_x: i32, chunk_z: i32) -> Result<Uint8Array> {
+        let chunk_coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Chunk at {}, {} not loaded", chunk_x, chunk_z),
+        ))?;
+        let column = entry.value().read().map_err(|_| napi::Error::new(
+            napi::Status::GenericFailure,
+            "Failed to acquire read lock for chunk".to_string(),
+        ))?;
+
+        let mut exposure = vec![0u8; 256];
+        for z in 0..16 {
+            for x in 0..16 {
+                exposure[(z * 16 + x) as usize] = self.direct_sky_light(&column, (chunk_x << 4) + x, (chunk_z << 4) + z);
+            }
+        }
+        Ok(Uint8Array::new(exposure))
+    }
+
+    /// Finds the first solid block straight below (or, with `up`, above) a point of the block column (x, z),
+    /// starting with the block containing `fromY` (default: the top of the world when looking down). Looking down
+    /// from above the terrain is answered from a cached heightmap, kept up to date as blocks change, instead of
+    /// stepping through the air in between. Returns `null` if the chunk isn't loaded or no block is found.
+    #[napi]
+    pub fn surface_raycast(&self, x: i32, z: i32, from_y: Option<f64>, up: Option<bool>) -> Option<SurfaceHit> {
+        let chunk_coords = WorldCoords { x, y: 0, z }.to_chunk_coords();
+        self.ensure_column(chunk_coords);
+        let entry = self.columns.get(&chunk_coords)?;
+        let column = entry.value().read().ok()?;
+        let up = up.unwrap_or(false);
+        let solid = |state_id: u32| self.registry.is_solid(state_id);
+        let height = self.solid_height(&column, x, z);
+
+        let (y, state_id) = if up {
+            let from_y = from_y.map_or(MIN_CHUNK_Y, |y| y.floor() as i32);
+            if from_y > height { return None; }
+            lowest_matching(&column, x, z, from_y, height, solid)?
+        } else {
+            let from_y = from_y.map_or(MAX_CHUNK_Y - 1, |y| y.floor() as i32);
+            if from_y >= height {
+                if height < MIN_CHUNK_Y { return None; }
+                (height, column.get_block_state_id(WorldCoords { x, y: height, z }))
+            } else {
+                highest_matching(&column, x, z, MIN_CHUNK_Y, from_y, solid)?
+            }
+        };
+        // Solid blocks without collision data are full cubes
+        let shapes = self.registry.collision_shapes(state_id);
+        let surface_y = match (up, shapes.is_empty()) {
+            (true, true) => y as f64,
+            (false, true) => y as f64 + 1.0,
+            (true, false) => y as f64 + shapes.iter().map(|shape| shape[1]).fold(f64::INFINITY, f64::min),
+            (false, false) => y as f64 + shapes.iter().map(|shape| shape[4]).fold(f64::NEG_INFINITY, f64::max),
+        };
+        Some(SurfaceHit { x, y, z, state_id, surface_y })
+    }
+}
@@ -0,0 +1,138 @@
+// src/tags.rs
+#[cfg(feature = "napi")]
+use crate::world::NapiWorld;
+use minecraft_data_rs::models::block::Block;
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+
+// minecraft-data has no tag files, so vanilla block tags are approximated here. Only `mineable/*` comes from game
+// data (each block's material); every other tag is guessed from block names and can miss members of newer
+// versions or pick up unrelated blocks that share a suffix. Tags outside these lists are unknown.
+
+const SMALL_FLOWERS: &[&str] = &[
+    "dandelion", "poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip", "orange_tulip", "white_tulip",
+    "pink_tulip", "oxeye_daisy", "cornflower", "lily_of_the_valley", "wither_rose", "torchflower",
+];
+const TALL_FLOWERS: &[&str] = &["sunflower", "lilac", "rose_bush", "peony", "pitcher_plant"];
+const ORE_KINDS: &[&str] = &["coal", "iron", "copper", "gold", "redstone", "lapis", "diamond", "emerald"];
+
+// Tags whose members are listed by name
+const NAMED_TAGS: &[(&str, &[&str])] = &[
+    ("rails", &["rail", "powered_rail", "detector_rail", "activator_rail"]),
+    ("sand", &["sand", "red_sand", "suspicious_sand"]),
+    ("dirt", &["dirt", "coarse_dirt", "grass_block", "podzol", "mycelium", "rooted_dirt", "moss_block", "mud", "muddy_mangrove_roots"]),
+    ("ice", &["ice", "packed_ice", "blue_ice", "frosted_ice"]),
+    ("climbable", &["ladder", "vine", "scaffolding", "weeping_vines", "weeping_vines_plant", "twisting_vines", "twisting_vines_plant", "cave_vines", "cave_vines_plant"]),
+    ("crops", &["wheat", "carrots", "potatoes", "beetroots", "melon_stem", "pumpkin_stem", "torchflower_crop", "pitcher_crop"]),
+    ("fire", &["fire", "soul_fire"]),
+    ("campfires", &["campfire", "soul_campfire"]),
+    ("beehives", &["bee_nest", "beehive"]),
+    ("anvil", &["anvil", "chipped_anvil", "damaged_anvil"]),
+    ("small_flowers", SMALL_FLOWERS),
+    ("tall_flowers", TALL_FLOWERS),
+];
+
+// Tags whose members share a name suffix
+const SUFFIX_TAGS: &[(&str, &str)] = &[
+    ("leaves", "_leaves"),
+    ("planks", "_planks"),
+    ("wool", "_wool"),
+    ("stairs", "_stairs"),
+    ("slabs", "_slab"),
+    ("walls", "_wall"),
+    ("fences", "_fence"),
+    ("fence_gates", "_fence_gate"),
+    ("doors", "_door"),
+    ("trapdoors", "_trapdoor"),
+    ("buttons", "_button"),
+    ("pressure_plates", "_pressure_plate"),
+    ("beds", "_bed"),
+    ("banners", "_banner"),
+    ("all_signs", "_sign"),
+];
+
+// The (unprefixed) tags a block belongs to
+pub fn block_tags(block: &Block) -> Vec<String> {
+    let name = block.name.as_str();
+    let mut tags: Vec<String> = block
+        .material
+        .as_deref()
+        .unwrap_or("")
+        .split(';')
+        .filter(|material| material.starts_with("mineable/"))
+        .map(str::to_string)
+        .collect();
+
+    tags.extend(NAMED_TAGS.iter().filter(|(_, members)| members.contains(&name)).map(|(tag, _)| tag.to_string()));
+    tags.extend(SUFFIX_TAGS.iter().filter(|(_, suffix)| name.ends_with(suffix)).map(|(tag, _)| tag.to_string()));
+
+    let mut add = |tag: &str, member: bool| if member { tags.push(tag.to_string()); };
+    // Of the stems, only the nether ones are logs (not mushroom, melon or pumpkin stems)
+    let stem = name.strip_prefix("stripped_").unwrap_or(name);
+    add("logs", name.ends_with("_log") || name.ends_with("_wood") || name.ends_with("_hyphae") || matches!(stem, "crimson_stem" | "warped_stem"));
+    add("wool_carpets", name.ends_with("_carpet") && name != "moss_carpet");
+    add("signs", name.ends_with("_sign") && !name.contains("hanging"));
+    add("saplings", name.ends_with("_sapling") || matches!(name, "mangrove_propagule" | "azalea" | "flowering_azalea"));
+    add("flowers", SMALL_FLOWERS.contains(&name) || TALL_FLOWERS.contains(&name));
+    add("candles", name == "candle" || name.ends_with("_candle"));
+    add("shulker_boxes", name == "shulker_box" || name.ends_with("_shulker_box"));
+    add("cauldrons", name == "cauldron" || name.ends_with("_cauldron"));
+    add("terracotta", name == "terracotta" || name.ends_with("_terracotta") && !name.ends_with("glazed_terracotta"));
+
+    // coal_ore and deepslate_coal_ore are coal_ores, nether_gold_ore is a gold_ores
+    if let Some(kind) = name.strip_suffix("_ore").map(|n| n.trim_start_matches("deepslate_").trim_start_matches("nether_")) {
+        if ORE_KINDS.contains(&kind) { tags.push(format!("{}_ores", kind)); }
+    }
+    tags
+}
+
+// Tag name as given by users: `#minecraft:logs`, `#logs`, `minecraft:logs` or `logs`
+pub fn normalize_tag(tag: &str) -> &str {
+    let tag = tag.strip_prefix('#').unwrap_or(tag);
+    tag.strip_prefix("minecraft:").unwrap_or(tag)
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+impl NapiWorld {
+    /// Lists the block tags (e.g. `logs`, `mineable/pickaxe`) of a block state's block. minecraft-data has no tag
+    /// data, so these are approximations, also used wherever blocks can be given as `#` tags: `mineable/*` tags
+    /// come from block materials, while `rails`, `sand`, `dirt`, `ice`, `climbable`, `crops`, `fire`, `campfires`,
+    /// `beehives`, `anvil`, `small_flowers`, `tall_flowers`, `flowers`, `leaves`, `planks`, `wool`, `wool_carpets`,
+    /// `stairs`, `slabs`, `walls`, `fences`, `fence_gates`, `doors`, `trapdoors`, `buttons`, `pressure_plates`,
+    /// `beds`, `banners`, `signs`, `all_signs`, `logs`, `saplings`, `candles`, `shulker_boxes`, `cauldrons`,
+    /// `terracotta` and `<ore>_ores` are guessed from block names and may differ from the game's.
+    #[napi]
+    pub fn get_approximate_block_tags(&self, state_id: u32) -> Vec<String> {
+        self.registry.block(state_id).map(|block| self.registry.tags_of(block.id)).unwrap_or_default()
+    }
+
+    /// Checks whether a block state's block is in an approximated tag (see `getApproximateBlockTags`), given as
+    /// `#minecraft:logs`, `#logs` or `logs`.
+    #[napi]
+    pub fn block_has_approximate_tag(&self, state_id: u32, tag: String) -> bool {
+        self.registry.block(state_id).is_some_and(|block| self.registry.block_has_tag(block.id, &tag))
+    }
+
+    /// Lists the names of the blocks in an approximated tag (see `getApproximateBlockTags`), e.g. for building a
+    /// raycast filter. Unknown tags are empty.
+    #[napi]
+    pub fn get_blocks_in_approximate_tag(&self, tag: String) -> Vec<String> {
+        self.registry.blocks_in_tag(&tag).into_iter().map(|block| block.name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::test_support::test_registry;
+
+    #[test]
+    fn logs_tag_leaves_out_crop_stems() {
+        let registry = test_registry();
+        let has_logs_tag = |name: &str| registry.block_has_tag(registry.block_by_name(name).unwrap().id, "#logs");
+        assert!(has_logs_tag("oak_log"));
+        assert!(has_logs_tag("crimson_stem"));
+        assert!(!has_logs_tag("melon_stem"));
+        assert!(!has_logs_tag("pumpkin_stem"));
+    }
+}
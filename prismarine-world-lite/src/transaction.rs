@@ -0,0 +1,96 @@
+// src/transaction.rs
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::BTreeMap;
+
+// Block writes staged by an open transaction, grouped by column in the order they were made.
+// BTreeMap keeps columns sorted so commits always lock them in the same order.
+pub type StagedEdits = BTreeMap<(i32, i32), Vec<(WorldCoords, u32)>>;
+
+impl NapiWorld {
+    // Stages a block write if a transaction is open. Returns None when the write should be applied directly.
+    pub(crate) fn stage_block_write(&self, coords: WorldCoords, state_id: u32) -> Option<Result<()>> {
+        let mut transaction = self.transaction.lock().unwrap();
+        let staged = transaction.as_mut()?;
+
+        let chunk = coords.to_chunk_coords();
+        if !self.thaw_column(chunk) {
+            return Some(Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Chunk at {}, {} not loaded", chunk.x, chunk.z),
+            )));
+        }
+        staged.entry((chunk.x, chunk.z)).or_default().push((coords, state_id));
+        Some(Ok(()))
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Starts staging `set_block_state_id` writes instead of applying them. Reads keep returning the
+    /// committed blocks until `commit`, which applies every staged write at once; `rollback` discards them.
+    #[napi]
+    pub fn begin_transaction(&self) -> Result<()> {
+        let mut transaction = self.transaction.lock().unwrap();
+        if transaction.is_some() {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "A transaction is already open".to_string(),
+            ));
+        }
+        *transaction = Some(StagedEdits::new());
+        Ok(())
+    }
+
+    /// Applies the open transaction's writes atomically: every affected column is locked before any write happens,
+    /// and watchers/subscribers receive all changes as one batch. If a column was unloaded or can't be locked,
+    /// nothing is written and the transaction stays open. Returns the number of blocks that changed.
+    #[napi]
+    pub fn commit(&self) -> Result<u32> {
+        let mut transaction = self.transaction.lock().unwrap();
+        let Some(staged) = transaction.as_ref() else {
+            return Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "No transaction is open".to_string(),
+            ));
+        };
+
+        let mut columns = Vec::with_capacity(staged.len());
+        for &(x, z) in staged.keys() {
+            self.thaw_column(ChunkCoords { x, z }); // May have gone cold since it was staged
+            self.unshare_column(ChunkCoords { x, z });
+            let entry = self.columns.get(&ChunkCoords { x, z }).ok_or_else(|| napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Chunk at {}, {} not loaded", x, z),
+            ))?;
+            columns.push(entry.value().clone());
+        }
+        let mut guards = Vec::with_capacity(columns.len());
+        for column in &columns {
+            guards.push(column.try_write().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire write lock for chunk".to_string(),
+            ))?);
+        }
+
+        let mut changed = Vec::new();
+        for (guard, writes) in guards.iter_mut().zip(staged.values()) {
+            changed.extend(self.apply_writes(guard, writes));
+        }
+        drop(guards);
+        *transaction = None;
+        drop(transaction);
+
+        changed.sort_unstable_by_key(|p| (p.x, p.y, p.z));
+        self.blocks_changed(&changed);
+        Ok(changed.len() as u32)
+    }
+
+    /// Discards the open transaction's staged writes. Returns false if no transaction was open.
+    #[napi]
+    pub fn rollback(&self) -> bool {
+        self.transaction.lock().unwrap().take().is_some()
+    }
+}
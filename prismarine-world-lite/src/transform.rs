@@ -0,0 +1,258 @@
+// src/transform.rs
+use crate::registry::BlockRegistry;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// Horizontal directions in clockwise order, seen from above
+const DIRECTIONS: [&str; 4] = ["north", "east", "south", "west"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorAxis {
+    X, // Flips east and west
+    Z, // Flips north and south
+}
+
+// A mirror (applied first) followed by a clockwise rotation, for blocks and schematics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateTransform {
+    pub mirror: Option<MirrorAxis>,
+    pub quarter_turns: u32, // Clockwise, 0-3
+}
+
+/// How to transform a schematic when placing or comparing it: mirrored first, then rotated clockwise around Y.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct TransformOptions {
+    pub rotation: Option<u32>,  // Degrees clockwise seen from above: 0, 90, 180 or 270
+    pub mirror: Option<String>, // "x" (flips east/west) or "z" (flips north/south)
+}
+
+pub fn parse_rotation(degrees: u32) -> Result<u32> {
+    if !degrees.is_multiple_of(90) {
+        return Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Rotation must be a multiple of 90 degrees, got {}", degrees),
+        ));
+    }
+    Ok(degrees / 90 % 4)
+}
+
+pub fn parse_mirror(axis: &str) -> Result<MirrorAxis> {
+    match axis {
+        "x" => Ok(MirrorAxis::X),
+        "z" => Ok(MirrorAxis::Z),
+        _ => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Mirror axis must be 'x' or 'z', got {}", axis),
+        )),
+    }
+}
+
+impl StateTransform {
+    pub fn from_options(options: Option<TransformOptions>) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        Ok(StateTransform {
+            mirror: options.mirror.as_deref().map(parse_mirror).transpose()?,
+            quarter_turns: parse_rotation(options.rotation.unwrap_or(0))?,
+        })
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.mirror.is_none() && self.quarter_turns == 0
+    }
+
+    // Transforms a horizontal direction name; anything else (up, down, non-directions) is returned as is
+    pub fn direction<'a>(&self, direction: &'a str) -> &'a str {
+        let Some(mut index) = DIRECTIONS.iter().position(|&d| d == direction) else { return direction; };
+        index = match self.mirror {
+            Some(MirrorAxis::X) if index % 2 == 1 => (index + 2) % 4,
+            Some(MirrorAxis::Z) if index % 2 == 0 => (index + 2) % 4,
+            _ => index,
+        };
+        DIRECTIONS[(index + self.quarter_turns as usize) % 4]
+    }
+
+    // Transforms a 16-step `rotation` property (0 = south, increasing clockwise)
+    fn rotation_step(&self, step: u32) -> u32 {
+        let step = match self.mirror {
+            Some(MirrorAxis::X) => (16 - step) % 16,
+            Some(MirrorAxis::Z) => (24 - step) % 16,
+            None => step,
+        };
+        (step + self.quarter_turns * 4) % 16
+    }
+
+    // Transforms a rail `shape` (north_south, ascending_east, south_west...)
+    fn rail_shape(&self, shape: &str) -> Option<String> {
+        if let Some(direction) = shape.strip_prefix("ascending_") {
+            return Some(format!("ascending_{}", self.direction(direction)));
+        }
+        let (a, b) = shape.split_once('_')?;
+        if !DIRECTIONS.contains(&a) || !DIRECTIONS.contains(&b) { return None; }
+        let mut ends = [self.direction(a), self.direction(b)];
+        // Names list north/south first: north_south, east_west, south_east...
+        ends.sort_by_key(|d| match *d { "north" => 0, "south" => 1, "east" => 2, _ => 3 });
+        Some(ends.join("_"))
+    }
+}
+
+fn swap_sides(value: &str) -> Option<String> {
+    if value.contains("left") {
+        Some(value.replace("left", "right"))
+    } else if value.contains("right") {
+        Some(value.replace("right", "left"))
+    } else {
+        None
+    }
+}
+
+// Applies a transform to a block state through its properties: facings, axes, 16-step rotations, per-side
+// connections (fences, walls, redstone), rail shapes, and left/right of stairs, doors and chests when mirrored.
+// States that can't be expressed after the transform are returned unchanged.
+pub fn transform_state(registry: &BlockRegistry, state_id: u32, transform: StateTransform) -> u32 {
+    if transform.is_identity() { return state_id; }
+    let Some(block) = registry.block(state_id) else { return state_id; };
+    // Without properties there's nothing to turn, and rebuilding the state would lose which one it was
+    if block.states.as_ref().is_none_or(|states| states.is_empty()) { return state_id; }
+    let mirrored = transform.mirror.is_some();
+
+    let properties: Vec<(String, String)> = registry
+        .state_properties(state_id)
+        .into_iter()
+        .map(|(name, value)| match name.as_str() {
+            "facing" => (name, transform.direction(&value).to_string()),
+            "axis" if transform.quarter_turns % 2 == 1 => {
+                let axis = match value.as_str() { "x" => "z", "z" => "x", other => other };
+                (name, axis.to_string())
+            }
+            "rotation" => {
+                let value = value.parse().map(|step| transform.rotation_step(step).to_string()).unwrap_or(value);
+                (name, value)
+            }
+            "north" | "east" | "south" | "west" => (transform.direction(&name).to_string(), value),
+            "shape" => {
+                let shape = transform.rail_shape(&value)
+                    .or_else(|| if mirrored { swap_sides(&value) } else { None })
+                    .unwrap_or(value);
+                (name, shape)
+            }
+            "hinge" | "type" if mirrored => {
+                let value = swap_sides(&value).unwrap_or(value);
+                (name, value)
+            }
+            _ => (name, value),
+        })
+        .collect();
+    registry.state_id_from_properties(&block.name, &properties).unwrap_or(state_id)
+}
+
+#[napi]
+impl NapiWorld {
+    /// Rotates a block state clockwise (seen from above) by 0, 90, 180 or 270 degrees: stairs, logs, rails, signs,
+    /// fences and other directional blocks are turned to match. Blocks without directions are returned as is.
+    #[napi]
+    pub fn rotate_state(&self, state_id: u32, rotation: u32) -> Result<u32> {
+        let transform = StateTransform { mirror: None, quarter_turns: parse_rotation(rotation)? };
+        Ok(transform_state(&self.registry, state_id, transform))
+    }
+
+    /// Mirrors a block state across an axis: `'x'` flips east and west, `'z'` flips north and south. Left and
+    /// right variants (stair corners, door hinges, double chests) swap too.
+    #[napi(ts_args_type = "stateId: number, axis: 'x' | 'z'")]
+    pub fn mirror_state(&self, state_id: u32, axis: String) -> Result<u32> {
+        let transform = StateTransform { mirror: Some(parse_mirror(&axis)?), quarter_turns: 0 };
+        Ok(transform_state(&self.registry, state_id, transform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::test_registry;
+
+    fn state(registry: &BlockRegistry, name: &str, properties: &[(&str, &str)]) -> u32 {
+        let properties: Vec<(String, String)> = properties.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect();
+        registry.state_id_from_properties(name, &properties).unwrap()
+    }
+
+    fn rotate(quarter_turns: u32) -> StateTransform {
+        StateTransform { mirror: None, quarter_turns }
+    }
+
+    fn mirror(axis: MirrorAxis) -> StateTransform {
+        StateTransform { mirror: Some(axis), quarter_turns: 0 }
+    }
+
+    #[test]
+    fn directional_states_turn_and_flip() {
+        let registry = test_registry();
+        let r = &registry;
+        let stairs = |facing, shape| state(r, "oak_stairs", &[("facing", facing), ("half", "top"), ("shape", shape)]);
+        assert_eq!(transform_state(r, stairs("north", "straight"), rotate(1)), stairs("east", "straight"));
+        assert_eq!(transform_state(r, stairs("north", "inner_left"), mirror(MirrorAxis::Z)), stairs("south", "inner_right"));
+        assert_eq!(transform_state(r, stairs("north", "outer_right"), mirror(MirrorAxis::X)), stairs("north", "outer_left"));
+
+        let log = |axis| state(r, "oak_log", &[("axis", axis)]);
+        assert_eq!(transform_state(r, log("x"), rotate(1)), log("z"));
+        assert_eq!(transform_state(r, log("x"), rotate(2)), log("x"));
+        assert_eq!(transform_state(r, log("y"), rotate(3)), log("y"));
+
+        let rail = |shape| state(r, "rail", &[("shape", shape)]);
+        assert_eq!(transform_state(r, rail("north_east"), rotate(1)), rail("south_east"));
+        assert_eq!(transform_state(r, rail("ascending_north"), rotate(1)), rail("ascending_east"));
+        assert_eq!(transform_state(r, rail("north_south"), rotate(3)), rail("east_west"));
+        assert_eq!(transform_state(r, rail("south_west"), mirror(MirrorAxis::X)), rail("south_east"));
+
+        let sign = |rotation| state(r, "oak_sign", &[("rotation", rotation)]);
+        assert_eq!(transform_state(r, sign("0"), rotate(1)), sign("4"));
+        assert_eq!(transform_state(r, sign("4"), mirror(MirrorAxis::X)), sign("12"));
+        assert_eq!(transform_state(r, sign("0"), mirror(MirrorAxis::Z)), sign("8"));
+
+        let fence = |north, east| {
+            state(r, "oak_fence", &[("north", north), ("east", east), ("south", "false"), ("west", "false")])
+        };
+        assert_eq!(transform_state(r, fence("true", "false"), rotate(1)), fence("false", "true"));
+
+        // Blocks without directions, and unknown states, are left alone
+        assert_eq!(transform_state(r, 1, rotate(1)), 1);
+        assert_eq!(transform_state(r, 500, mirror(MirrorAxis::X)), 500);
+        assert_eq!(transform_state(r, u32::MAX, rotate(2)), u32::MAX);
+    }
+
+    #[test]
+    fn transforms_permute_states_and_undo_each_other() {
+        let registry = test_registry();
+        let states = 0..1124;
+        for mirror in [None, Some(MirrorAxis::X), Some(MirrorAxis::Z)] {
+            for quarter_turns in 0..4 {
+                let transform = StateTransform { mirror, quarter_turns };
+                let mut seen = std::collections::HashSet::new();
+                for state_id in states.clone() {
+                    let transformed = transform_state(&registry, state_id, transform);
+                    assert_eq!(registry.block(transformed).map(|b| b.id), registry.block(state_id).map(|b| b.id));
+                    assert!(seen.insert(transformed), "{:?} maps two states to {}", transform, transformed);
+                }
+            }
+        }
+
+        for state_id in states {
+            let turned = (0..4).fold(state_id, |state_id, _| transform_state(&registry, state_id, rotate(1)));
+            assert_eq!(turned, state_id, "four quarter turns of {}", state_id);
+            for axis in [MirrorAxis::X, MirrorAxis::Z] {
+                let flipped = transform_state(&registry, transform_state(&registry, state_id, mirror(axis)), mirror(axis));
+                assert_eq!(flipped, state_id, "two {:?} mirrors of {}", axis, state_id);
+            }
+        }
+    }
+
+    #[test]
+    fn transform_arguments_are_validated() {
+        assert_eq!(parse_rotation(270).unwrap(), 3);
+        assert_eq!(parse_rotation(360).unwrap(), 0);
+        assert!(parse_rotation(45).is_err());
+        assert_eq!(parse_mirror("z").unwrap(), MirrorAxis::Z);
+        assert!(parse_mirror("y").is_err());
+        assert!(StateTransform::from_options(Some(TransformOptions { rotation: Some(90), mirror: Some("X".to_string()) })).is_err());
+    }
+}
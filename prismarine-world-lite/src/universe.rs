@@ -0,0 +1,117 @@
+// src/universe.rs
+use crate::config::WorldConfig;
+use crate::coords::{MIN_CHUNK_Y, WORLD_HEIGHT};
+use crate::registry::{BiomeRegistry, BlockRegistry};
+use crate::world::{load_registries, ColumnStore, NapiWorld};
+use dashmap::DashMap;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::{Arc, RwLock};
+
+/// A set of named worlds (one per dimension) for one Minecraft version, sharing their block and biome data.
+/// Dimension names may be given with or without the `minecraft:` prefix.
+#[napi]
+pub struct Universe {
+    registry: Arc<BlockRegistry>,
+    biomes: Arc<BiomeRegistry>,
+    worlds: DashMap<String, NapiWorld>,
+    current: RwLock<Option<String>>,
+}
+
+fn dimension_key(name: &str) -> String {
+    name.strip_prefix("minecraft:").unwrap_or(name).to_string()
+}
+
+// (min Y, height) of the vanilla dimensions; others default to the overworld's
+fn dimension_layout(key: &str) -> (i32, i32) {
+    match key {
+        "the_nether" | "the_end" => (0, 256),
+        _ => (MIN_CHUNK_Y, WORLD_HEIGHT),
+    }
+}
+
+#[napi]
+impl Universe {
+    /// Creates an empty universe for the version. Worlds are created as they're first asked for.
+    #[napi(constructor)]
+    pub fn new(version_string: String) -> Result<Self> {
+        let (registry, biomes) = load_registries(&version_string)?;
+        Ok(Universe {
+            registry: Arc::new(registry),
+            biomes: Arc::new(biomes),
+            worlds: DashMap::new(),
+            current: RwLock::new(None),
+        })
+    }
+
+    /// Gets the world of a dimension (e.g. `overworld`, `the_nether`, `the_end`), creating it if needed.
+    /// Every call returns a handle to the same world. A new world spans the dimension's Y range: `minY` and
+    /// `height` if given (from the dimension type the server sent), else the vanilla range for the Nether and the
+    /// End (0..256) and -64..320 for anything else. They're ignored for a world that already exists.
+    #[napi]
+    pub fn world(&self, name: String, min_y: Option<i32>, height: Option<u32>) -> Result<NapiWorld> {
+        let key = dimension_key(&name);
+        if let Some(world) = self.worlds.get(&key) { return Ok(world.same_world()); }
+
+        let (default_min_y, default_height) = dimension_layout(&key);
+        let mut config = WorldConfig::new(&self.registry.minecraft_version);
+        config.set_dimension(
+            min_y.unwrap_or(default_min_y),
+            height.map_or(default_height, |height| height.min(i32::MAX as u32) as i32),
+        )?;
+        let world = self.worlds.entry(key).or_insert_with(|| {
+            let mut world = NapiWorld::from_parts(ColumnStore::default(), Arc::clone(&self.registry), Arc::clone(&self.biomes));
+            world.config = Arc::new(config);
+            world
+        });
+        Ok(world.same_world())
+    }
+
+    #[napi]
+    pub fn has_world(&self, name: String) -> bool {
+        self.worlds.contains_key(&dimension_key(&name))
+    }
+
+    /// Names of the dimensions that have a world, sorted.
+    #[napi]
+    pub fn dimensions(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.worlds.iter().map(|entry| entry.key().clone()).collect();
+        names.sort();
+        names
+    }
+
+    /// Makes a dimension the current one (e.g. on a respawn packet after going through a portal) and returns its
+    /// world, creating it if needed (`minY` and `height` as for `world`). The previous dimension's world keeps its
+    /// columns; unload them or call `removeWorld` if they won't be needed again.
+    #[napi]
+    pub fn switch_dimension(&self, name: String, min_y: Option<i32>, height: Option<u32>) -> Result<NapiWorld> {
+        let world = self.world(name.clone(), min_y, height)?;
+        *self.current.write().unwrap() = Some(dimension_key(&name));
+        Ok(world)
+    }
+
+    /// Name of the current dimension, if `switchDimension` was called.
+    #[napi(getter)]
+    pub fn current_dimension(&self) -> Option<String> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// The current dimension's world, if any.
+    #[napi]
+    pub fn current_world(&self) -> Option<NapiWorld> {
+        let current = self.current.read().unwrap();
+        current.as_ref().and_then(|name| self.worlds.get(name)).map(|world| world.same_world())
+    }
+
+    /// Drops a dimension's world from the universe, stopping its prefetcher. Handles to it obtained earlier keep
+    /// working on their own. Returns false if there was no such world.
+    #[napi]
+    pub fn remove_world(&self, name: String) -> bool {
+        let key = dimension_key(&name);
+        let Some((_, world)) = self.worlds.remove(&key) else { return false; };
+        world.stop_prefetch();
+        let mut current = self.current.write().unwrap();
+        if current.as_deref() == Some(key.as_str()) { *current = None; }
+        true
+    }
+}
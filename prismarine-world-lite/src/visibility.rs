@@ -0,0 +1,66 @@
+// src/visibility.rs
+use crate::raycast::{AabbArg, RaycastSettings, Vec3Arg};
+use crate::world::NapiWorld;
+use glam::DVec3;
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VisibilityResult {
+    pub visible_fraction: f64, // 0 to 1
+    pub visible_samples: u32,
+    pub samples: u32,
+    pub first_visible_point: Option<Vec3Arg>, // In sample order, so the center when it's visible
+}
+
+// Element `index` of the Halton sequence in `base`, in 0..1
+fn halton(mut index: u32, base: u32) -> f64 {
+    let (mut result, mut fraction) = (0.0, 1.0);
+    while index > 0 {
+        fraction /= base as f64;
+        result += fraction * (index % base) as f64;
+        index /= base;
+    }
+    result
+}
+
+// Sample points spread evenly through a box: its center, then a low-discrepancy sequence filling the box, so
+// any prefix of the points covers it about as well as possible
+fn sample_points(min: DVec3, max: DVec3, count: u32) -> impl Iterator<Item = DVec3> {
+    (0..count).map(move |i| {
+        let t = if i == 0 { DVec3::splat(0.5) } else { DVec3::new(halton(i, 2), halton(i, 3), halton(i, 5)) };
+        min + (max - min) * t
+    })
+}
+
+#[napi]
+impl NapiWorld {
+    /// Casts rays from an eye position to `samples` points spread through a target box (its center first) and
+    /// reports which fraction of them no block hides, with the first visible point in sample order, e.g. to pick
+    /// a target and the point to aim at. Points farther than `maxDistance` (if given) count as hidden.
+    #[napi(ts_args_type = "eye: { x: number, y: number, z: number }, target_aabb: { min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number } }, samples: number, max_distance?: number | null")]
+    pub fn sample_visibility(&self, eye: Vec3Arg, target_aabb: AabbArg, samples: u32, max_distance: Option<f64>) -> VisibilityResult {
+        let eye = DVec3::from(eye);
+        let (min, max) = (DVec3::from(target_aabb.min), DVec3::from(target_aabb.max));
+        let samples = samples.max(1);
+
+        let mut visible_samples = 0;
+        let mut first_visible_point = None;
+        for point in sample_points(min.min(max), min.max(max), samples) {
+            let to_point = point - eye;
+            let distance = to_point.length();
+            if max_distance.is_some_and(|max_distance| distance > max_distance) { continue; }
+            let settings = RaycastSettings::new(distance);
+            if self.cast_ray(eye, to_point, &settings).is_none() {
+                visible_samples += 1;
+                first_visible_point.get_or_insert(point);
+            }
+        }
+        VisibilityResult {
+            visible_fraction: visible_samples as f64 / samples as f64,
+            visible_samples,
+            samples,
+            first_visible_point: first_visible_point.map(Vec3Arg::from),
+        }
+    }
+}
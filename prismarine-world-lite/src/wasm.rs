@@ -0,0 +1,108 @@
+// src/wasm.rs
+use crate::api::{ChunkCoords, DVec3, RaycastHit, RaycastSettings, World, WorldCoords};
+use wasm_bindgen::prelude::*;
+
+// wasm-bindgen facade over the Rust API, for web viewers and browser bots. Chunk parsing, palettes and the
+// raycaster are the exact code the Node binding runs; only the storage is simpler (single-threaded, no locks).
+
+/// A world of loaded chunk columns for one Minecraft version.
+#[wasm_bindgen(js_name = "World")]
+pub struct WasmWorld {
+    world: World,
+}
+
+/// The closest block a ray hit.
+#[wasm_bindgen(js_name = "RaycastHit")]
+#[derive(Debug, Clone, Copy)]
+pub struct WasmRaycastHit {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub face: u32, // Same numbering as the Node binding's `BlockFace`
+    #[wasm_bindgen(js_name = "pointX")]
+    pub point_x: f64,
+    #[wasm_bindgen(js_name = "pointY")]
+    pub point_y: f64,
+    #[wasm_bindgen(js_name = "pointZ")]
+    pub point_z: f64,
+    pub distance: f64,
+    #[wasm_bindgen(js_name = "enteredInside")]
+    pub entered_inside: bool,
+    #[wasm_bindgen(js_name = "exitedLoadedTerrain")]
+    pub exited_loaded_terrain: bool,
+}
+
+impl From<RaycastHit> for WasmRaycastHit {
+    fn from(hit: RaycastHit) -> Self {
+        WasmRaycastHit {
+            x: hit.position.x,
+            y: hit.position.y,
+            z: hit.position.z,
+            face: hit.face as u32,
+            point_x: hit.point.x,
+            point_y: hit.point.y,
+            point_z: hit.point.z,
+            distance: hit.t,
+            entered_inside: hit.entered_inside,
+            exited_loaded_terrain: hit.exited_loaded_terrain,
+        }
+    }
+}
+
+#[wasm_bindgen(js_class = "World")]
+impl WasmWorld {
+    /// Creates an empty world for a Minecraft version, e.g. `new World("1.21.1")`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(version: &str) -> Result<WasmWorld, JsError> {
+        World::new(version).map(|world| WasmWorld { world }).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Loads chunk column data from a network buffer (like `map_chunk` packet data), replacing any column already
+    /// loaded there. Data that ends early leaves the remaining sections empty.
+    #[wasm_bindgen(js_name = "loadColumn")]
+    pub fn load_column(&mut self, chunk_x: i32, chunk_z: i32, data: &[u8]) -> Result<(), JsError> {
+        self.world.load_column(ChunkCoords { x: chunk_x, z: chunk_z }, data).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "unloadColumn")]
+    pub fn unload_column(&mut self, chunk_x: i32, chunk_z: i32) {
+        self.world.unload_column(ChunkCoords { x: chunk_x, z: chunk_z });
+    }
+
+    #[wasm_bindgen(js_name = "isColumnLoaded")]
+    pub fn is_column_loaded(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        self.world.is_column_loaded(ChunkCoords { x: chunk_x, z: chunk_z })
+    }
+
+    /// Gets the block state ID at a position; blocks in unloaded columns read as air (0).
+    #[wasm_bindgen(js_name = "getBlockStateId")]
+    pub fn get_block_state_id(&self, x: i32, y: i32, z: i32) -> u32 {
+        self.world.block_state_id(WorldCoords { x, y, z }).unwrap_or(0)
+    }
+
+    /// Sets the block state ID at a position. Fails if the column isn't loaded.
+    #[wasm_bindgen(js_name = "setBlockStateId")]
+    pub fn set_block_state_id(&mut self, x: i32, y: i32, z: i32, state_id: u32) -> Result<(), JsError> {
+        self.world.set_block_state_id(WorldCoords { x, y, z }, state_id).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Casts a ray against solid blocks, like the Node binding's `raycast`. Blocks named in
+    /// `intersectNonSolidBlockNames` (names or `#` tags) are hit too. Unloaded columns are passed through.
+    /// Returns the closest hit within `maxDistance`, or undefined.
+    #[allow(clippy::too_many_arguments)]
+    pub fn raycast(
+        &self,
+        origin_x: f64, origin_y: f64, origin_z: f64,
+        direction_x: f64, direction_y: f64, direction_z: f64,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+    ) -> Option<WasmRaycastHit> {
+        let settings = RaycastSettings {
+            non_solid_exceptions: intersect_non_solid_block_names.unwrap_or_default().into_iter().collect(),
+            ..RaycastSettings::new(max_distance)
+        };
+        let origin = DVec3::new(origin_x, origin_y, origin_z);
+        let direction = DVec3::new(direction_x, direction_y, direction_z);
+        self.world.raycast(origin, direction, &settings).map(WasmRaycastHit::from)
+    }
+}
@@ -0,0 +1,73 @@
+// src/watchers.rs
+use crate::coords::WorldCoords;
+use crate::events::UpdateChannel;
+use crate::region::Region;
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+use std::sync::atomic::Ordering;
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct BlockUpdate {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub state_id: u32,
+}
+
+// A region of interest and the JS callback notified when blocks inside it change
+pub struct BlockWatcher {
+    region: Region,
+    pub(crate) channel: UpdateChannel,
+}
+
+impl NapiWorld {
+    fn add_watcher(&self, env: Env, region: Region, callback: JsFunction) -> Result<u32> {
+        let channel = UpdateChannel::new(env, callback)?;
+        let id = self.next_watcher_id.fetch_add(1, Ordering::Relaxed);
+        self.watchers.insert(id, BlockWatcher { region, channel });
+        Ok(id)
+    }
+
+    // Queues the changes for every watcher covering some of the changed positions
+    pub(crate) fn notify_watchers(&self, positions: &[WorldCoords]) {
+        if self.watchers.is_empty() { return; }
+
+        let (held, coalesce) = (self.events_held(), self.write_dedup.load(Ordering::Relaxed));
+        for watcher in self.watchers.iter() {
+            let updates: Vec<BlockUpdate> = positions
+                .iter()
+                .filter(|pos| watcher.region.contains(**pos))
+                .map(|pos| BlockUpdate { x: pos.x, y: pos.y, z: pos.z, state_id: self.get_block_state_id(pos.x, pos.y, pos.z) })
+                .collect();
+            if !updates.is_empty() {
+                watcher.channel.push(updates, held, coalesce);
+            }
+        }
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Calls `callback` with the new block whenever the block at the position changes. Returns a watcher ID for `unwatch`.
+    #[napi(ts_args_type = "x: number, y: number, z: number, callback: (updates: BlockUpdate[]) => void")]
+    pub fn watch_block(&self, env: Env, x: i32, y: i32, z: i32, callback: JsFunction) -> Result<u32> {
+        let pos = WorldCoords { x, y, z };
+        self.add_watcher(env, Region::new(pos, pos), callback)
+    }
+
+    /// Calls `callback` with the changed blocks whenever blocks inside the region (inclusive corners) change.
+    /// Changes are coalesced and delivered at most once per macrotask. Returns a watcher ID for `unwatch`.
+    #[napi(ts_args_type = "min: { x: number, y: number, z: number }, max: { x: number, y: number, z: number }, callback: (updates: BlockUpdate[]) => void")]
+    pub fn watch_region(&self, env: Env, min: WorldCoords, max: WorldCoords, callback: JsFunction) -> Result<u32> {
+        self.add_watcher(env, Region::new(min, max), callback)
+    }
+
+    /// Removes a watcher. Returns false if no watcher had this ID.
+    #[napi]
+    pub fn unwatch(&self, watcher_id: u32) -> bool {
+        self.watchers.remove(&watcher_id).is_some()
+    }
+}
@@ -1,47 +1,102 @@
 // src/world.rs
-use crate::chunk::ChunkColumn;
-use crate::coords::{ChunkCoords, WorldCoords, MIN_SECTION_Y, SECTION_COUNT};
-use crate::parsing::parse_chunk_section;
-use crate::raycast::{intersect_aabb, RaycastIterator, RaycastResult, Vec3Arg, BlockFace};
+use crate::chunk::{ChunkColumn, ChunkSection};
+use crate::anvil::RegionDir;
+use crate::chunk_batch::ChunkBatchState;
+use crate::chunk_keys::column_coords;
+use crate::cold::ColdColumns;
+use crate::config::WorldConfig;
+use crate::heightmap::Heightmap;
+use crate::perf::PerfCounters;
+use crate::coords::{ChunkCoords, SectionCoords, WorldCoords, SECTION_VOLUME};
+use crate::navmesh::ChunkNavmesh;
+use crate::occupancy::OccupancyCache;
+use crate::parsing::{parse_chunk_section, parse_column_sections};
+use crate::prefetch::Prefetcher;
+use crate::raycast::{find_ray_hits, find_ray_outcome, BlockFace, BlockSource, RayOutcome, RaycastHit, RaycastOutcome, RaycastResult, RaycastSettings, RaycastTrace, TraceVoxel, Vec3Arg};
+use crate::registry::{BiomeRegistry, BlockRegistry, ShapeBox};
+use crate::events::{BlockUpdateSubscriber, EventCenter};
+use crate::transaction::StagedEdits;
+use crate::watchers::BlockWatcher;
+use crate::writes::{DeferredEdits, UnloadedWriteMode, WriteStatus};
 use glam::DVec3;
-// Import CollisionShapeIds from the correct module
-use minecraft_data_rs::models::block_collision_shapes::CollisionShapeIds;
 use minecraft_data_rs::api::{versions_by_minecraft_version, Api};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use std::collections::HashSet;
 use std::io::Cursor;
-use std::sync::{Arc, RwLock};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
 use dashmap::DashMap;
 
+pub type ColumnMap = Arc<DashMap<ChunkCoords, Arc<RwLock<ChunkColumn>>>>;
+
+// A world's columns plus the state that has to stay in step with them, shared by every world operating on the same
+// columns (`from_columns`, `from_handle`): a column compressed by one of them must be found cold by the others, and
+// caches derived from blocks must drop what any of them overwrites.
+#[derive(Clone, Default)]
+pub struct ColumnStore {
+    pub(crate) columns: ColumnMap,
+    pub(crate) cold: Arc<ColdColumns>,
+    pub(crate) occupancy: Arc<OccupancyCache>,
+    pub(crate) heightmaps: Arc<DashMap<ChunkCoords, Heightmap>>,
+    // Set once columns are shared with a fork (see `fork`), so writes check whether they have to copy them first
+    pub(crate) forked: Arc<AtomicBool>,
+}
+
 #[napi(js_name = "World")]
 pub struct NapiWorld {
-    columns: Arc<DashMap<ChunkCoords, Arc<RwLock<ChunkColumn>>>>,
-    mc_data_api: Arc<Api>,
+    pub(crate) columns: ColumnMap,
+    pub(crate) registry: Arc<BlockRegistry>,
+    pub(crate) biomes: Arc<BiomeRegistry>,
+    pub(crate) navmeshes: Arc<DashMap<ChunkCoords, ChunkNavmesh>>,
+    // Sections changed since the last `take_dirty_sections`
+    pub(crate) dirty_sections: Arc<Mutex<HashSet<SectionCoords>>>,
+    pub(crate) watchers: Arc<DashMap<u32, BlockWatcher>>,
+    pub(crate) next_watcher_id: Arc<AtomicU32>, // Shared by watchers and event subscriptions
+    pub(crate) subscribers: Arc<DashMap<u32, BlockUpdateSubscriber>>,
+    pub(crate) event_center: Arc<RwLock<Option<EventCenter>>>,
+    pub(crate) event_holds: Arc<AtomicU32>,
+    pub(crate) transaction: Arc<Mutex<Option<StagedEdits>>>,
+    pub(crate) chunk_batch: Arc<Mutex<ChunkBatchState>>,
+    // Bedrock runtime ID -> state ID, for worlds loading Bedrock sub-chunks
+    pub(crate) bedrock_runtime_ids: Option<Arc<Vec<u32>>>,
+    // Region files columns are lazily read from, see `attach_region_dir`
+    pub(crate) region_dir: Arc<RwLock<Option<Arc<RegionDir>>>>,
+    pub(crate) prefetcher: Arc<Mutex<Option<Prefetcher>>>,
+    pub(crate) unloaded_writes: Arc<RwLock<UnloadedWriteMode>>,
+    pub(crate) deferred_edits: Arc<Mutex<DeferredEdits>>,
+    pub(crate) write_dedup: Arc<AtomicBool>, // See `set_write_dedup`
+    pub(crate) config: Arc<WorldConfig>,
+    pub(crate) perf: Arc<PerfCounters>,
+    pub(crate) cold: Arc<ColdColumns>,
+    // Cached per-column heights of the topmost solid blocks, see `surface_raycast`
+    pub(crate) heightmaps: Arc<DashMap<ChunkCoords, Heightmap>>,
+    pub(crate) occupancy: Arc<OccupancyCache>,
+    pub(crate) forked: Arc<AtomicBool>,
 }
 
 #[napi]
 impl NapiWorld {
     #[napi(factory)]
     pub fn with_version(version_string: String) -> Result<Self> {
-        let versions = versions_by_minecraft_version()
-            .map_err(|e| napi::Error::new(
-                napi::Status::GenericFailure,
-                format!("Failed to get Minecraft versions: {}", e)
-            ))?;
-
-        let version = versions.get(&version_string)
-            .ok_or_else(|| napi::Error::new(
-                napi::Status::InvalidArg,
-                format!("Unsupported Minecraft version: {}", version_string)
-            ))?;
+        let (registry, biomes) = load_registries(&version_string)?;
+        Ok(NapiWorld::from_parts(ColumnStore::default(), Arc::new(registry), Arc::new(biomes)))
+    }
 
-        let api = Api::new(version.clone());
+    /// Creates a world for the version operating on the column storage of another world (see `columns_handle`).
+    /// Both worlds see each other's loaded (and compressed) columns and block writes; events, watchers and caches
+    /// stay separate.
+    #[napi(factory)]
+    pub fn from_columns(version_string: String, columns: External<ColumnStore>) -> Result<Self> {
+        let (registry, biomes) = load_registries(&version_string)?;
+        Ok(NapiWorld::from_parts(ColumnStore::clone(&columns), Arc::new(registry), Arc::new(biomes)))
+    }
 
-        Ok(NapiWorld {
-            columns: Arc::new(DashMap::new()),
-            mc_data_api: Arc::new(api),
-        })
+    /// Gets a handle to this world's column storage, for `World.fromColumns`.
+    #[napi]
+    pub fn columns_handle(&self) -> External<ColumnStore> {
+        External::new(self.column_store())
     }
 
     /// Loads chunk column data from a network buffer (like `map_chunk` packet data).
@@ -53,50 +108,59 @@ impl NapiWorld {
         data_buffer: Buffer,
     ) -> Result<()> {
         let coords = ChunkCoords { x: chunk_x, z: chunk_z };
-        let mut cursor = Cursor::new(data_buffer.as_ref());
-        let mut column = ChunkColumn::new();
+        let started = Instant::now();
         eprintln!("[load_column] Loading chunk ({}, {}), Buffer length: {}", chunk_x, chunk_z, data_buffer.len());
+        let column = parse_column_sections(data_buffer.as_ref(), self.config.strict_parsing, self.config.section_range()).map_err(|e| {
+            eprintln!("Error parsing chunk ({}, {}): {}", chunk_x, chunk_z, e);
+            napi::Error::new(napi::Status::GenericFailure, e.to_string())
+        })?;
+
+        self.record_batch_chunk(started.elapsed());
+        self.perf.chunk_parsed(started.elapsed());
+        self.insert_column(coords, column);
+        Ok(())
+    }
 
-        for i in 0..SECTION_COUNT {
-             let section_y = MIN_SECTION_Y + i as i32;
-             let cursor_before = cursor.position();
-             // eprintln!("[load_column] Attempting to parse section y={}, cursor at: {}", section_y, cursor_before); // Optional
-
-             if cursor_before < cursor.get_ref().len() as u64 {
-                match parse_chunk_section(&mut cursor, section_y) { // Pass section_y
-                    Ok(section) => {
-                        // eprintln!("[load_column] Parsed section y={}, bytes read: {}", section_y, cursor.position() - cursor_before); // Optional
-                        column.insert_section(section_y, section);
-                    }
-                    Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
-                        eprintln!("[load_column] Reached EOF while parsing section y={}, stopping.", section_y);
-                        break;
-                    }
-                    Err(e) => {
-                         eprintln!("Error parsing section y={} for chunk ({}, {}): {}", section_y, chunk_x, chunk_z, e);
-                         return Err(napi::Error::new(
-                             napi::Status::GenericFailure,
-                             format!("Failed to parse chunk section at y={}: {}", section_y, e),
-                         ));
-                    }
-                }
-             } else {
-                 eprintln!("[load_column] No more data in buffer for section y={}, stopping.", section_y);
-                 break;
-             }
+    /// Replaces one section of a loaded column with a lone section payload (block count, block states, biomes),
+    /// as sent by protocols that resend single sections. Blocks that differ are reported like any other block change.
+    #[napi]
+    pub fn load_section(&self, chunk_x: i32, section_y: i32, chunk_z: i32, data_buffer: Buffer) -> Result<()> {
+        if !self.config.section_range().contains(&section_y) {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Section y={} is outside the world", section_y),
+            ));
         }
-        eprintln!("[load_column] Finished parsing sections for ({}, {}), final cursor at: {}", chunk_x, chunk_z, cursor.position());
+        let coords = ChunkCoords { x: chunk_x, z: chunk_z };
+        let mut cursor = Cursor::new(data_buffer.as_ref());
+        let section = parse_chunk_section(&mut cursor, section_y).map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to parse chunk section at y={}: {}", section_y, e),
+        ))?;
 
-        self.columns.insert(coords, Arc::new(RwLock::new(column)));
+        self.replace_section(coords, section_y, section)
+    }
+
+    /// Unloads a chunk column, given by coordinates or a `chunkKey`.
+    #[napi]
+    pub fn unload_column(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<()> {
+        self.remove_column(column_coords(chunk_x, chunk_z)?);
         Ok(())
     }
 
-    /// Unloads a chunk column.
+    /// Ensures a column is loaded at the coordinates (or `chunkKey`), inserting an empty (all air) one if missing.
+    /// Returns true if a column was created. Useful when serving chunks that were never received.
     #[napi]
-    pub fn unload_column(&self, chunk_x: i32, chunk_z: i32) {
-        let coords = ChunkCoords { x: chunk_x, z: chunk_z };
-        self.columns.remove(&coords);
-        // TODO: Emit chunkColumnUnload event via NAPI if needed
+    pub fn get_or_create_column(&self, chunk_x: Either<i32, BigInt>, chunk_z: Option<i32>) -> Result<bool> {
+        Ok(self.create_column_if_missing(column_coords(chunk_x, chunk_z)?))
+    }
+
+    /// Loads a column made of horizontal layers stacked from the bottom of the world (first layer lowest),
+    /// replacing any column already loaded there. Everything above the layers is air.
+    #[napi]
+    pub fn create_flat_column(&self, chunk_x: i32, chunk_z: i32, layers: Vec<FlatLayer>) {
+        let layers: Vec<(u32, u32)> = layers.iter().map(|l| (l.state_id, l.height)).collect();
+        self.insert_column(ChunkCoords { x: chunk_x, z: chunk_z }, ChunkColumn::from_layers(&layers));
     }
 
     /// Gets the state ID of the block at the given world coordinates.
@@ -104,7 +168,9 @@ impl NapiWorld {
     pub fn get_block_state_id(&self, x: i32, y: i32, z: i32) -> u32 {
         let coords = WorldCoords { x, y, z };
         let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
 
+        self.perf.block_get();
         // Use a read lock to access the column
         self.columns
             .get(&chunk_coords)
@@ -112,38 +178,31 @@ impl NapiWorld {
                 // Use try_read to avoid blocking if a write lock is held briefly
                 match entry.value().try_read() {
                     Ok(guard) => guard.get_block_state_id(coords),
-                    Err(_) => 0 // Or handle contention differently
+                    Err(_) => {
+                        self.perf.lock_contended();
+                        0 // Or handle contention differently
+                    }
                 }
             })
             .unwrap_or(0) // Default to air if chunk not loaded
     }
 
-    /// Sets the state ID of the block at the given world coordinates.
+    /// Sets the state ID of the block at the given world coordinates, returning whether the block changed.
+    /// While a transaction is open the write is staged until `commit`. Fails if the position is outside the world
+    /// or its chunk isn't loaded (see `try_set_block_state_id` and `set_unloaded_write_mode`).
     #[napi]
-    pub fn set_block_state_id(&self, x: i32, y: i32, z: i32, state_id: u32) -> Result<()> {
-        let coords = WorldCoords { x, y, z };
-        let chunk_coords = coords.to_chunk_coords();
-
-        // Use a write lock to modify the column
-        match self.columns.get_mut(&chunk_coords) {
-            Some(mut entry) => {
-                 // Use try_write to avoid blocking if a read lock is held briefly
-                match entry.value_mut().try_write() {
-                    Ok(mut guard) => {
-                        guard.set_block_state_id(coords, state_id);
-                         // TODO: Emit blockUpdate event via NAPI if needed
-                        Ok(())
-                    },
-                    Err(_) => Err(napi::Error::new(
-                        napi::Status::GenericFailure,
-                        "Failed to acquire write lock for chunk".to_string(),
-                    ))
-                }
-            }
-            None => Err(napi::Error::new(
+    pub fn set_block_state_id(&self, x: i32, y: i32, z: i32, state_id: u32) -> Result<bool> {
+        match self.write_block(WorldCoords { x, y, z }, state_id)?.status {
+            WriteStatus::OutOfWorld => Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Y {} is outside the world", y),
+            )),
+            WriteStatus::Unloaded => Err(napi::Error::new(
                 napi::Status::GenericFailure,
-                format!("Chunk at {}, {} not loaded", chunk_coords.x, chunk_coords.z),
+                format!("Chunk at {}, {} not loaded", x >> 4, z >> 4),
             )),
+            WriteStatus::Written => Ok(true),
+            WriteStatus::Unchanged | WriteStatus::Staged | WriteStatus::Deferred => Ok(false),
         }
     }
 
@@ -154,16 +213,12 @@ impl NapiWorld {
     pub fn get_block(&self, x: i32, y: i32, z: i32) -> Option<BlockInfo> {
          let coords = WorldCoords { x, y, z };
          let chunk_coords = coords.to_chunk_coords();
+         self.ensure_column(chunk_coords);
 
          self.columns.get(&chunk_coords).map(|entry| {
              // Use try_read for potentially better performance in read-heavy scenarios
              match entry.value().try_read() {
-                 Ok(column) => BlockInfo {
-                     state_id: column.get_block_state_id(coords),
-                     light: column.get_block_light(coords),
-                     sky_light: column.get_sky_light(coords),
-                     biome_id: column.get_biome_id(coords),
-                 },
+                 Ok(column) => block_info(&column, coords),
                  Err(_) => BlockInfo { // Return default/air if lock contended
                      state_id: 0,
                      light: 0,
@@ -174,11 +229,43 @@ impl NapiWorld {
          })
     }
 
+    /// Gets the block at the given world coordinates (like `get_block`) together with its six neighbors in one
+    /// call, reading across chunk borders with one lock per chunk, waiting out concurrent writes to them. Neighbors in
+    /// unloaded chunks are `null`, and so is the result if the block's own chunk isn't loaded.
+    #[napi]
+    pub fn get_block_with_neighbors(&self, x: i32, y: i32, z: i32) -> Option<BlockNeighbors> {
+        // The block itself, then its neighbors in BlockFace order
+        let origin = WorldCoords { x, y, z };
+        let positions: [WorldCoords; 7] =
+            std::array::from_fn(|i| if i == 0 { origin } else { BlockFace::ALL[i - 1].offset_position(origin, 1) });
+        let mut infos: [Option<BlockInfo>; 7] = Default::default();
+
+        let mut pending: Vec<usize> = (0..positions.len()).collect();
+        while let Some(&first) = pending.first() {
+            let chunk_coords = positions[first].to_chunk_coords();
+            let (in_chunk, rest): (Vec<usize>, Vec<usize>) = pending
+                .iter()
+                .partition(|&&i| positions[i].to_chunk_coords() == chunk_coords);
+            pending = rest;
+
+            self.ensure_column(chunk_coords);
+            let Some(entry) = self.columns.get(&chunk_coords) else { continue; };
+            let column = entry.value().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+            for i in in_chunk {
+                infos[i] = Some(block_info(&column, positions[i]));
+            }
+        }
+
+        let [block, bottom, top, north, south, west, east] = infos;
+        Some(BlockNeighbors { block: block?, bottom, top, north, south, west, east })
+    }
+
      /// Gets the block light level at the given world coordinates.
     #[napi]
     pub fn get_block_light(&self, x: i32, y: i32, z: i32) -> u8 {
         let coords = WorldCoords { x, y, z };
         let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
         self.columns
             .get(&chunk_coords)
             .map(|entry| match entry.value().try_read() {
@@ -193,6 +280,7 @@ impl NapiWorld {
     pub fn get_sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
          let coords = WorldCoords { x, y, z };
         let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
         self.columns
             .get(&chunk_coords)
             .map(|entry| match entry.value().try_read() {
@@ -207,6 +295,7 @@ impl NapiWorld {
     pub fn get_biome_id(&self, x: i32, y: i32, z: i32) -> u32 {
         let coords = WorldCoords { x, y, z };
         let chunk_coords = coords.to_chunk_coords();
+        self.ensure_column(chunk_coords);
         self.columns
             .get(&chunk_coords)
             .map(|entry| match entry.value().try_read() {
@@ -216,119 +305,418 @@ impl NapiWorld {
             .unwrap_or(0) // Default biome 0
     }
 
+    /// Gets the biome at the given world coordinates resolved through minecraft-data (id, name, temperature, rainfall).
+    /// Returns `null` if the chunk isn't loaded or the biome ID is unknown for this version.
+    #[napi]
+    pub fn get_biome(&self, x: i32, y: i32, z: i32) -> Option<BiomeInfo> {
+        let coords = WorldCoords { x, y, z };
+        if !self.ensure_column(coords.to_chunk_coords()) { return None; }
+
+        let biome_id = self.get_biome_id(x, y, z);
+        self.biomes.biome(biome_id).map(|biome| BiomeInfo {
+            id: biome.id,
+            name: biome.name.clone(),
+            temperature: biome.temperature as f64,
+            rainfall: biome.rainfall.unwrap_or(0.0) as f64,
+        })
+    }
+
 
     /// Performs a raycast from the origin in the given direction.
     ///
     /// Args:
     /// - `origin`: `{ x: number, y: number, z: number }` - The starting point of the ray.
     /// - `direction`: `{ x: number, y: number, z: number }` - The direction vector of the ray (should be normalized).
-    /// - `max_distance`: `number` - The maximum distance the ray should travel.
-    /// - `intersect_non_solid_block_names`: `string[] | null` - Optional array of block names (e.g., "water", "grass") to intersect even if they are not solid.
+    /// - `max_distance`: `number` - The maximum Euclidean distance from the origin to the hit point; anything further is ignored.
+    /// - `intersect_non_solid_block_names`: `string[] | null` - Optional array of block names (e.g., "water", "grass") or block tags (e.g., "#minecraft:flowers") to intersect even if they are not solid.
+    /// - `unloaded_chunks`: `'air' | 'solid' | 'stop' | null` - How blocks in unloaded chunks are treated (defaults to `'air'`).
+    ///   With `'solid'` they are hit like full cubes, with `'stop'` the ray ends at the first unloaded block and reports it.
+    /// - `min_y` / `max_y`: `number | null` - Optional inclusive block Y bounds within the dimension (its range by default); the ray stops once it leaves them for good.
     ///
     /// Returns:
-    /// - `object | null`: An object containing `position` (block coords), `face` (number), and `intersect_point` (exact coords), or `null` if no intersection is found within the distance.
-    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null")]
+    /// - `object | null`: An object containing `position` (block coords), `face` (number), `intersect_point` (exact coords),
+    ///   `exited_loaded_terrain` (whether the ray crossed an unloaded chunk), `distance` (from the origin to `intersect_point`),
+    ///   `entered_inside` (whether the origin is inside the hit box, e.g. standing in a snow layer; the hit is then at the origin),
+    ///   and `shape` (`index`, `min` and `max` of the collision box that was hit, relative to the block; `null` for blocks hit as
+    ///   full cubes),
+    ///   or `null` if no intersection is found within the distance. Use `raycastOutcome` to learn whether a ray that
+    ///   missed crossed unloaded chunks.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
     pub fn raycast(
         &self,
         origin_arg: Vec3Arg,
         direction_arg: Vec3Arg,
         max_distance: f64,
         intersect_non_solid_block_names: Option<Vec<String>>,
-    ) -> Option<RaycastResult> {
-        let origin = DVec3::from(origin_arg);
-        let direction = DVec3::from(direction_arg);
-
-        let direction = direction.normalize_or_zero();
-        if direction == DVec3::ZERO { return None; }
-
-        let inv_dir = DVec3::new(1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z);
-
-        let mut iterator = RaycastIterator::new(origin, direction, max_distance);
-        let non_solid_exceptions: HashSet<String> = intersect_non_solid_block_names
-            .unwrap_or_default()
-            .into_iter()
-            .collect();
-
-        // Load collision shapes data once
-        let collision_shapes_data = match self.mc_data_api.blocks.block_collision_shapes() {
-            Ok(data) => data,
-            Err(_) => return None, // Or handle error appropriately
-        };
-
-        let mut closest_hit: Option<(f64, BlockFace, WorldCoords)> = None;
-
-        while let Some((block_pos, _entered_face)) = iterator.next() {
-            let state_id = self.get_block_state_id(block_pos.x, block_pos.y, block_pos.z);
-            if state_id == 0 { continue; } // Skip air
-
-            // Use unwrap here assuming minecraft-data is consistent for the loaded version
-            if let Some(block_data) = self.mc_data_api.blocks.blocks_by_state_id().unwrap().get(&(state_id as u32)) {
-                // FIX 1: Use matches! for BoundingBox comparison
-                let is_solid = matches!(block_data.bounding_box, minecraft_data_rs::models::block::BoundingBox::Block);
-                let is_exception = non_solid_exceptions.contains(&block_data.name);
-
-                if is_solid || is_exception {
-                    let block_world_pos = DVec3::new(block_pos.x as f64, block_pos.y as f64, block_pos.z as f64);
-                    let mut hit_in_this_block = false;
-
-                    // FIX 2: Get shapes from collision_shapes_data
-                    if let Some(shape_ids) = collision_shapes_data.blocks.get(&block_data.name) {
-                        let ids_to_check = match shape_ids {
-                            CollisionShapeIds::Value(id) => vec![*id],
-                            CollisionShapeIds::Array(ids) => ids.clone(),
-                        };
-
-                        for shape_id in ids_to_check {
-                            if let Some(shape_vec) = collision_shapes_data.shapes.get(&shape_id) {
-                                for shape in shape_vec {
-                                    // Shape coords are relative 0-1, convert to world AABB
-                                    let aabb_min = block_world_pos + DVec3::new(shape[0] as f64, shape[1] as f64, shape[2] as f64);
-                                    let aabb_max = block_world_pos + DVec3::new(shape[3] as f64, shape[4] as f64, shape[5] as f64);
-
-                                    if let Some((t, face)) = intersect_aabb(aabb_min, aabb_max, origin, inv_dir) {
-                                        if t >= 0.0 && t * t * direction.length_squared() <= iterator.max_distance_sq { // Check distance using t
-                                            if closest_hit.is_none() || t < closest_hit.unwrap().0 {
-                                                closest_hit = Some((t, face, block_pos));
-                                                hit_in_this_block = true; // Mark that we found a hit within this block's shapes
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<Option<RaycastResult>> {
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        Ok(self.cast_ray(DVec3::from(origin_arg), DVec3::from(direction_arg), &settings).map(RaycastResult::from))
+    }
 
-                    // Fallback for solid blocks if no specific shapes were found or intersected
-                    // Only do this if we didn't already find a hit within specific shapes for this block
-                    if !hit_in_this_block && is_solid {
-                         let aabb_min = block_world_pos;
-                         let aabb_max = block_world_pos + DVec3::ONE;
-                         if let Some((t, face)) = intersect_aabb(aabb_min, aabb_max, origin, inv_dir) {
-                             if t >= 0.0 && t * t * direction.length_squared() <= iterator.max_distance_sq {
-                                 if closest_hit.is_none() || t < closest_hit.unwrap().0 {
-                                     closest_hit = Some((t, face, block_pos));
-                                     hit_in_this_block = true;
-                                 }
-                             }
-                         }
-                    }
+    /// Performs the same raycast as `raycast` (with the same arguments), returning `{ hit, exitedLoadedTerrain }`
+    /// so that a miss still tells whether the ray crossed unloaded chunks. `hit` is `null` on a miss.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
+    pub fn raycast_outcome(
+        &self,
+        origin_arg: Vec3Arg,
+        direction_arg: Vec3Arg,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<RaycastOutcome> {
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        let outcome = self.cast_ray_outcome(DVec3::from(origin_arg), DVec3::from(direction_arg), &settings, None);
+        Ok(RaycastOutcome {
+            hit: outcome.hits.into_iter().next().map(RaycastResult::from),
+            exited_loaded_terrain: outcome.exited_loaded_terrain,
+        })
+    }
 
-                    // Optimization: If the closest hit found is closer than the current ray position, stop.
-                    if hit_in_this_block && closest_hit.unwrap().0 < iterator.current_t {
-                        break; // Break outer loop
+    /// Performs the same raycast as `raycast` (with the same arguments), but also returns every voxel the ray
+    /// visited, in order, with the distance at which it entered the voxel and each box tested against the ray.
+    /// Meant for visualizing why a ray hit or missed something.
+    #[allow(clippy::too_many_arguments)]
+    #[napi(ts_args_type = "origin: { x: number, y: number, z: number }, direction: { x: number, y: number, z: number }, max_distance: number, intersect_non_solid_block_names?: string[] | null, unloaded_chunks?: 'air' | 'solid' | 'stop' | null, min_y?: number | null, max_y?: number | null")]
+    pub fn raycast_debug(
+        &self,
+        origin_arg: Vec3Arg,
+        direction_arg: Vec3Arg,
+        max_distance: f64,
+        intersect_non_solid_block_names: Option<Vec<String>>,
+        unloaded_chunks: Option<String>,
+        min_y: Option<i32>,
+        max_y: Option<i32>,
+    ) -> Result<RaycastTrace> {
+        let settings = RaycastSettings::from_args(max_distance, intersect_non_solid_block_names, unloaded_chunks, min_y, max_y)?;
+        let mut voxels = Vec::new();
+        let outcome = self.cast_ray_outcome(DVec3::from(origin_arg), DVec3::from(direction_arg), &settings, Some(&mut voxels));
+        Ok(RaycastTrace {
+            hit: outcome.hits.into_iter().next().map(RaycastResult::from),
+            exited_loaded_terrain: outcome.exited_loaded_terrain,
+            voxels,
+        })
+    }
+
+    /// Finds a point on the target block that is visible from `eye_pos`, for aiming at partially occluded blocks.
+    ///
+    /// Face centers are tried first, then points near the corners of each face turned towards the eye.
+    /// Returns `null` if every sampled point is blocked or further away than `max_distance` (defaults to unlimited).
+    #[napi(ts_args_type = "eye_pos: { x: number, y: number, z: number }, target_block: { x: number, y: number, z: number }, max_distance?: number | null")]
+    pub fn get_visible_point(&self, eye_pos: Vec3Arg, target_block: WorldCoords, max_distance: Option<f64>) -> Option<Vec3Arg> {
+        let eye = DVec3::from(eye_pos);
+        let block_origin = DVec3::new(target_block.x as f64, target_block.y as f64, target_block.z as f64);
+
+        let state_id = self.get_block_state_id(target_block.x, target_block.y, target_block.z);
+        let mut boxes: Vec<ShapeBox> = self.registry.collision_shapes(state_id).to_vec();
+        if boxes.is_empty() {
+            boxes.push([0.0, 0.0, 0.0, 1.0, 1.0, 1.0]); // Non-colliding blocks (torches, grass) are aimed at as a full cube
+        }
+
+        // Centers first so the common unobstructed case needs a single ray per face
+        let mut candidates = Vec::new();
+        for shape in &boxes {
+            let min = block_origin + DVec3::new(shape[0], shape[1], shape[2]);
+            let max = block_origin + DVec3::new(shape[3], shape[4], shape[5]);
+            for (normal, face_center) in aabb_face_centers(min, max) {
+                if normal.dot(eye - face_center) > 0.0 {
+                    candidates.push((0, face_center));
+                    for corner in face_sample_points(min, max, normal) {
+                        candidates.push((1, corner));
                     }
                 }
             }
         }
+        candidates.sort_by_key(|(pass, _)| *pass);
+
+        for (_, point) in candidates {
+            let to_point = point - eye;
+            let distance = to_point.length();
+            if max_distance.is_some_and(|max| distance > max) { continue; }
+
+            // Anything other than the target that is hit before the point occludes it
+            let settings = RaycastSettings::new(distance);
+            match self.cast_ray(eye, to_point, &settings) {
+                Some(hit) if hit.position != target_block && hit.t < distance - VISIBILITY_EPSILON => continue,
+                _ => return Some(point.into()),
+            }
+        }
+        None
+    }
+}
+
+// How far before the sampled point an obstruction is ignored, so the face the point lies on doesn't count
+const VISIBILITY_EPSILON: f64 = 1e-4;
+// How far sampled corner points are pulled towards the face center
+const FACE_SAMPLE_INSET: f64 = 0.1;
+
+// Outward normal and center point of each face of an AABB
+fn aabb_face_centers(min: DVec3, max: DVec3) -> [(DVec3, DVec3); 6] {
+    let center = (min + max) * 0.5;
+    [
+        (DVec3::NEG_Y, DVec3::new(center.x, min.y, center.z)),
+        (DVec3::Y, DVec3::new(center.x, max.y, center.z)),
+        (DVec3::NEG_Z, DVec3::new(center.x, center.y, min.z)),
+        (DVec3::Z, DVec3::new(center.x, center.y, max.z)),
+        (DVec3::NEG_X, DVec3::new(min.x, center.y, center.z)),
+        (DVec3::X, DVec3::new(max.x, center.y, center.z)),
+    ]
+}
+
+// Four points near the corners of the face with the given outward normal, inset from its edges
+fn face_sample_points(min: DVec3, max: DVec3, normal: DVec3) -> [DVec3; 4] {
+    let inset = (max - min) * FACE_SAMPLE_INSET;
+    let (lo, hi) = (min + inset, max - inset);
+    // The coordinates of the face plane itself
+    let face = if normal.max_element() > 0.0 { max } else { min };
+    if normal.x != 0.0 {
+        [DVec3::new(face.x, lo.y, lo.z), DVec3::new(face.x, lo.y, hi.z), DVec3::new(face.x, hi.y, lo.z), DVec3::new(face.x, hi.y, hi.z)]
+    } else if normal.y != 0.0 {
+        [DVec3::new(lo.x, face.y, lo.z), DVec3::new(lo.x, face.y, hi.z), DVec3::new(hi.x, face.y, lo.z), DVec3::new(hi.x, face.y, hi.z)]
+    } else {
+        [DVec3::new(lo.x, lo.y, face.z), DVec3::new(lo.x, hi.y, face.z), DVec3::new(hi.x, lo.y, face.z), DVec3::new(hi.x, hi.y, face.z)]
+    }
+}
+
+impl BlockSource for NapiWorld {
+    fn block_state_id(&self, pos: WorldCoords) -> Option<u32> {
+        self.ensure_column(pos.to_chunk_coords()).then(|| self.get_block_state_id(pos.x, pos.y, pos.z))
+    }
+
+    fn section_occupancy(&self, section: SectionCoords) -> Option<u64> {
+        NapiWorld::section_occupancy(self, section)
+    }
+
+    fn y_range(&self) -> (i32, i32) {
+        (self.config.min_y, self.config.min_y + self.config.height - 1)
+    }
+}
+
+pub(crate) fn load_registries(version_string: &str) -> Result<(BlockRegistry, BiomeRegistry)> {
+    let versions = versions_by_minecraft_version()
+        .map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to get Minecraft versions: {}", e)
+        ))?;
+
+    let version = versions.get(version_string)
+        .ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Unsupported Minecraft version: {}", version_string)
+        ))?;
+
+    let api = Api::new(version.clone());
+    let registry = BlockRegistry::load(&api)
+        .map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to load block data for {}: {}", version_string, e)
+        ))?;
+    let biomes = BiomeRegistry::load(&api)
+        .map_err(|e| napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to load biome data for {}: {}", version_string, e)
+        ))?;
+    Ok((registry, biomes))
+}
+
+impl NapiWorld {
+    pub(crate) fn from_parts(store: ColumnStore, registry: Arc<BlockRegistry>, biomes: Arc<BiomeRegistry>) -> Self {
+        let config = Arc::new(WorldConfig::new(&registry.minecraft_version));
+        NapiWorld {
+            columns: store.columns,
+            registry,
+            biomes,
+            navmeshes: Arc::new(DashMap::new()),
+            dirty_sections: Arc::new(Mutex::new(HashSet::new())),
+            watchers: Arc::new(DashMap::new()),
+            next_watcher_id: Arc::new(AtomicU32::new(1)),
+            subscribers: Arc::new(DashMap::new()),
+            event_center: Arc::new(RwLock::new(None)),
+            event_holds: Arc::new(AtomicU32::new(0)),
+            transaction: Arc::new(Mutex::new(None)),
+            chunk_batch: Arc::new(Mutex::new(ChunkBatchState::default())),
+            bedrock_runtime_ids: None,
+            region_dir: Arc::new(RwLock::new(None)),
+            prefetcher: Arc::new(Mutex::new(None)),
+            unloaded_writes: Arc::new(RwLock::new(UnloadedWriteMode::default())),
+            deferred_edits: Arc::new(Mutex::new(DeferredEdits::new())),
+            write_dedup: Arc::new(AtomicBool::new(false)),
+            config,
+            perf: Arc::new(PerfCounters::default()),
+            cold: store.cold,
+            heightmaps: store.heightmaps,
+            occupancy: store.occupancy,
+            forked: store.forked,
+        }
+    }
+
+    // The columns and the state kept in step with them, for other worlds to operate on
+    pub(crate) fn column_store(&self) -> ColumnStore {
+        ColumnStore {
+            columns: Arc::clone(&self.columns),
+            cold: Arc::clone(&self.cold),
+            occupancy: Arc::clone(&self.occupancy),
+            heightmaps: Arc::clone(&self.heightmaps),
+            forked: Arc::clone(&self.forked),
+        }
+    }
+
+    // Another handle to this world sharing all of its state, for work on background threads
+    pub(crate) fn same_world(&self) -> NapiWorld {
+        NapiWorld {
+            columns: Arc::clone(&self.columns),
+            registry: Arc::clone(&self.registry),
+            biomes: Arc::clone(&self.biomes),
+            navmeshes: Arc::clone(&self.navmeshes),
+            dirty_sections: Arc::clone(&self.dirty_sections),
+            watchers: Arc::clone(&self.watchers),
+            next_watcher_id: Arc::clone(&self.next_watcher_id),
+            subscribers: Arc::clone(&self.subscribers),
+            event_center: Arc::clone(&self.event_center),
+            event_holds: Arc::clone(&self.event_holds),
+            transaction: Arc::clone(&self.transaction),
+            chunk_batch: Arc::clone(&self.chunk_batch),
+            bedrock_runtime_ids: self.bedrock_runtime_ids.clone(),
+            region_dir: Arc::clone(&self.region_dir),
+            prefetcher: Arc::clone(&self.prefetcher),
+            unloaded_writes: Arc::clone(&self.unloaded_writes),
+            deferred_edits: Arc::clone(&self.deferred_edits),
+            write_dedup: Arc::clone(&self.write_dedup),
+            config: Arc::clone(&self.config),
+            perf: Arc::clone(&self.perf),
+            cold: Arc::clone(&self.cold),
+            heightmaps: Arc::clone(&self.heightmaps),
+            occupancy: Arc::clone(&self.occupancy),
+            forked: Arc::clone(&self.forked),
+        }
+    }
+
+    // Swaps in a new section for a loaded column, reporting the blocks that differ like any other block change
+    pub(crate) fn replace_section(&self, coords: ChunkCoords, section_y: i32, section: ChunkSection) -> Result<()> {
+        let (chunk_x, chunk_z) = (coords.x, coords.z);
+        let mut changed = Vec::new();
+        {
+            self.ensure_column(coords);
+            self.unshare_column(coords);
+            let entry = self.columns.get(&coords).ok_or_else(|| napi::Error::new(
+                napi::Status::GenericFailure,
+                format!("Chunk at {}, {} not loaded", chunk_x, chunk_z),
+            ))?;
+            let mut column = entry.value().try_write().map_err(|_| napi::Error::new(
+                napi::Status::GenericFailure,
+                "Failed to acquire write lock for chunk".to_string(),
+            ))?;
 
-        // Construct result from the closest hit found
-        closest_hit.map(|(t, face, block_pos)| {
-            RaycastResult {
-                position: block_pos,
-                face: face as u32,
-                intersect_point: (origin + direction * t).into(),
+            let old = column.get_section(section_y);
+            for index in 0..SECTION_VOLUME {
+                let old_state = old.map_or(0, |s| s.block_states().get(index));
+                if old_state != section.block_states().get(index) {
+                    changed.push(WorldCoords {
+                        x: (chunk_x << 4) + (index & 15) as i32,
+                        y: (section_y << 4) + (index >> 8) as i32,
+                        z: (chunk_z << 4) + ((index >> 4) & 15) as i32,
+                    });
+                }
             }
-        })
+            column.insert_section(section_y, section);
+        }
+
+        // Biomes may have changed too, even when no block did
+        self.mark_sections_dirty([SectionCoords { x: chunk_x, y: section_y, z: chunk_z }]);
+        self.blocks_changed(&changed);
+        Ok(())
+    }
+
+    // Stores a freshly built column, replacing any column already loaded there, and applies the writes deferred
+    // until it arrived
+    pub(crate) fn insert_column(&self, coords: ChunkCoords, column: ChunkColumn) {
+        if !self.columns.contains_key(&coords) {
+            self.enforce_column_limit(coords);
+        }
+        self.columns.insert(coords, Arc::new(RwLock::new(column)));
+        self.column_inserted(coords);
+    }
+
+    // Bookkeeping after a column was put into the map at `coords`
+    fn column_inserted(&self, coords: ChunkCoords) {
+        self.cold.forget(coords);
+        self.cold.touch(coords);
+        self.heightmaps.remove(&coords);
+        self.forget_column_occupancy(coords);
+        self.invalidate_navmeshes_near(coords);
+        self.mark_column_dirty(coords);
+        self.apply_deferred_edits(coords);
+        self.maybe_compress_idle_columns();
+    }
+
+    pub(crate) fn remove_column(&self, coords: ChunkCoords) {
+        self.columns.remove(&coords);
+        self.cold.forget(coords);
+        self.heightmaps.remove(&coords);
+        self.forget_column_occupancy(coords);
+        self.invalidate_navmeshes_near(coords);
+        self.forget_dirty_column(coords);
+        // TODO: Emit chunkColumnUnload event via NAPI if needed
+    }
+
+    // Loads an empty (all air) column at `coords` unless one is loaded or can be loaded from cold storage or
+    // region files; true if it was created
+    pub(crate) fn create_column_if_missing(&self, coords: ChunkCoords) -> bool {
+        if self.ensure_column(coords) { return false; }
+        self.enforce_column_limit(coords);
+        // Another thread may create or load the column in between; only the one inserting it reports it created
+        let mut created = false;
+        self.columns.entry(coords).or_insert_with(|| {
+            created = true;
+            Arc::new(RwLock::new(ChunkColumn::new()))
+        });
+        if created { self.column_inserted(coords); }
+        created
+    }
+
+    // Called after blocks were written, once no column locks are held anymore
+    pub(crate) fn blocks_changed(&self, positions: &[WorldCoords]) {
+        self.update_navmeshes(positions);
+        self.update_heightmaps(positions);
+        self.update_occupancy(positions);
+        self.mark_blocks_dirty(positions);
+        if self.config.emit_events {
+            self.notify_watchers(positions);
+            self.emit_block_updates(positions);
+        }
+    }
+
+    // Core block raycast shared by the public raycasting APIs
+    pub(crate) fn cast_ray(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings) -> Option<RaycastHit> {
+        self.cast_ray_traced(origin, direction, settings, None)
+    }
+
+    // `cast_ray`, optionally recording every visited voxel and tested box into `trace`
+    pub(crate) fn cast_ray_traced(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings, trace: Option<&mut Vec<TraceVoxel>>) -> Option<RaycastHit> {
+        self.cast_ray_hits(origin, direction, settings, trace).into_iter().next()
+    }
+
+    // Block raycast core: the closest hit, or with `return_all` the closest hit in every block along the ray, nearest
+    // first
+    pub(crate) fn cast_ray_hits(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings, trace: Option<&mut Vec<TraceVoxel>>) -> Vec<RaycastHit> {
+        let started = Instant::now();
+        let hits = find_ray_hits(self, &self.registry, origin, direction, settings, trace);
+        self.perf.raycast(started.elapsed());
+        hits
+    }
+
+    // `cast_ray_hits`, also telling whether the ray crossed unloaded chunks
+    pub(crate) fn cast_ray_outcome(&self, origin: DVec3, direction: DVec3, settings: &RaycastSettings, trace: Option<&mut Vec<TraceVoxel>>) -> RayOutcome {
+        let started = Instant::now();
+        let outcome = find_ray_outcome(self, &self.registry, origin, direction, settings, trace);
+        self.perf.raycast(started.elapsed());
+        outcome
     }
 }
 
@@ -339,4 +727,68 @@ pub struct BlockInfo {
     pub light: u8,
     pub sky_light: u8,
     pub biome_id: u32,
-}
\ No newline at end of file
+}
+
+fn block_info(column: &ChunkColumn, coords: WorldCoords) -> BlockInfo {
+    BlockInfo {
+        state_id: column.get_block_state_id(coords),
+        light: column.get_block_light(coords),
+        sky_light: column.get_sky_light(coords),
+        biome_id: column.get_biome_id(coords),
+    }
+}
+
+// A block and the blocks touching each of its faces
+#[napi(object)]
+pub struct BlockNeighbors {
+    pub block: BlockInfo,
+    pub bottom: Option<BlockInfo>,
+    pub top: Option<BlockInfo>,
+    pub north: Option<BlockInfo>,
+    pub south: Option<BlockInfo>,
+    pub west: Option<BlockInfo>,
+    pub east: Option<BlockInfo>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct FlatLayer {
+    pub state_id: u32,
+    pub height: u32,
+}
+
+// Biome details resolved from the registry
+#[napi(object)]
+pub struct BiomeInfo {
+    pub id: u32,
+    pub name: String,
+    pub temperature: f64,
+    pub rainfall: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn neighbors_in_chunks_being_written_are_waited_for() {
+        let world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(test_registry()), Arc::new(test_biomes()));
+        let mut column = ChunkColumn::new();
+        column.set_block_state_id(WorldCoords { x: 16, y: 70, z: 0 }, 1);
+        world.insert_column(ChunkCoords { x: 0, z: 0 }, ChunkColumn::new());
+        world.insert_column(ChunkCoords { x: 1, z: 0 }, column);
+
+        let neighbor = Arc::clone(world.columns.get(&ChunkCoords { x: 1, z: 0 }).unwrap().value());
+        let writing = neighbor.write().unwrap();
+        let neighbors = thread::scope(|scope| {
+            let reader = scope.spawn(|| world.get_block_with_neighbors(15, 70, 0));
+            thread::sleep(Duration::from_millis(50));
+            drop(writing);
+            reader.join().unwrap()
+        });
+        assert_eq!(neighbors.unwrap().east.map(|info| info.state_id), Some(1));
+    }
+}
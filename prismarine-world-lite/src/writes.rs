@@ -0,0 +1,240 @@
+// src/writes.rs
+use crate::chunk::ChunkColumn;
+use crate::coords::{ChunkCoords, WorldCoords};
+use crate::world::NapiWorld;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+// Writes to chunks that weren't loaded yet, by chunk in the order they were made, see `UnloadedWriteMode::Defer`
+pub type DeferredEdits = HashMap<ChunkCoords, Vec<(WorldCoords, u32)>>;
+
+// What block writes to chunks that aren't loaded do
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnloadedWriteMode {
+    #[default]
+    Reject, // Report the chunk as unloaded
+    Create, // Load an empty (all air) column and write into it
+    Defer,  // Keep the write and apply it once the chunk is loaded
+}
+
+impl UnloadedWriteMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "reject" => Some(UnloadedWriteMode::Reject),
+            "create" => Some(UnloadedWriteMode::Create),
+            "defer" => Some(UnloadedWriteMode::Defer),
+            _ => None,
+        }
+    }
+}
+
+// What happened to a block write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStatus {
+    Written,
+    Unchanged,
+    Staged, // An open transaction holds it until `commit`
+    Deferred,
+    OutOfWorld,
+    Unloaded,
+}
+
+impl WriteStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            WriteStatus::Written => "written",
+            WriteStatus::Unchanged => "unchanged",
+            WriteStatus::Staged => "staged",
+            WriteStatus::Deferred => "deferred",
+            WriteStatus::OutOfWorld => "outOfWorld",
+            WriteStatus::Unloaded => "unloaded",
+        }
+    }
+}
+
+// Outcome of `write_block`
+#[derive(Debug, Clone, Copy)]
+pub struct BlockWrite {
+    pub status: WriteStatus,
+    pub previous_state_id: Option<u32>, // State the block had, for written and unchanged blocks
+    pub created_column: bool,
+}
+
+impl BlockWrite {
+    fn status(status: WriteStatus) -> Self {
+        BlockWrite { status, previous_state_id: None, created_column: false }
+    }
+}
+
+/// What happened to a block write.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct BlockWriteResult {
+    pub status: String, // "written", "unchanged", "staged" (open transaction), "deferred", "outOfWorld" or "unloaded"
+    pub previous_state_id: Option<u32>, // State the block had, for "written" and "unchanged"
+    pub created_column: bool, // An empty column was created for the write (see `set_unloaded_write_mode`)
+}
+
+impl From<BlockWrite> for BlockWriteResult {
+    fn from(write: BlockWrite) -> Self {
+        BlockWriteResult {
+            status: write.status.name().to_string(),
+            previous_state_id: write.previous_state_id,
+            created_column: write.created_column,
+        }
+    }
+}
+
+impl NapiWorld {
+    // Core of the block writing APIs: applies a write unless it's outside the world or its chunk isn't loaded (and
+    // can't be created), staging it instead while a transaction is open
+    pub(crate) fn write_block(&self, coords: WorldCoords, state_id: u32) -> Result<BlockWrite> {
+        self.perf.block_set();
+        if !self.config.contains_y(coords.y) {
+            return Ok(BlockWrite::status(WriteStatus::OutOfWorld));
+        }
+
+        let chunk_coords = coords.to_chunk_coords();
+        let mut created_column = false;
+        if !self.ensure_column(chunk_coords) {
+            match *self.unloaded_writes.read().unwrap() {
+                UnloadedWriteMode::Reject => return Ok(BlockWrite::status(WriteStatus::Unloaded)),
+                // A column loaded meanwhile (by a packet or the prefetcher) is kept and written into
+                UnloadedWriteMode::Create => created_column = self.create_column_if_missing(chunk_coords),
+                UnloadedWriteMode::Defer => {
+                    self.deferred_edits.lock().unwrap().entry(chunk_coords).or_default().push((coords, state_id));
+                    return Ok(BlockWrite::status(WriteStatus::Deferred));
+                }
+            }
+        }
+
+        if let Some(staged) = self.stage_block_write(coords, state_id) {
+            return staged.map(|_| BlockWrite { created_column, ..BlockWrite::status(WriteStatus::Staged) });
+        }
+
+        let previous_state_id = {
+            self.unshare_column(chunk_coords);
+            let Some(entry) = self.columns.get(&chunk_coords) else {
+                return Ok(BlockWrite::status(WriteStatus::Unloaded)); // Unloaded by another thread meanwhile
+            };
+            let mut column = entry.value().try_write().map_err(|_| {
+                self.perf.lock_contended();
+                napi::Error::new(napi::Status::GenericFailure, "Failed to acquire write lock for chunk".to_string())
+            })?;
+            let previous_state_id = column.get_block_state_id(coords);
+            if previous_state_id != state_id {
+                column.set_block_state_id(coords, state_id);
+            }
+            previous_state_id
+        };
+
+        // The column entry is released by now, so change handlers can read the world again
+        let status = if previous_state_id == state_id { WriteStatus::Unchanged } else { WriteStatus::Written };
+        if status == WriteStatus::Written {
+            self.blocks_changed(&[coords]);
+        }
+        Ok(BlockWrite { previous_state_id: Some(previous_state_id), created_column, ..BlockWrite::status(status) })
+    }
+
+    // Applies a column's writes in order, returning each block that changed once. With write dedup on, blocks
+    // written back to the state they started from (e.g. A -> B -> A) don't count as changed.
+    pub(crate) fn apply_writes(&self, column: &mut ChunkColumn, writes: &[(WorldCoords, u32)]) -> Vec<WorldCoords> {
+        let mut before = HashMap::new(); // State of each changed block before the first write
+        for &(pos, state_id) in writes {
+            let previous = column.get_block_state_id(pos);
+            if previous != state_id {
+                before.entry(pos).or_insert(previous);
+                column.set_block_state_id(pos, state_id);
+            }
+        }
+        if !self.write_dedup.load(Ordering::Relaxed) {
+            return before.into_keys().collect();
+        }
+        before.into_iter().filter(|&(pos, state_id)| column.get_block_state_id(pos) != state_id).map(|(pos, _)| pos).collect()
+    }
+
+    // Applies the writes deferred for a column that was just loaded, reporting the blocks that changed
+    pub(crate) fn apply_deferred_edits(&self, coords: ChunkCoords) {
+        let Some(edits) = self.deferred_edits.lock().unwrap().remove(&coords) else { return; };
+        let changed = {
+            self.unshare_column(coords);
+            let Some(entry) = self.columns.get(&coords) else { return; };
+            let mut column = entry.value().write().unwrap();
+            self.apply_writes(&mut column, &edits)
+        };
+        self.blocks_changed(&changed);
+    }
+}
+
+#[napi]
+impl NapiWorld {
+    /// Sets a block like `set_block_state_id`, but reports what happened instead of failing: whether the block
+    /// changed, was staged by an open transaction, or couldn't be written because it's outside the world or its
+    /// chunk isn't loaded.
+    #[napi]
+    pub fn try_set_block_state_id(&self, x: i32, y: i32, z: i32, state_id: u32) -> Result<BlockWriteResult> {
+        self.write_block(WorldCoords { x, y, z }, state_id).map(BlockWriteResult::from)
+    }
+
+    /// Sets what block writes to chunks that aren't loaded do: `'reject'` (the default) fails them, `'create'`
+    /// loads an empty (all air) column there first, for worlds built from block writes alone, and `'defer'` keeps
+    /// them until the chunk is loaded and then applies them on top of its data, for block updates that arrive
+    /// before their chunk.
+    #[napi(ts_args_type = "mode: 'reject' | 'create' | 'defer'")]
+    pub fn set_unloaded_write_mode(&self, mode: String) -> Result<()> {
+        let mode = UnloadedWriteMode::from_name(&mode).ok_or_else(|| napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Unknown unloaded write mode: {}", mode),
+        ))?;
+        *self.unloaded_writes.write().unwrap() = mode;
+        Ok(())
+    }
+
+    /// Turns write deduplication on or off (off by default), for servers applying updates that echo their own
+    /// writes back. Writes of the state a block already has never count as changes; with deduplication on, blocks
+    /// that a transaction commit or a chunk's deferred writes set back to the state they started from aren't
+    /// reported either, and a block updated several times before its watchers and subscribers get the updates is
+    /// delivered once, with its latest state.
+    #[napi]
+    pub fn set_write_dedup(&self, enabled: bool) {
+        self.write_dedup.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Counts the deferred writes still waiting for their chunk to load.
+    #[napi]
+    pub fn get_deferred_edit_count(&self) -> u32 {
+        self.deferred_edits.lock().unwrap().values().map(|edits| edits.len() as u32).sum()
+    }
+
+    /// Drops the deferred writes waiting for one chunk, or for every chunk if none is given. Returns how many
+    /// writes were dropped.
+    #[napi]
+    pub fn clear_deferred_edits(&self, chunk_x: Option<i32>, chunk_z: Option<i32>) -> u32 {
+        let mut deferred = self.deferred_edits.lock().unwrap();
+        match (chunk_x, chunk_z) {
+            (Some(x), Some(z)) => deferred.remove(&ChunkCoords { x, z }).map_or(0, |edits| edits.len() as u32),
+            _ => deferred.drain().map(|(_, edits)| edits.len() as u32).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_support::{test_biomes, test_registry};
+    use crate::world::ColumnStore;
+    use std::sync::Arc;
+
+    #[test]
+    fn create_mode_keeps_columns_that_are_already_there() {
+        let world = NapiWorld::from_parts(ColumnStore::default(), Arc::new(test_registry()), Arc::new(test_biomes()));
+        world.set_unloaded_write_mode("create".to_string()).unwrap();
+        let first = world.try_set_block_state_id(1, 64, 1, 1).unwrap();
+        assert!(first.created_column);
+        let second = world.try_set_block_state_id(2, 64, 2, 2).unwrap();
+        assert!(!second.created_column);
+        assert_eq!((world.get_block_state_id(1, 64, 1), world.get_block_state_id(2, 64, 2)), (1, 2));
+    }
+}